@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     control_statements::{
         AstControlStatement, CaseStatement, ForLoopStatement, IfStatement, LoopStatement, ReturnStatement,
+        TryStatement,
     },
     literals::{AstLiteral, StringValue},
     pre_processor,
@@ -27,12 +28,37 @@ pub type AstId = usize;
 pub struct GenericBinding {
     pub name: String,
     pub nature: TypeNature,
+    /// the concrete types the generic is additionally constrained to, e.g. `INT` and `DINT` for
+    /// `T : {INT, DINT}`. Empty if the generic is only constrained by its nature
+    pub constraint_types: Vec<String>,
+}
+
+impl GenericBinding {
+    /// renders the binding as it is declared, e.g. `T: ANY_INT {INT, DINT}`
+    pub fn signature_string(&self) -> String {
+        let mut signature = format!("{}: {}", self.name, self.nature.keyword());
+        if !self.constraint_types.is_empty() {
+            signature.push_str(&format!(" {{{}}}", self.constraint_types.join(", ")));
+        }
+        signature
+    }
 }
 
 #[derive(PartialEq)]
 pub struct Pou {
     pub id: AstId,
     pub name: String,
+    /// the namespace of a POU declared with a qualified name (e.g. `A.B` for `FUNCTION A.B.foo`), the
+    /// `name` is always fully qualified
+    pub namespace: Option<String>,
+    /// the declaration keyword as written in the source (e.g. `Function`), only recorded if the
+    /// parser is asked to preserve keyword casing
+    pub keyword: Option<String>,
+    /// the comments directly preceding the declaration without their delimiters, e.g. `adds two numbers`
+    /// for `(* adds two numbers *)`. A comment on the line ending the previous declaration is not included
+    pub doc_comment: Option<String>,
+    /// the `{attribute 'name'}` pragmas preceding the declaration, e.g. `{attribute 'enable_init'}`
+    pub attributes: Vec<Attribute>,
     pub kind: PouType,
     pub variable_blocks: Vec<VariableBlock>,
     pub return_type: Option<DataTypeDeclaration>,
@@ -51,6 +77,25 @@ pub struct Pou {
 
     /// A list of properties this POU contains
     pub properties: Vec<PropertyBlock>,
+
+    /// The explicitly declared `EN : BOOL` input of a function block, only recorded if the parser is
+    /// asked to recognize enable parameters
+    pub enable: Option<Identifier>,
+    /// The explicitly declared `ENO : BOOL` output of a function block, see [`Pou::enable`]
+    pub enable_out: Option<Identifier>,
+    /// The task a program is associated with by a `WITH` clause, e.g. `PROGRAM p WITH fast : main;`
+    pub task: Option<TaskAssociation>,
+    /// whether the function is marked as free of side effects by a `{pure}` pragma
+    pub is_pure: bool,
+}
+
+/// The `WITH task : program_type` clause of a program, associating it with a task of its configuration
+#[derive(Debug, PartialEq, Clone)]
+pub struct TaskAssociation {
+    pub task: Identifier,
+    /// The type of the program instance, e.g. `main` for `PROGRAM p WITH fast : main;`
+    pub program_type: Option<Identifier>,
+    pub location: SourceLocation,
 }
 
 #[derive(Debug, PartialEq)]
@@ -63,6 +108,14 @@ pub struct Interface {
     pub properties: Vec<PropertyBlock>,
 }
 
+impl Interface {
+    /// returns the unqualified names of the methods declared directly in this interface, methods
+    /// inherited via `EXTENDS` are not included
+    pub fn method_names(&self) -> Vec<&str> {
+        self.methods.iter().map(|it| it.get_return_name()).collect()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct Identifier {
     pub name: String,
@@ -75,6 +128,8 @@ pub struct PropertyBlock {
     pub ident: Identifier,
     pub datatype: DataTypeDeclaration,
     pub implementations: Vec<PropertyImplementation>,
+    /// Whether the property was declared with `OVERRIDE`, refining a property of the super class
+    pub overriding: bool,
 }
 
 impl Eq for PropertyBlock {}
@@ -96,6 +151,18 @@ pub struct PropertyImplementation {
     pub end_location: SourceLocation,
 }
 
+impl PropertyImplementation {
+    /// Returns the statements making up the body of this getter or setter
+    pub fn statements(&self) -> &[AstNode] {
+        &self.body
+    }
+
+    /// Returns true if this getter or setter has no statements
+    pub fn is_empty(&self) -> bool {
+        self.body.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum PropertyKind {
     Get,
@@ -159,6 +226,29 @@ pub enum TypeNature {
 }
 
 impl TypeNature {
+    /// returns the keyword declaring this nature in the source, e.g. `ANY_NUM` rather than the display
+    /// name `ANY_NUMBER`
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            TypeNature::Any => "ANY",
+            TypeNature::Derived => "ANY_DERIVED",
+            TypeNature::Elementary => "ANY_ELEMENTARY",
+            TypeNature::Magnitude => "ANY_MAGNITUDE",
+            TypeNature::Num => "ANY_NUM",
+            TypeNature::Real => "ANY_REAL",
+            TypeNature::Int => "ANY_INT",
+            TypeNature::Signed => "ANY_SIGNED",
+            TypeNature::Unsigned => "ANY_UNSIGNED",
+            TypeNature::Duration => "ANY_DURATION",
+            TypeNature::Bit => "ANY_BIT",
+            TypeNature::Chars => "ANY_CHARS",
+            TypeNature::String => "ANY_STRING",
+            TypeNature::Char => "ANY_CHAR",
+            TypeNature::Date => "ANY_DATE",
+            TypeNature::__VLA => "__ANY_VLA",
+        }
+    }
+
     pub fn derives_from(self, other: TypeNature) -> bool {
         if other == self {
             true
@@ -276,11 +366,48 @@ impl Debug for Pou {
         if !self.generics.is_empty() {
             str.field("generics", &self.generics);
         }
+        if self.namespace.is_some() {
+            str.field("namespace", &self.namespace);
+        }
+        if self.keyword.is_some() {
+            str.field("keyword", &self.keyword);
+        }
+        if self.doc_comment.is_some() {
+            str.field("doc_comment", &self.doc_comment);
+        }
+        if !self.attributes.is_empty() {
+            str.field("attributes", &self.attributes);
+        }
+        if self.enable.is_some() {
+            str.field("enable", &self.enable);
+        }
+        if self.enable_out.is_some() {
+            str.field("enable_out", &self.enable_out);
+        }
+        if self.task.is_some() {
+            str.field("task", &self.task);
+        }
+        if self.is_pure {
+            str.field("is_pure", &self.is_pure);
+        }
         str.finish()
     }
 }
 
 impl Pou {
+    /// whether an `FB_init` method should be generated for this POU as requested by an
+    /// `{attribute 'enable_init'}` or `{attribute 'disable_init'}` pragma, `None` if neither is present.
+    /// The value of `{attribute 'enable_init' := 'false'}` negates the attribute as well
+    pub fn init_enabled(&self) -> Option<bool> {
+        self.attributes.iter().rev().find_map(|attribute| match attribute.name.as_str() {
+            "enable_init" => {
+                Some(!attribute.value.as_deref().is_some_and(|it| it.eq_ignore_ascii_case("false")))
+            }
+            "disable_init" => Some(false),
+            _ => None,
+        })
+    }
+
     pub fn get_return_name(&self) -> &str {
         Pou::calc_return_name(&self.name)
     }
@@ -296,6 +423,41 @@ impl Pou {
     pub fn is_generic(&self) -> bool {
         !self.generics.is_empty()
     }
+
+    /// renders a one-line signature of this POU for hover displays, e.g. `FUNCTION Add(a: INT, b: INT): INT`.
+    /// Only `VAR_INPUT` parameters are listed, generic parameters are rendered as declared in angle brackets
+    pub fn signature_string(&self) -> String {
+        let keyword = match self.kind {
+            PouType::Program => "PROGRAM",
+            PouType::Function | PouType::Init | PouType::ProjectInit => "FUNCTION",
+            PouType::FunctionBlock => "FUNCTION_BLOCK",
+            PouType::Action => "ACTION",
+            PouType::Class => "CLASS",
+            PouType::Method { .. } => "METHOD",
+        };
+
+        let mut signature = format!("{keyword} {}", self.name);
+        if self.is_generic() {
+            let generics =
+                self.generics.iter().map(GenericBinding::signature_string).collect::<Vec<_>>().join(", ");
+            signature.push_str(&format!("<{generics}>"));
+        }
+
+        let inputs = self
+            .variable_blocks
+            .iter()
+            .filter(|block| matches!(block.kind, VariableBlockType::Input(_)))
+            .flat_map(|block| block.variables.iter())
+            .map(|it| format!("{}: {}", it.name, it.data_type_declaration.signature_string()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        signature.push_str(&format!("({inputs})"));
+
+        if let Some(return_type) = &self.return_type {
+            signature.push_str(&format!(": {}", return_type.signature_string()));
+        }
+        signature
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -313,6 +475,18 @@ pub struct Implementation {
     pub access: Option<AccessModifier>,
 }
 
+impl Implementation {
+    /// Returns the statements making up the body of this implementation
+    pub fn statements(&self) -> &[AstNode] {
+        &self.statements
+    }
+
+    /// Returns true if this implementation has no statements
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+}
+
 #[derive(Debug, Copy, PartialEq, Eq, Clone, Hash)]
 pub enum LinkageType {
     Internal,
@@ -425,10 +599,28 @@ impl ConfigVariable {
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// An access path declared in a `VAR_ACCESS` block, e.g. `counter : main.fb.count : INT READ_ONLY;`
+#[derive(Debug, PartialEq, Clone)]
+pub struct AccessVariable {
+    pub name: Identifier,
+    /// the path to the accessed variable, e.g. `main.fb.count`
+    pub reference: AstNode,
+    pub data_type: DataTypeDeclaration,
+    pub direction: AccessDirection,
+    pub location: SourceLocation,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AccessDirection {
+    ReadOnly,
+    ReadWrite,
+}
+
+#[derive(PartialEq)]
 pub struct CompilationUnit {
     pub global_vars: Vec<VariableBlock>,
     pub var_config: Vec<ConfigVariable>,
+    pub var_access: Vec<AccessVariable>,
     /// List of POU definitions (signature and some additional metadata)
     pub pous: Vec<Pou>,
     /// List of statements within a POU body
@@ -438,11 +630,28 @@ pub struct CompilationUnit {
     pub file: FileMarker,
 }
 
+impl Debug for CompilationUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut str = f.debug_struct("CompilationUnit");
+        str.field("global_vars", &self.global_vars).field("var_config", &self.var_config);
+        if !self.var_access.is_empty() {
+            str.field("var_access", &self.var_access);
+        }
+        str.field("pous", &self.pous)
+            .field("implementations", &self.implementations)
+            .field("interfaces", &self.interfaces)
+            .field("user_types", &self.user_types)
+            .field("file", &self.file)
+            .finish()
+    }
+}
+
 impl CompilationUnit {
     pub fn new(file_name: &'static str) -> Self {
         CompilationUnit {
             global_vars: Vec::new(),
             var_config: Vec::new(),
+            var_access: Vec::new(),
             pous: Vec::new(),
             implementations: Vec::new(),
             interfaces: Vec::new(),
@@ -458,19 +667,22 @@ impl CompilationUnit {
 
     /// imports all elements of the other CompilationUnit into this CompilationUnit
     ///
-    /// this will import all global_vars, units, implementations and types. The imported
-    /// structs are moved from the other unit into this unit
+    /// this will import all global_vars, config and access variables, units, implementations, interfaces and
+    /// types. The imported structs are moved from the other unit into this unit
     /// # Arguments
     /// `other` the other CompilationUnit to import the elements from.
     pub fn import(&mut self, other: CompilationUnit) {
         self.global_vars.extend(other.global_vars);
+        self.var_config.extend(other.var_config);
+        self.var_access.extend(other.var_access);
         self.pous.extend(other.pous);
         self.implementations.extend(other.implementations);
+        self.interfaces.extend(other.interfaces);
         self.user_types.extend(other.user_types);
     }
 }
 
-#[derive(Debug, Copy, PartialEq, Eq, Clone)]
+#[derive(Debug, Copy, PartialEq, Eq, Clone, Hash)]
 pub enum VariableBlockType {
     Local,
     Temp,
@@ -495,7 +707,7 @@ impl Display for VariableBlockType {
     }
 }
 
-#[derive(Debug, Copy, PartialEq, Eq, Clone)]
+#[derive(Debug, Copy, PartialEq, Eq, Clone, Hash)]
 pub enum ArgumentProperty {
     ByVal,
     ByRef,
@@ -506,6 +718,7 @@ pub struct VariableBlock {
     pub access: AccessModifier,
     pub constant: bool,
     pub retain: bool,
+    pub persistent: bool,
     pub variables: Vec<Variable>,
     pub kind: VariableBlockType,
     pub linkage: LinkageType,
@@ -534,6 +747,7 @@ impl Default for VariableBlock {
             access: AccessModifier::Internal,
             constant: false,
             retain: false,
+            persistent: false,
             variables: vec![],
             kind: VariableBlockType::Local,
             linkage: LinkageType::Internal,
@@ -557,9 +771,28 @@ pub struct Variable {
     pub data_type_declaration: DataTypeDeclaration,
     pub initializer: Option<AstNode>,
     pub address: Option<AstNode>,
+    /// the member's own persistence qualifier: `Some(true)` for `RETAIN`, `Some(false)` for `NON_RETAIN`
+    /// and `None` if it inherits the retain setting of its container
+    pub retain: Option<bool>,
+    /// the `{attribute ...}` pragmas preceding the declaration
+    pub attributes: Vec<Attribute>,
+    /// whether this is an explicitly declared `EN : BOOL` input or `ENO : BOOL` output, which replaces the
+    /// implicit one
+    pub explicit_en_eno: bool,
+    /// whether this is an input with a default value, which may be omitted at call sites
+    pub optional: bool,
+    /// the absolute address given by an `{address 16#1000}` pragma preceding the declaration
+    pub absolute_address: Option<u64>,
     pub location: SourceLocation,
 }
 
+/// An `{attribute 'name' := 'value'}` pragma attached to a declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    pub name: String,
+    pub value: Option<String>,
+}
+
 impl PartialEq for Variable {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name && self.location == other.location
@@ -576,6 +809,21 @@ impl Debug for Variable {
         if self.address.is_some() {
             var.field("address", &self.address);
         }
+        if self.retain.is_some() {
+            var.field("retain", &self.retain);
+        }
+        if !self.attributes.is_empty() {
+            var.field("attributes", &self.attributes);
+        }
+        if self.explicit_en_eno {
+            var.field("explicit_en_eno", &self.explicit_en_eno);
+        }
+        if self.optional {
+            var.field("optional", &self.optional);
+        }
+        if self.absolute_address.is_some() {
+            var.field("absolute_address", &self.absolute_address);
+        }
         var.finish()
     }
 }
@@ -592,6 +840,16 @@ impl Variable {
     pub fn get_name(&self) -> &str {
         &self.name
     }
+
+    /// returns the attribute with the given name (case-insensitive), if declared
+    pub fn get_attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes.iter().find(|it| it.name.eq_ignore_ascii_case(name))
+    }
+
+    /// returns the debugger display format (e.g. `hex`) set via `{attribute 'displaymode' := '...'}`
+    pub fn get_display_mode(&self) -> Option<&str> {
+        self.get_attribute("displaymode").and_then(|it| it.value.as_deref())
+    }
 }
 
 #[derive(Clone, PartialEq)]
@@ -640,6 +898,35 @@ impl DataTypeDeclaration {
         }
     }
 
+    /// renders the type as it would be written in a declaration, inline definitions without a name are
+    /// summarized by their kind (e.g. `ARRAY OF INT`), anything else that can't be named renders as `?`
+    pub fn signature_string(&self) -> String {
+        if let Some(name) = self.get_name() {
+            return name.to_string();
+        }
+
+        let DataTypeDeclaration::Definition { data_type, .. } = self else { return "?".to_string() };
+        match data_type.as_ref() {
+            DataType::ArrayType { referenced_type, .. } => {
+                format!("ARRAY OF {}", referenced_type.signature_string())
+            }
+            DataType::PointerType { referenced_type, kind, .. } => {
+                let keyword = match kind {
+                    PointerKind::PointerTo => "POINTER TO",
+                    PointerKind::ReferenceTo => "REFERENCE TO",
+                    PointerKind::Function => "__FPOINTER",
+                    PointerKind::RefTo | PointerKind::Alias => "REF_TO",
+                };
+                format!("{keyword} {}", referenced_type.signature_string())
+            }
+            DataType::StringType { is_wide: true, .. } => "WSTRING".to_string(),
+            DataType::StringType { is_wide: false, .. } => "STRING".to_string(),
+            DataType::SubRangeType { referenced_type, .. } => referenced_type.clone(),
+            DataType::StructType { .. } => "STRUCT".to_string(),
+            _ => "?".to_string(),
+        }
+    }
+
     pub fn get_referenced_type(&self) -> Option<String> {
         let DataTypeDeclaration::Reference { referenced_type, .. } = self else { return None };
         Some(referenced_type.to_owned())
@@ -725,11 +1012,19 @@ pub enum DataType {
 
         /// Indicates whether the pointer is a function pointer.
         is_function: bool,
+
+        /// The keyword the pointer was declared with, e.g. to tell `REF_TO` and `REFERENCE TO` apart.
+        kind: PointerKind,
+
+        /// The nullability annotated by a `{not_null}` or `{nullable}` pragma, if any.
+        nullability: Option<Nullability>,
     },
     StringType {
         name: Option<String>,
         is_wide: bool, //WSTRING
         size: Option<AstNode>,
+        /// the code-page annotation following the size (e.g. `CP1252` for `STRING[10](CP1252)`), kept verbatim
+        encoding: Option<String>,
     },
     VarArgs {
         referenced_type: Option<Box<DataTypeDeclaration>>,
@@ -742,7 +1037,36 @@ pub enum DataType {
     },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// The nullability of a [`DataType::PointerType`], as annotated by a pragma
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Nullability {
+    /// The pointer never is null, e.g. `foo : {not_null} REF_TO DINT;`
+    NotNull,
+
+    /// The pointer may be null, e.g. `foo : {nullable} REF_TO DINT;`
+    Nullable,
+}
+
+/// The kind of a [`DataType::PointerType`], as declared in the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerKind {
+    /// A pointer without type validation, e.g. `foo : POINTER TO DINT;`
+    PointerTo,
+
+    /// A type-safe pointer, e.g. `foo : REF_TO DINT;`
+    RefTo,
+
+    /// An auto-dereferencing reference, e.g. `foo : REFERENCE TO DINT;`
+    ReferenceTo,
+
+    /// An alias of another variable, e.g. `foo AT bar : DINT`
+    Alias,
+
+    /// A function pointer, e.g. `foo : __FPOINTER bar;`
+    Function,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AutoDerefType {
     /// A plain pointer variable with the auto-deref trait, e.g. VAR_IN_OUT or VAR_INPUT{ref} variables
     Default,
@@ -1047,6 +1371,19 @@ impl Debug for AstNode {
                 .field("case_blocks", case_blocks)
                 .field("else_block", else_block)
                 .finish(),
+            AstStatement::ControlStatement(AstControlStatement::Try(TryStatement {
+                body,
+                catch_variable,
+                catch_body,
+                finally_body,
+                ..
+            })) => f
+                .debug_struct("TryStatement")
+                .field("body", body)
+                .field("catch_variable", catch_variable)
+                .field("catch_body", catch_body)
+                .field("finally_body", finally_body)
+                .finish(),
             AstStatement::DirectAccess(DirectAccess { access, index }) => {
                 f.debug_struct("DirectAccess").field("access", access).field("index", index).finish()
             }
@@ -1065,8 +1402,13 @@ impl Debug for AstNode {
             AstStatement::CaseCondition(condition) => {
                 f.debug_struct("CaseCondition").field("condition", condition).finish()
             }
-            AstStatement::ReturnStatement(ReturnStatement { condition }) => {
-                f.debug_struct("ReturnStatement").field("condition", condition).finish()
+            AstStatement::ReturnStatement(ReturnStatement { condition, value }) => {
+                let mut debug = f.debug_struct("ReturnStatement");
+                debug.field("condition", condition);
+                if let Some(value) = value {
+                    debug.field("value", value);
+                }
+                debug.finish()
             }
             AstStatement::ContinueStatement(..) => f.debug_struct("ContinueStatement").finish(),
             AstStatement::ExitStatement(..) => f.debug_struct("ExitStatement").finish(),
@@ -1297,6 +1639,29 @@ impl AstNode {
         matches!(self.stmt, AstStatement::ExpressionList { .. })
     }
 
+    /// Returns the members of a struct initializer, i.e. a parenthesized list of named
+    /// (`(x := 1, y := 2)`) or positional (`(1, 2)`) member initializations, or `None` if this
+    /// is no struct initializer. Unlike a call, a struct initializer has no operator.
+    pub fn get_struct_initializer_members(&self) -> Option<Vec<StructInitializerMember<'_>>> {
+        let AstStatement::ParenExpression(expression) = &self.stmt else { return None };
+        let members = match &expression.stmt {
+            AstStatement::ExpressionList(expressions) => expressions.iter().collect::<Vec<_>>(),
+            AstStatement::Assignment(..) => vec![expression.as_ref()],
+            _ => return None,
+        };
+
+        let members = members
+            .into_iter()
+            .map(|member| match &member.stmt {
+                AstStatement::Assignment(Assignment { left, right }) => {
+                    StructInitializerMember { name: left.get_flat_reference_name(), value: right }
+                }
+                _ => StructInitializerMember { name: None, value: member },
+            })
+            .collect();
+        Some(members)
+    }
+
     pub fn is_super(&self) -> bool {
         let node = match &self.stmt {
             AstStatement::ReferenceExpr(
@@ -1647,7 +2012,17 @@ impl AstFactory {
         id: AstId,
     ) -> AstNode {
         let condition = condition.map(Box::new);
-        AstNode::new(AstStatement::ReturnStatement(ReturnStatement { condition }), id, location)
+        AstNode::new(AstStatement::ReturnStatement(ReturnStatement { condition, value: None }), id, location)
+    }
+
+    /// creates a `RETURN <value>` statement
+    pub fn create_return_statement_with_value(
+        value: AstNode,
+        location: SourceLocation,
+        id: AstId,
+    ) -> AstNode {
+        let value = Some(Box::new(value));
+        AstNode::new(AstStatement::ReturnStatement(ReturnStatement { condition: None, value }), id, location)
     }
 
     pub fn create_exit_statement(location: SourceLocation, id: AstId) -> AstNode {
@@ -1721,6 +2096,11 @@ impl AstFactory {
         AstNode::new(AstStatement::ControlStatement(AstControlStatement::Case(stmt)), id, location)
     }
 
+    /// creates a new try-statement
+    pub fn create_try_statement(stmt: TryStatement, location: SourceLocation, id: AstId) -> AstNode {
+        AstNode::new(AstStatement::ControlStatement(AstControlStatement::Try(stmt)), id, location)
+    }
+
     /// creates an or-expression
     pub fn create_or_expression(left: AstNode, right: AstNode) -> AstNode {
         let id = left.get_id();
@@ -2107,6 +2487,14 @@ pub struct Assignment {
     pub right: Box<AstNode>,
 }
 
+/// A single member of a struct initializer, see [`AstNode::get_struct_initializer_members`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StructInitializerMember<'a> {
+    /// the initialized member's name, `None` for positional initialization (e.g. `(1, 2)`)
+    pub name: Option<&'a str>,
+    pub value: &'a AstNode,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct CallStatement {
     pub operator: Box<AstNode>,
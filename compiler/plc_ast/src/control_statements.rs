@@ -2,7 +2,7 @@ use std::fmt::Debug;
 
 use plc_source::source_location::SourceLocation;
 
-use crate::ast::AstNode;
+use crate::ast::{AstNode, AstStatement, RangeStatement};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct IfStatement {
@@ -32,11 +32,25 @@ pub struct LoopStatement {
 #[derive(Debug, Clone, PartialEq)]
 pub struct CaseStatement {
     pub selector: Box<AstNode>,
+    /// whether the selector is a plain reference (e.g. `state` or `fb.state`) rather than a complex expression,
+    /// so later passes can look up its type (e.g. to check if all enum elements are covered)
+    pub selector_is_reference: bool,
     pub case_blocks: Vec<ConditionalBlock>,
     pub else_block: Vec<AstNode>,
     pub end_location: SourceLocation,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// a `__TRY ... __CATCH(e) ... __FINALLY ... END_TRY` block (dialect extension)
+pub struct TryStatement {
+    pub body: Vec<AstNode>,
+    /// the exception variable of the `__CATCH` block, [`None`] if there is no `__CATCH` block
+    pub catch_variable: Option<Box<AstNode>>,
+    pub catch_body: Vec<AstNode>,
+    pub finally_body: Vec<AstNode>,
+    pub end_location: SourceLocation,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum AstControlStatement {
     If(IfStatement),
@@ -44,6 +58,7 @@ pub enum AstControlStatement {
     WhileLoop(LoopStatement),
     RepeatLoop(LoopStatement),
     Case(CaseStatement),
+    Try(TryStatement),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -52,11 +67,40 @@ pub struct ConditionalBlock {
     pub body: Vec<AstNode>,
 }
 
+impl ConditionalBlock {
+    /// returns the labels of a `CASE` branch as a list of values and ranges, e.g. `1`, `3..5` and `9` for
+    /// `1, 3..5, 9:`. The parser stores them as one flat list, a branch with a single label yields a
+    /// one-element list
+    pub fn case_labels(&self) -> Vec<CaseLabel<'_>> {
+        self.condition
+            .get_as_list()
+            .into_iter()
+            .map(|label| match label.get_stmt() {
+                AstStatement::RangeStatement(RangeStatement { start, end }) => {
+                    CaseLabel::Range { start, end }
+                }
+                _ => CaseLabel::Value(label),
+            })
+            .collect()
+    }
+}
+
+/// A single label of a `CASE` branch, see [`ConditionalBlock::case_labels`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaseLabel<'a> {
+    /// A single value, e.g. `1` or `state.IDLE`
+    Value(&'a AstNode),
+    /// A range of values, e.g. `3..5`
+    Range { start: &'a AstNode, end: &'a AstNode },
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ReturnStatement {
     /// Indicates that the given condition must evaluate to true in order for the return to take place.
     /// Only used in CFC where the condition may be [`Some`] and [`None`] otherwise.
     pub condition: Option<Box<AstNode>>,
+    /// The value following the `RETURN` keyword, e.g. the `(a + b).field` in `RETURN (a + b).field;`
+    pub value: Option<Box<AstNode>>,
 }
 
 impl ForLoopStatement {
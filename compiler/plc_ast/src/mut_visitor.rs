@@ -354,6 +354,12 @@ impl WalkerMut for AstControlStatement {
                 stmt.case_blocks.walk(visitor);
                 visit_all_nodes_mut!(visitor, &mut stmt.else_block);
             }
+            AstControlStatement::Try(stmt) => {
+                visit_all_nodes_mut!(visitor, &mut stmt.body);
+                visit_all_nodes_mut!(visitor, &mut stmt.catch_variable);
+                visit_all_nodes_mut!(visitor, &mut stmt.catch_body);
+                visit_all_nodes_mut!(visitor, &mut stmt.finally_body);
+            }
         }
     }
 }
@@ -364,6 +370,7 @@ impl WalkerMut for ReturnStatement {
         V: AstVisitorMut,
     {
         visit_all_nodes_mut!(visitor, &mut self.condition);
+        visit_all_nodes_mut!(visitor, &mut self.value);
     }
 }
 
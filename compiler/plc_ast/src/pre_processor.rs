@@ -186,6 +186,11 @@ fn process_global_variables(unit: &mut CompilationUnit, id_provider: &mut IdProv
                     data_type_declaration: ref_ty.unwrap_or(global_var.data_type_declaration.clone()),
                     initializer: None,
                     address: None,
+                    retain: None,
+                    attributes: vec![],
+                    explicit_en_eno: false,
+                    optional: false,
+                    absolute_address: None,
                     location: node.location.clone(),
                 };
                 mangled_globals.push(internal_mangled_var);
@@ -218,6 +223,11 @@ fn process_var_config_variables(unit: &mut CompilationUnit) {
             data_type_declaration: data_type.get_inner_pointer_ty().unwrap_or(data_type.clone()),
             initializer: None,
             address: None,
+            retain: None,
+            attributes: vec![],
+            explicit_en_eno: false,
+            optional: false,
+            absolute_address: None,
             location: address.get_location(),
         })
     });
@@ -567,6 +567,12 @@ impl Walker for AstControlStatement {
                 walk_conditional_blocks(visitor, &stmt.case_blocks);
                 visit_all_nodes!(visitor, &stmt.else_block);
             }
+            AstControlStatement::Try(stmt) => {
+                visit_all_nodes!(visitor, &stmt.body);
+                visit_all_nodes!(visitor, &stmt.catch_variable);
+                visit_all_nodes!(visitor, &stmt.catch_body);
+                visit_all_nodes!(visitor, &stmt.finally_body);
+            }
         }
     }
 }
@@ -577,6 +583,7 @@ impl Walker for ReturnStatement {
         V: AstVisitor,
     {
         visit_all_nodes!(visitor, &self.condition);
+        visit_all_nodes!(visitor, &self.value);
     }
 }
 
@@ -35,7 +35,6 @@ pub struct Diagnostic {
     pub inner: Box<DiagnosticsInner>,
 }
 
-#[derive(Debug)]
 pub struct DiagnosticsInner {
     /// The Description of the error being reported.
     pub message: String,
@@ -49,6 +48,25 @@ pub struct DiagnosticsInner {
     pub sub_diagnostics: Vec<Diagnostic>,
     /// If the diagnostic is caused by an error, this field contains the original error
     pub internal_error: Option<anyhow::Error>,
+    /// The name of the POU the diagnostic was reported in, if known
+    pub scope: Option<String>,
+}
+
+impl std::fmt::Debug for DiagnosticsInner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut str = f.debug_struct("DiagnosticsInner");
+        str.field("message", &self.message)
+            .field("primary_location", &self.primary_location)
+            .field("secondary_locations", &self.secondary_locations)
+            .field("error_code", &self.error_code)
+            .field("sub_diagnostics", &self.sub_diagnostics)
+            .field("internal_error", &self.internal_error);
+
+        if self.scope.is_some() {
+            str.field("scope", &self.scope);
+        }
+        str.finish()
+    }
 }
 
 impl Deref for Diagnostic {
@@ -87,6 +105,7 @@ impl Diagnostic {
             error_code: "E001", //Default error if none specified
             sub_diagnostics: Default::default(),
             internal_error: Default::default(),
+            scope: Default::default(),
         };
         Self { inner: Box::new(inner) }
     }
@@ -134,6 +153,11 @@ impl Diagnostic {
         self
     }
 
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
     pub fn from_serde_error(error: serde_json::Error, source: &SourceCode) -> Self {
         let factory = SourceLocationFactory::for_source(source);
         let line = error.line();
@@ -192,6 +216,11 @@ impl Diagnostic {
         self.error_code
     }
 
+    /// Returns the name of the POU this diagnostic was reported in, see [`Diagnostic::with_scope`]
+    pub fn get_scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
+
     pub fn get_sub_diagnostics(&self) -> Vec<&Diagnostic> {
         let mut diagnostics = vec![];
         self.sub_diagnostics.iter().for_each(|d| {
@@ -7,7 +7,7 @@ use crate::diagnostician::DiagnosticAssessor;
 
 use super::{
     Diagnostic,
-    Severity::{self, Error, Info, Warning},
+    Severity::{self, Error, Ignore, Info, Warning},
 };
 
 macro_rules! add_diagnostic {
@@ -224,6 +224,36 @@ lazy_static! {
         E120,   Error,      include_str!("./error_codes/E120.md"),  // Invalid use of `THIS` keyword
         E121,   Error,      include_str!("./error_codes/E121.md"),  // Recursive type alias
         E122,   Error,      include_str!("./error_codes/E122.md"),  // Invalid enum base type
+        E123,   Ignore,     include_str!("./error_codes/E123.md"),  // Variable named like an elementary type (opt-in)
+        E124,   Error,      include_str!("./error_codes/E124.md"),  // Conflicting RETAIN and NON_RETAIN qualifiers
+        E125,   Error,      include_str!("./error_codes/E125.md"),  // EXIT or CONTINUE outside of a loop
+        E126,   Error,      include_str!("./error_codes/E126.md"),  // Conflicting initial_value attribute and initializer
+        E127,   Warning,    include_str!("./error_codes/E127.md"),  // POU exceeds the configured statement count (opt-in)
+        E128,   Error,      include_str!("./error_codes/E128.md"),  // CONSTANT pointer without an initializer
+        E129,   Warning,    include_str!("./error_codes/E129.md"),  // Input-mapped global variable with an initializer
+        E130,   Warning,    include_str!("./error_codes/E130.md"),  // Pointer type in a `{ref}` input block
+        E131,   Error,      include_str!("./error_codes/E131.md"),  // Method in a PROGRAM (opt-in)
+        E132,   Error,      include_str!("./error_codes/E132.md"),  // Variable block after the body
+        E133,   Error,      include_str!("./error_codes/E133.md"),  // Declaration in a formula
+        E134,   Warning,    include_str!("./error_codes/E134.md"),  // Array without bounds
+        E135,   Error,      include_str!("./error_codes/E135.md"),  // Initial value outside of a subrange
+        E136,   Error,      include_str!("./error_codes/E136.md"),  // External pragma on a builtin
+        E137,   Error,      include_str!("./error_codes/E137.md"),  // CONSTANT block with a retention qualifier
+        E138,   Error,      include_str!("./error_codes/E138.md"),  // Invalid VAR_CONFIG target
+        E139,   Error,      include_str!("./error_codes/E139.md"),  // Reparsed range is not a POU
+        E140,   Error,      include_str!("./error_codes/E140.md"),  // Initializer on a REF_TO output
+        E141,   Error,      include_str!("./error_codes/E141.md"),  // Nullability pragma on a non-pointer type
+        E142,   Error,      include_str!("./error_codes/E142.md"),  // Invalid absolute address pragma
+        E143,   Error,      include_str!("./error_codes/E143.md"),  // Absolute address pragma conflicting with AT
+        E144,   Error,      include_str!("./error_codes/E144.md"),  // Invalid type name in a generic constraint
+        E145,   Error,      include_str!("./error_codes/E145.md"),  // Reserved keyword used as an identifier
+        E146,   Error,      include_str!("./error_codes/E146.md"),  // {pure} pragma on a non-function
+        E147,   Warning,    include_str!("./error_codes/E147.md"),  // Missing VAR_ACCESS direction
+        E148,   Warning,    include_str!("./error_codes/E148.md"),  // String literal quotes not matching the string type
+        E149,   Error,      include_str!("./error_codes/E149.md"),  // Overlapping array initializer indices
+        E150,   Error,      include_str!("./error_codes/E150.md"),  // Missing END_CASE
+        E151,   Warning,    include_str!("./error_codes/E151.md"),  // Duplicate CASE label
+        E152,   Error,      include_str!("./error_codes/E152.md"),  // RETURN with a value
     );
 }
 
@@ -201,6 +201,11 @@ impl AstVisitorMut for InheritanceLowerer {
             location: SourceLocation::internal(),
             initializer: None,
             address: None,
+            retain: None,
+            attributes: vec![],
+            explicit_en_eno: false,
+            optional: false,
+            absolute_address: None,
         };
 
         let block = VariableBlock {
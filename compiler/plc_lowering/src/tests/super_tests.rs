@@ -741,7 +741,6 @@ fn super_in_complex_expressions() {
                 ],
                 else_block: [],
             },
-            EmptyStatement,
             Assignment {
                 left: ReferenceExpr {
                     kind: Member(
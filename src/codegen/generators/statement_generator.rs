@@ -142,7 +142,7 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
             AstStatement::ControlStatement(ctl_statement, ..) => {
                 self.generate_control_statement(&llvm_index, ctl_statement)?
             }
-            AstStatement::ReturnStatement(ReturnStatement { condition }) => match condition {
+            AstStatement::ReturnStatement(ReturnStatement { condition, .. }) => match condition {
                 Some(condition) => {
                     self.generate_conditional_return(&llvm_index, statement, condition)?;
                 }
@@ -264,6 +264,9 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
                 self.generate_loop_statement(llvm_index, stmt)
             }
             AstControlStatement::Case(stmt) => self.generate_case_statement(llvm_index, stmt),
+            AstControlStatement::Try(stmt) => {
+                Err(CodegenError::new("Exception handling (`__TRY`) is not supported", &stmt.end_location))
+            }
         }
     }
 
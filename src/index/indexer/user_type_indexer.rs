@@ -1,8 +1,8 @@
 use plc_ast::{
     ast::{
         flatten_expression_list, get_enum_element_name, Assignment, AstFactory, AstNode, AstStatement,
-        AutoDerefType, DataType, DataTypeDeclaration, RangeStatement, TypeNature, UserTypeDeclaration,
-        Variable,
+        AutoDerefType, DataType, DataTypeDeclaration, PointerKind, RangeStatement, TypeNature,
+        UserTypeDeclaration, Variable,
     },
     literals::AstLiteral,
     visitor::{AstVisitor, Walker},
@@ -73,10 +73,10 @@ impl AstVisitor for UserTypeIndexer<'_, '_> {
             DataType::ArrayType { name: Some(name), bounds, referenced_type, is_variable_length: true } => {
                 self.index_vla_array(name, bounds, referenced_type)
             }
-            DataType::PointerType { name, referenced_type, auto_deref, type_safe, is_function } => {
+            DataType::PointerType { name, referenced_type, auto_deref, type_safe, is_function, .. } => {
                 self.index_pointer_type(name, referenced_type, *auto_deref, *type_safe, *is_function)
             }
-            DataType::StringType { name: Some(name), is_wide, size } => {
+            DataType::StringType { name: Some(name), is_wide, size, .. } => {
                 self.index_string_type(name.as_ref(), *is_wide, size.as_ref())
             }
             DataType::VarArgs { .. } => {
@@ -156,6 +156,8 @@ impl UserTypeIndexer<'_, '_> {
                             auto_deref: None,
                             type_safe: true,
                             is_function: false,
+                            kind: PointerKind::RefTo,
+                            nullability: None,
                         }),
                         location: SourceLocation::internal(),
                         scope: None,
@@ -206,6 +208,11 @@ impl UserTypeIndexer<'_, '_> {
                 data_type_declaration: vla_arr_type_declaration,
                 initializer: None,
                 address: None,
+                retain: None,
+                attributes: vec![],
+                explicit_en_eno: false,
+                optional: false,
+                absolute_address: None,
                 location: SourceLocation::internal(),
             },
             // Dimensions Array
@@ -214,6 +221,11 @@ impl UserTypeIndexer<'_, '_> {
                 data_type_declaration: dim_arr_type_declaration,
                 initializer: None,
                 address: None,
+                retain: None,
+                attributes: vec![],
+                explicit_en_eno: false,
+                optional: false,
+                absolute_address: None,
                 location: SourceLocation::internal(),
             },
         ];
@@ -15,7 +15,10 @@ fn generics_saved_in_index() {
     assert!(foo_info.is_generic());
     if let PouIndexEntry::Function { generics, .. } = foo_info {
         let t = &generics[0];
-        assert_eq!(&GenericBinding { name: "T".into(), nature: TypeNature::Any }, t);
+        assert_eq!(
+            &GenericBinding { name: "T".into(), nature: TypeNature::Any, constraint_types: vec![] },
+            t
+        );
     } else {
         panic!("{foo_info:#?} not a generic function");
     }
@@ -1385,7 +1385,12 @@ fn a_program_pou_is_indexed() {
         Some(&PouIndexEntry::Function {
             name: "myFunction".into(),
             linkage: LinkageType::Internal,
-            generics: [GenericBinding { name: "A".into(), nature: TypeNature::Int }].to_vec(),
+            generics: [GenericBinding {
+                name: "A".into(),
+                nature: TypeNature::Int,
+                constraint_types: vec![]
+            }]
+            .to_vec(),
             return_type: "INT".into(),
             is_variadic: false,
             location: source_location_factory.create_range(65..75),
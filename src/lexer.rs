@@ -4,9 +4,14 @@ use logos::{Filter, Lexer, Logos};
 use plc_ast::ast::{AstId, DirectAccessType, HardwareAccessType};
 use plc_ast::provider::IdProvider;
 use plc_diagnostics::diagnostics::Diagnostic;
-use plc_source::source_location::{SourceLocation, SourceLocationFactory};
+use plc_source::{
+    source_location::{SourceLocation, SourceLocationFactory},
+    SourceCode,
+};
 pub use tokens::Token;
 
+use crate::parser::ParseOptions;
+
 #[cfg(test)]
 mod tests;
 mod tokens;
@@ -23,7 +28,16 @@ pub struct ParseSession<'a> {
     pub parse_progress: usize,
     pub id_provider: IdProvider,
     pub source_range_factory: SourceLocationFactory,
-    pub scope: Option<String>,
+    /// the names of the declarations enclosing the current position, outermost first, see `with_scope`
+    pub scope: Vec<String>,
+    /// the dialect/feature flags the parser runs with
+    pub options: ParseOptions,
+    /// the ranges of the `{attribute ...}` pragmas directly preceding the current `token`
+    pub attributes: Vec<Range<usize>>,
+    /// the range of the `{address ...}` pragma directly preceding the current `token`
+    pub address_pragma: Option<Range<usize>>,
+    /// the number of loops enclosing the current `token`
+    pub loop_depth: usize,
 }
 
 #[macro_export]
@@ -55,8 +69,12 @@ impl<'a> ParseSession<'a> {
             last_range: 0..0,
             parse_progress: 0,
             id_provider,
-            scope: None,
+            scope: vec![],
             source_range_factory,
+            options: ParseOptions::default(),
+            attributes: vec![],
+            address_pragma: None,
+            loop_depth: 0,
         };
         lexer.advance();
         lexer
@@ -86,6 +104,17 @@ impl<'a> ParseSession<'a> {
         }
     }
 
+    /// returns the token following the current one without advancing, skipping attribute and address
+    /// pragmas like `advance` does
+    pub fn peek(&self) -> Token {
+        let mut lexer = self.lexer.clone();
+        let mut next_token = lexer.next().unwrap_or(Token::End);
+        while matches!(next_token, Token::PropertyAttribute | Token::PropertyAddress) {
+            next_token = lexer.next().unwrap_or(Token::End);
+        }
+        next_token
+    }
+
     pub fn slice_and_advance(&mut self) -> String {
         let slice = self.slice().to_string();
         self.advance();
@@ -102,7 +131,28 @@ impl<'a> ParseSession<'a> {
 
     pub fn advance(&mut self) {
         self.last_range = self.range();
-        self.last_token = std::mem::replace(&mut self.token, self.lexer.next().unwrap_or(Token::End));
+        // attribute and address pragmas are not part of the token stream, they are attached to the token
+        // following them
+        self.attributes.clear();
+        self.address_pragma = None;
+        let mut next_token = self.lexer.next().unwrap_or(Token::End);
+        while matches!(next_token, Token::PropertyAttribute | Token::PropertyAddress) {
+            if next_token == Token::PropertyAddress {
+                self.address_pragma = Some(self.lexer.span());
+            } else {
+                self.attributes.push(self.lexer.span());
+            }
+            next_token = self.lexer.next().unwrap_or(Token::End);
+        }
+        if !self.options.exception_handling
+            && matches!(
+                next_token,
+                Token::KeywordTry | Token::KeywordCatch | Token::KeywordFinally | Token::KeywordEndTry
+            )
+        {
+            next_token = Token::Identifier;
+        }
+        self.last_token = std::mem::replace(&mut self.token, next_token);
         self.parse_progress += 1;
 
         match self.token {
@@ -158,10 +208,37 @@ impl<'a> ParseSession<'a> {
         self.lexer.span()
     }
 
+    /// returns the range of the content of a `{...}` list directly following the current token, e.g. the
+    /// range of `INT, DINT` after the `:` in `T : {INT, DINT}`. The lexer skips such lists like pragmas, so
+    /// their content has to be lexed separately (see `lex_range_with_ids`)
+    pub fn brace_list_after_token(&self) -> Option<Range<usize>> {
+        let remainder = self.lexer.remainder();
+        let list = remainder.trim_start();
+        let start = self.range().end + (remainder.len() - list.len()) + 1;
+        let len = list.strip_prefix('{')?.find('}')?;
+        Some(start..start + len)
+    }
+
     pub fn accept_diagnostic(&mut self, diagnostic: Diagnostic) {
+        let diagnostic = match self.qualified_scope() {
+            Some(scope) if self.options.diagnostic_scopes => diagnostic.with_scope(scope),
+            _ => diagnostic,
+        };
         self.diagnostics.push(diagnostic);
     }
 
+    /// the qualified name of the innermost enclosing declaration, e.g. `fb.m` within the method `m` of
+    /// the function block `fb`
+    pub fn qualified_scope(&self) -> Option<String> {
+        (!self.scope.is_empty()).then(|| self.scope.join("."))
+    }
+
+    /// the outermost enclosing declaration, e.g. `fb` within the method `m` of the function block `fb`.
+    /// The names used in inline declarations are resolved within this scope
+    pub fn pou_scope(&self) -> Option<String> {
+        self.scope.first().cloned()
+    }
+
     pub fn enter_region(&mut self, end_token: Vec<Token>) {
         self.closing_keywords.push(end_token);
     }
@@ -180,6 +257,12 @@ impl<'a> ParseSession<'a> {
         }
     }
 
+    /// leaves the innermost region without consuming the current token, e.g. because the token closes an
+    /// enclosing region and therefore belongs to the enclosing code
+    pub fn leave_region_unclosed(&mut self) {
+        self.closing_keywords.pop();
+    }
+
     /// returns the level (which corresponds to the position on the `closing_keywords` stack)
     /// returns `None` if this token does not close an open region
     fn get_close_region_level(&self, token: &Token) -> Option<usize> {
@@ -336,6 +419,131 @@ pub fn lex(source: &str) -> ParseSession<'_> {
     ParseSession::new(Token::lexer(source), IdProvider::default(), SourceLocationFactory::internal(source))
 }
 
+/// Controls which of the source parts the lexer skips are yielded by `tokenize_with_options`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenizeOptions {
+    /// yield comments and skipped pragmas as `Token::Comment`
+    pub include_comments: bool,
+    /// yield whitespace as `Token::Whitespace`
+    pub include_whitespace: bool,
+}
+
+/// returns the tokens of the given source with their locations, without building an AST.
+/// Comments and whitespace are skipped, see `tokenize_with_options` to include them.
+pub fn tokenize(
+    source: &SourceCode,
+    id_provider: IdProvider,
+) -> impl Iterator<Item = (Token, SourceLocation)> + '_ {
+    tokenize_with_options(source, id_provider, TokenizeOptions::default())
+}
+
+/// returns the tokens of the given source with their locations, including the comments and whitespace
+/// requested by `options`. Attribute and address pragmas are yielded as `Token::PropertyAttribute` and
+/// `Token::PropertyAddress`.
+pub fn tokenize_with_options(
+    source: &SourceCode,
+    id_provider: IdProvider,
+    options: TokenizeOptions,
+) -> impl Iterator<Item = (Token, SourceLocation)> + '_ {
+    let mut session = lex_with_ids(&source.source, id_provider, SourceLocationFactory::for_source(source));
+    let mut pending = std::collections::VecDeque::new();
+    let mut position = 0;
+    let mut done = false;
+
+    std::iter::from_fn(move || {
+        while pending.is_empty() && !done {
+            // everything between the previous and the current token was skipped by the lexer,
+            // except for attribute and address pragmas which are attached to the current token
+            let range = if session.token == Token::End {
+                let end = session.get_src().len();
+                end..end
+            } else {
+                session.range()
+            };
+            let mut pragmas = session
+                .attributes
+                .iter()
+                .map(|it| (Token::PropertyAttribute, it.clone()))
+                .chain(session.address_pragma.clone().map(|it| (Token::PropertyAddress, it)))
+                .collect::<Vec<_>>();
+            pragmas.sort_by_key(|(_, range)| range.start);
+            pragmas.push((Token::End, range.start..range.start));
+            for (pragma, pragma_range) in pragmas {
+                let skipped = session.get_src().get(position..pragma_range.start).unwrap_or_default();
+                pending.extend(split_skipped_text(skipped, position).into_iter().filter(|(token, _)| {
+                    (*token == Token::Comment && options.include_comments)
+                        || (*token == Token::Whitespace && options.include_whitespace)
+                }));
+                if !pragma_range.is_empty() {
+                    position = pragma_range.end;
+                    pending.push_back((pragma, pragma_range));
+                }
+            }
+
+            if session.token == Token::End {
+                done = true;
+            } else {
+                pending.push_back((session.token, range.clone()));
+                position = range.end;
+                session.advance();
+            }
+        }
+
+        pending.pop_front().map(|(token, range)| (token, session.source_range_factory.create_range(range)))
+    })
+}
+
+/// splits text skipped by the lexer into whitespace and comments (including skipped pragmas), the
+/// returned ranges are shifted by `offset`
+pub(crate) fn split_skipped_text(text: &str, offset: usize) -> Vec<(Token, Range<usize>)> {
+    let mut parts = vec![];
+    let mut start = 0;
+    while start < text.len() {
+        let remainder = &text[start..];
+        let (token, len) = if remainder.starts_with(char::is_whitespace) {
+            (Token::Whitespace, remainder.find(|c: char| !c.is_whitespace()).unwrap_or(remainder.len()))
+        } else {
+            (Token::Comment, get_comment_len(remainder))
+        };
+        parts.push((token, offset + start..offset + start + len));
+        start += len;
+    }
+    parts
+}
+
+/// returns the length of the comment or pragma at the start of the given text
+fn get_comment_len(text: &str) -> usize {
+    if text.starts_with("//") {
+        return text.find('\n').unwrap_or(text.len());
+    }
+    if text.starts_with('{') {
+        return text.find('}').map(|it| it + 1).unwrap_or(text.len());
+    }
+    if !(text.starts_with("(*") || text.starts_with("/*")) {
+        return text.chars().next().map(char::len_utf8).unwrap_or(text.len());
+    }
+
+    // block comments may be nested, see `parse_comments`
+    let (open, close) = get_closing_tag(&text[..2]);
+    let mut unclosed = 1;
+    let mut prev = ' ';
+    for (index, c) in text.char_indices().skip(2) {
+        if c == '*' && prev == open {
+            unclosed += 1;
+            prev = ' ';
+        } else if c == close && prev == '*' {
+            unclosed -= 1;
+            prev = c;
+        } else {
+            prev = c;
+        }
+        if unclosed == 0 {
+            return index + c.len_utf8();
+        }
+    }
+    text.len()
+}
+
 pub fn lex_with_ids(
     source: &str,
     id_provider: IdProvider,
@@ -343,3 +551,15 @@ pub fn lex_with_ids(
 ) -> ParseSession<'_> {
     ParseSession::new(Token::lexer(source), id_provider, location_factory)
 }
+
+/// lexes only the given byte range of the source, the tokens keep their offsets within the whole source
+pub fn lex_range_with_ids(
+    source: &str,
+    range: Range<usize>,
+    id_provider: IdProvider,
+    location_factory: SourceLocationFactory,
+) -> ParseSession<'_> {
+    let mut tokens = Token::lexer(&source[..range.end]);
+    tokens.bump(range.start);
+    ParseSession::new(tokens, id_provider, location_factory)
+}
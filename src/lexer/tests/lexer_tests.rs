@@ -1,9 +1,13 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 
-use plc_ast::ast::{DirectAccessType, HardwareAccessType};
+use plc_ast::{
+    ast::{DirectAccessType, HardwareAccessType},
+    provider::IdProvider,
+};
+use plc_source::SourceCode;
 use pretty_assertions::{assert_eq, assert_ne};
 
-use crate::lexer::{lex, Token::*};
+use crate::lexer::{lex, tokenize, tokenize_with_options, Token, Token::*, TokenizeOptions};
 
 #[test]
 fn generic_properties() {
@@ -69,6 +73,19 @@ fn registered_pragmas_parsed() {
     assert_eq!(lexer.token, End);
 }
 
+#[test]
+fn attribute_pragmas_are_attached_to_the_following_token() {
+    let mut lexer = lex(r"
+        {attribute 'displaymode' := 'hex'} {attribute 'hide'} x : INT; y
+        ");
+    assert_eq!(lexer.token, Identifier, "Token : {}", lexer.slice());
+    let attributes = lexer.attributes.iter().map(|it| lexer.slice_region(it.clone())).collect::<Vec<_>>();
+    assert_eq!(attributes, vec!["{attribute 'displaymode' := 'hex'}", "{attribute 'hide'}"]);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordColon, "Token : {}", lexer.slice());
+    assert!(lexer.attributes.is_empty());
+}
+
 #[test]
 fn comments_are_not_ignored_in_strings() {
     let mut lexer = lex(r#"
@@ -846,3 +863,73 @@ fn property_related_keywords() {
     assert!(lexer.try_consume(KeywordEndGet));
     assert!(lexer.try_consume(KeywordEndSet));
 }
+
+fn tokenize_to_slices(src: &str, options: TokenizeOptions) -> Vec<(Token, String)> {
+    let source = SourceCode::from(src);
+    tokenize_with_options(&source, IdProvider::default(), options)
+        .map(|(token, location)| (token, src[location.to_range().unwrap()].to_string()))
+        .collect()
+}
+
+#[test]
+fn tokenize_yields_tokens_with_their_locations() {
+    let src = "x := 1; (* comment *)\n{attribute 'hide'} {address 16#10} y";
+    let source = SourceCode::from(src);
+    let tokens = tokenize(&source, IdProvider::default())
+        .map(|(token, location)| (token, &src[location.to_range().unwrap()]))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tokens,
+        vec![
+            (Identifier, "x"),
+            (KeywordAssignment, ":="),
+            (LiteralInteger, "1"),
+            (KeywordSemicolon, ";"),
+            (PropertyAttribute, "{attribute 'hide'}"),
+            (PropertyAddress, "{address 16#10}"),
+            (Identifier, "y"),
+        ]
+    );
+}
+
+#[test]
+fn tokenize_can_include_comments_and_whitespace() {
+    let src = "a (* nested (* comment *) *) // line\n/* block */b ";
+
+    let with_comments =
+        tokenize_to_slices(src, TokenizeOptions { include_comments: true, include_whitespace: false });
+    assert_eq!(
+        with_comments,
+        vec![
+            (Identifier, "a".to_string()),
+            (Comment, "(* nested (* comment *) *)".to_string()),
+            (Comment, "// line".to_string()),
+            (Comment, "/* block */".to_string()),
+            (Identifier, "b".to_string()),
+        ]
+    );
+
+    let everything =
+        tokenize_to_slices(src, TokenizeOptions { include_comments: true, include_whitespace: true });
+    assert_eq!(everything.iter().map(|(_, slice)| slice.as_str()).collect::<String>(), src);
+    assert_eq!(everything.iter().filter(|(token, _)| *token == Whitespace).count(), 4);
+}
+
+#[test]
+fn unicode_identifiers_are_lexed() {
+    let src = "Größe 温度 _ünter2 Cafe\u{301} 2x";
+    let mut lexer = lex(src);
+
+    for (expected, range) in [("Größe", 0..7), ("温度", 8..14), ("_ünter2", 15..23), ("Cafe\u{301}", 24..30)]
+    {
+        assert_eq!(lexer.token, Identifier, "Token : {}", lexer.slice());
+        assert_eq!(lexer.slice(), expected);
+        // ranges are byte offsets into the source
+        assert_eq!(lexer.range(), range);
+        lexer.advance();
+    }
+
+    // an identifier can not start with a digit
+    assert_ne!(lexer.token, Identifier);
+}
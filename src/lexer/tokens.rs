@@ -26,6 +26,23 @@ pub enum Token {
     #[token("{sized}")]
     PropertySized,
 
+    #[token("{pure}")]
+    PropertyPure,
+
+    #[token("{not_null}")]
+    PropertyNotNull,
+
+    #[token("{nullable}")]
+    PropertyNullable,
+
+    /// an `{attribute 'name'}` or `{attribute 'name' := 'value'}` pragma
+    #[regex(r"\{[ \t]*attribute[ \t\r\n]+'[^']*'([ \t\r\n]*:=[ \t\r\n]*'[^']*')?[ \t]*\}")]
+    PropertyAttribute,
+
+    /// an `{address 16#1000}` pragma placing the following variable at an absolute address
+    #[regex(r"\{[ \t]*address[ \t\r\n]+[^}]*\}")]
+    PropertyAddress,
+
     #[token("PROGRAM", ignore(case))]
     KeywordProgram,
 
@@ -63,6 +80,9 @@ pub enum Token {
     #[token("VAR_CONFIG", ignore(case))]
     KeywordVarConfig,
 
+    #[token("VAR_ACCESS", ignore(case))]
+    KeywordVarAccess,
+
     #[token("ABSTRACT", ignore(case))]
     KeywordAbstract,
 
@@ -327,6 +347,19 @@ pub enum Token {
     #[token("ENDCASE", ignore(case))]
     KeywordEndCase,
 
+    // exception handling dialect extension, only recognized if enabled in the `ParseOptions`
+    #[token("__TRY", ignore(case))]
+    KeywordTry,
+
+    #[token("__CATCH", ignore(case))]
+    KeywordCatch,
+
+    #[token("__FINALLY", ignore(case))]
+    KeywordFinally,
+
+    #[token("END_TRY", ignore(case))]
+    KeywordEndTry,
+
     //Operators
     #[token("+")]
     OperatorPlus,
@@ -383,7 +416,9 @@ pub enum Token {
     OperatorNot,
 
     //Identifiers
-    #[regex(r"[a-zA-Z_][a-zA-Z_0-9]*")]
+    /// an identifier made of letters (including non-ASCII ones like `ö` or `温`), combining marks, digits and
+    /// underscores, which does not start with a digit
+    #[regex(r"[\p{L}_][\p{L}\p{M}\p{Nd}_]*")]
     Identifier,
 
     //Literals
@@ -435,9 +470,15 @@ pub enum Token {
     #[regex("\"((\\$.)|[^$\"])*\"")]
     LiteralWideString,
 
-    #[regex("[a-zA-Z_][a-zA-Z_0-9]*#")]
+    #[regex(r"[\p{L}_][\p{L}\p{M}\p{Nd}_]*#")]
     TypeCastPrefix,
 
+    /// a comment or a pragma the lexer skips, only yielded by `tokenize`
+    Comment,
+
+    /// whitespace the lexer skips, only yielded by `tokenize`
+    Whitespace,
+
     #[regex(r"[ \t\n\f]+", logos::skip)]
     End,
 }
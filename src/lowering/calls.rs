@@ -223,11 +223,17 @@ impl AstVisitorMut for AggregateTypeLowerer {
                 access: AccessModifier::Public,
                 constant: false,
                 retain: false,
+                persistent: false,
                 variables: vec![Variable {
                     name: pou.get_return_name().to_string(),
                     data_type_declaration: original_return,
                     initializer: None,
                     address: None,
+                    retain: None,
+                    attributes: vec![],
+                    explicit_en_eno: false,
+                    optional: false,
+                    absolute_address: None,
                     location: pou.name_location.clone(),
                 }],
                 kind: VariableBlockType::InOut,
@@ -413,6 +419,14 @@ impl AstVisitorMut for AggregateTypeLowerer {
                 );
                 self.steal_and_walk_list(&mut stmt.else_block);
             }
+            AstControlStatement::Try(stmt) => {
+                self.steal_and_walk_list(&mut stmt.body);
+                if let Some(ref mut variable) = stmt.catch_variable {
+                    variable.walk(self);
+                }
+                self.steal_and_walk_list(&mut stmt.catch_body);
+                self.steal_and_walk_list(&mut stmt.finally_body);
+            }
         }
     }
 }
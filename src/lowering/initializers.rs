@@ -182,6 +182,11 @@ fn create_init_unit(
                 },
                 initializer: None,
                 address: None,
+                retain: None,
+                attributes: vec![],
+                explicit_en_eno: false,
+                optional: false,
+                absolute_address: None,
                 location: location.clone(),
             },
         ])],
@@ -258,6 +263,11 @@ fn create_user_init_units(lowerer: &InitVisitor) -> Vec<CompilationUnit> {
                     },
                     initializer: None,
                     address: None,
+                    retain: None,
+                    attributes: vec![],
+                    explicit_en_eno: false,
+                    optional: false,
+                    absolute_address: None,
                     location: location.clone(),
                 }])];
 
@@ -453,6 +463,8 @@ fn new_pou(
 ) -> Pou {
     Pou {
         name: name.into(),
+        namespace: None,
+        keyword: None,
         id,
         variable_blocks,
         kind,
@@ -466,6 +478,12 @@ fn new_pou(
         interfaces: vec![],
         properties: vec![],
         is_const: false,
+        enable: None,
+        enable_out: None,
+        task: None,
+        is_pure: false,
+        doc_comment: None,
+        attributes: vec![],
     }
 }
 
@@ -494,6 +512,7 @@ fn new_unit(pou: Pou, implementation: Implementation, file_name: &'static str) -
     CompilationUnit {
         global_vars: vec![],
         var_config: Default::default(),
+        var_access: Default::default(),
         pous: vec![pou],
         implementations: vec![implementation],
         interfaces: vec![],
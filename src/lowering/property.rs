@@ -218,6 +218,8 @@ pub fn lower_to_pou(
         // First transform the property into a method (__get... or __set...)
         let mut pou = Pou {
             name: mangled_name,
+            namespace: None,
+            keyword: None,
             kind: PouType::Method {
                 parent: parent.to_string(),
                 property: Some((name.to_string(), property_impl.kind)),
@@ -235,6 +237,12 @@ pub fn lower_to_pou(
             is_const: false,
             id: provider.next_id(),
             properties: Vec::new(),
+            enable: None,
+            enable_out: None,
+            task: None,
+            is_pure: false,
+            doc_comment: None,
+            attributes: vec![],
         };
 
         // ...then transform any statement inside the property into an implementation
@@ -247,7 +255,7 @@ pub fn lower_to_pou(
             location: location.clone(),
             name_location: location.clone(),
             end_location: property_impl.end_location.clone(),
-            overriding: false,
+            overriding: property.overriding,
             generic: false,
             access: Some(AccessModifier::Public),
         };
@@ -259,11 +267,17 @@ pub fn lower_to_pou(
                     access: AccessModifier::Public,
                     constant: false,
                     retain: false,
+                    persistent: false,
                     variables: vec![Variable {
                         name: name.to_string(),
                         data_type_declaration: property.datatype.clone(),
                         initializer: None,
                         address: None,
+                        retain: None,
+                        attributes: vec![],
+                        explicit_en_eno: false,
+                        optional: false,
+                        absolute_address: None,
                         location: SourceLocation::internal(),
                     }],
                     kind: VariableBlockType::Local,
@@ -282,11 +296,17 @@ pub fn lower_to_pou(
                     access: AccessModifier::Public,
                     constant: false,
                     retain: false,
+                    persistent: false,
                     variables: vec![Variable {
                         name: name.to_string(),
                         data_type_declaration: property.datatype.clone(),
                         initializer: None,
                         address: None,
+                        retain: None,
+                        attributes: vec![],
+                        explicit_en_eno: false,
+                        optional: false,
+                        absolute_address: None,
                         location: SourceLocation::internal(),
                     }],
                     kind: VariableBlockType::Input(ArgumentProperty::ByVal),
@@ -562,6 +582,33 @@ mod tests {
             assert_eq!(unit.implementations[2].statements.len(), 5); // 5 assignments
         }
 
+        #[test]
+        fn overriding_property_is_lowered_to_overriding_methods() {
+            let source = r"
+            FUNCTION_BLOCK parent
+                PROPERTY foo : DINT
+                    GET END_GET
+                    SET END_SET
+                END_PROPERTY
+            END_FUNCTION_BLOCK
+
+            FUNCTION_BLOCK child EXTENDS parent
+                PROPERTY OVERRIDE foo : DINT
+                    GET END_GET
+                    SET END_SET
+                END_PROPERTY
+            END_FUNCTION_BLOCK
+            ";
+
+            let unit = lower(source);
+            let overriding =
+                |name: &str| unit.implementations.iter().find(|it| it.name == name).unwrap().overriding;
+            assert!(!overriding("parent.__get_foo"));
+            assert!(!overriding("parent.__set_foo"));
+            assert!(overriding("child.__get_foo"));
+            assert!(overriding("child.__set_foo"));
+        }
+
         #[test]
         fn get_and_set_retains_original_variable_blocks() {
             let source = r"
@@ -69,7 +69,7 @@
 use plc_ast::{
     ast::{
         AccessModifier, AstFactory, AstNode, CompilationUnit, DataType, DataTypeDeclaration, LinkageType,
-        Pou, PouType, UserTypeDeclaration, Variable, VariableBlock, VariableBlockType,
+        PointerKind, Pou, PouType, UserTypeDeclaration, Variable, VariableBlock, VariableBlockType,
     },
     provider::IdProvider,
 };
@@ -130,18 +130,26 @@ impl VirtualTableGenerator {
                             auto_deref: None,
                             type_safe: false,
                             is_function: false,
+                            kind: PointerKind::PointerTo,
+                            nullability: None,
                         }),
                         location: location.clone(),
                         scope: None,
                     },
                     initializer: None,
                     address: None,
+                    retain: None,
+                    attributes: vec![],
+                    explicit_en_eno: false,
+                    optional: false,
+                    absolute_address: None,
                     location: location.clone(),
                 }],
                 linkage: LinkageType::Internal,
                 access: AccessModifier::Protected,
                 constant: false,
                 retain: false,
+                persistent: false,
                 location: location.clone(),
             },
         );
@@ -169,6 +177,11 @@ impl VirtualTableGenerator {
                 },
                 initializer: Some(self.generate_initalizer(pou.name.as_str())),
                 address: None,
+                retain: None,
+                attributes: vec![],
+                explicit_en_eno: false,
+                optional: false,
+                absolute_address: None,
                 location: location.clone(),
             };
 
@@ -189,6 +202,11 @@ impl VirtualTableGenerator {
                 },
                 initializer: Some(self.generate_initalizer(method.get_name())),
                 address: None,
+                retain: None,
+                attributes: vec![],
+                explicit_en_eno: false,
+                optional: false,
+                absolute_address: None,
                 location: location.clone(),
             };
 
@@ -213,6 +231,11 @@ impl VirtualTableGenerator {
             },
             initializer: None,
             address: None,
+            retain: None,
+            attributes: vec![],
+            explicit_en_eno: false,
+            optional: false,
+            absolute_address: None,
             location: SourceLocation::internal_in_unit(pou.location.get_file_name()),
         }
     }
@@ -284,6 +307,8 @@ mod helper {
             auto_deref: None,
             type_safe: false,
             is_function: true,
+            kind: PointerKind::Function,
+            nullability: None,
         }
     }
 }
@@ -345,6 +370,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: false,
+                                kind: PointerTo,
+                                nullability: None,
                             },
                         },
                     },
@@ -384,6 +411,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: false,
+                                kind: PointerTo,
+                                nullability: None,
                             },
                         },
                     },
@@ -442,6 +471,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -531,6 +562,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -567,6 +600,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -627,6 +662,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -720,6 +757,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -756,6 +795,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -816,6 +857,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -909,6 +952,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -945,6 +990,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -990,6 +1037,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -1050,6 +1099,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -1095,6 +1146,8 @@ mod tests {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                         initializer: Some(
@@ -1,16 +1,24 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 
-use std::ops::Range;
+use std::{
+    hash::{Hash, Hasher},
+    ops::Range,
+};
 
 use plc_ast::{
     ast::{
-        AccessModifier, ArgumentProperty, AstFactory, AstNode, AstStatement, AutoDerefType, CompilationUnit,
-        ConfigVariable, DataType, DataTypeDeclaration, DeclarationKind, DirectAccessType, GenericBinding,
-        HardwareAccessType, Identifier, Implementation, Interface, LinkageType, PolymorphismMode, Pou,
-        PouType, PropertyBlock, PropertyImplementation, PropertyKind, ReferenceAccess, ReferenceExpr,
-        TypeNature, UserTypeDeclaration, Variable, VariableBlock, VariableBlockType,
+        flatten_expression_list, AccessDirection, AccessModifier, AccessVariable, ArgumentProperty,
+        Assignment, AstFactory, AstId, AstNode, AstStatement, Attribute, AutoDerefType, CallStatement,
+        CompilationUnit, ConfigVariable, DataType, DataTypeDeclaration, DeclarationKind, DirectAccessType,
+        GenericBinding, HardwareAccessType, Identifier, Implementation, Interface, LinkageType, Nullability,
+        PointerKind, PolymorphismMode, Pou, PouType, PropertyBlock, PropertyImplementation, PropertyKind,
+        RangeStatement, ReferenceAccess, ReferenceExpr, TaskAssociation, TypeNature, UserTypeDeclaration,
+        Variable, VariableBlock, VariableBlockType,
     },
+    control_statements::AstControlStatement,
+    literals::{Array, AstLiteral, StringValue},
     provider::IdProvider,
+    visitor::{AstVisitor, Walker},
 };
 use plc_diagnostics::{
     diagnostician::Diagnostician,
@@ -21,6 +29,7 @@ use plc_source::{
     SourceCode, SourceContainer,
 };
 use plc_util::convention::qualified_name;
+use rustc_hash::{FxHashMap, FxHasher};
 
 use crate::{
     expect_token,
@@ -28,7 +37,13 @@ use crate::{
         self, ParseSession,
         Token::{self, *},
     },
-    typesystem::DINT_TYPE,
+    typesystem::{
+        BOOL_TYPE, BYTE_TYPE, CHAR_TYPE, DATE_AND_TIME_TYPE, DATE_TYPE, DINT_TYPE, DWORD_TYPE, INT_TYPE,
+        LINT_TYPE, LONG_DATE_AND_TIME_TYPE, LONG_DATE_TYPE, LONG_TIME_OF_DAY_TYPE, LONG_TIME_TYPE,
+        LREAL_TYPE, LWORD_TYPE, REAL_TYPE, SHORT_DATE_AND_TIME_TYPE, SHORT_TIME_OF_DAY_TYPE, SINT_TYPE,
+        STRING_TYPE, TIME_OF_DAY_TYPE, TIME_TYPE, UDINT_TYPE, UINT_TYPE, ULINT_TYPE, USINT_TYPE, WCHAR_TYPE,
+        WORD_TYPE, WSTRING_TYPE,
+    },
 };
 
 use self::{
@@ -38,6 +53,9 @@ use self::{
 
 mod control_parser;
 pub mod expressions_parser;
+mod unparser;
+
+pub use unparser::unparse;
 
 #[cfg(test)]
 pub mod tests;
@@ -49,12 +67,7 @@ pub fn parse_file(
     id_provider: IdProvider,
     diagnostician: &mut Diagnostician,
 ) -> Result<CompilationUnit, Diagnostic> {
-    let location_factory = SourceLocationFactory::for_source(source);
-    let (unit, errors) = parse(
-        lexer::lex_with_ids(&source.source, id_provider, location_factory),
-        linkage,
-        source.get_location_str(),
-    );
+    let (unit, errors) = parse_file_lossy(source, linkage, id_provider);
     //Register the source file with the diagnostician
     //TODO: We should reduce the clone here
     diagnostician.register_file(source.get_location_str().to_string(), source.source.clone()); // TODO: Remove clone here, generally passing the GlobalContext instead of the actual source here or in the handle method should be sufficient
@@ -65,15 +78,670 @@ pub fn parse_file(
     }
 }
 
-pub fn parse(mut lexer: ParseSession, lnk: LinkageType, file_name: &'static str) -> ParsedAst {
+/// parses the given source file and returns the (possibly partial) unit together with all diagnostics,
+/// regardless of their severity. Unlike `parse_file`, critical parse errors do not discard the unit,
+/// which allows tools like language servers to work with whatever could be recovered.
+pub fn parse_file_lossy(source: &SourceCode, linkage: LinkageType, id_provider: IdProvider) -> ParsedAst {
+    let location_factory = SourceLocationFactory::for_source(source);
+    parse(
+        lexer::lex_with_ids(&source.source, id_provider, location_factory),
+        linkage,
+        source.get_location_str(),
+    )
+}
+
+/// parses all sources of the given container and merges them into a single unit. If the container points
+/// to a directory, every text source directly within it is parsed (in file-name order), otherwise the
+/// container itself is parsed. All elements keep the locations of the file they were declared in.
+/// Top-level names (POUs, interfaces and types) declared in more than one file are reported.
+pub fn parse_container(
+    container: &impl SourceContainer,
+    linkage: LinkageType,
+    id_provider: IdProvider,
+) -> ParsedAst {
+    let mut unit = CompilationUnit::new(container.get_location_str());
+    let mut diagnostics = vec![];
+
+    let sources = match container.get_location().filter(|it| it.is_dir()) {
+        Some(directory) => match collect_directory_sources(directory) {
+            Ok(paths) => paths.iter().map(|it| it.load_source(None)).collect::<Vec<_>>(),
+            Err(message) => vec![Err(message)],
+        },
+        None => vec![container.load_source(None)],
+    };
+
+    let mut declared: FxHashMap<String, SourceLocation> = FxHashMap::default();
+    for source in sources {
+        let source = match source {
+            Ok(source) => source,
+            Err(message) => {
+                diagnostics.push(Diagnostic::new(message).with_error_code("E002"));
+                continue;
+            }
+        };
+
+        let (other, errors) = parse_file_lossy(&source, linkage, id_provider.clone());
+        diagnostics.extend(errors);
+
+        let names = other
+            .pous
+            .iter()
+            .map(|it| (it.name.as_str(), &it.name_location))
+            .chain(other.interfaces.iter().map(|it| (it.ident.name.as_str(), &it.ident.location)))
+            .chain(other.user_types.iter().filter_map(|it| Some((it.data_type.get_name()?, &it.location))));
+        for (name, location) in names {
+            match declared.get(&name.to_lowercase()) {
+                Some(first) if first.get_file_name() != location.get_file_name() => diagnostics.push(
+                    Diagnostic::new(format!(
+                        "{name}: Duplicate symbol, already declared in '{}'",
+                        first.get_file_name().unwrap_or_default()
+                    ))
+                    .with_error_code("E004")
+                    .with_location(location)
+                    .with_secondary_location(first),
+                ),
+                Some(_) => {}
+                None => {
+                    declared.insert(name.to_lowercase(), location.clone());
+                }
+            }
+        }
+
+        unit.import(other);
+    }
+
+    (unit, diagnostics)
+}
+
+fn collect_directory_sources(directory: &std::path::Path) -> Result<Vec<std::path::PathBuf>, String> {
+    let mut paths = std::fs::read_dir(directory)
+        .map_err(|err| err.to_string())?
+        .filter_map(|entry| entry.ok().map(|it| it.path()))
+        .filter(|path| path.is_file() && matches!(path.get_type(), plc_source::SourceType::Text))
+        .collect::<Vec<_>>();
+    paths.sort();
+    Ok(paths)
+}
+
+/// reparses a single POU of the given source, e.g. after an edit within the POU. Only the given byte range
+/// is lexed and parsed, the resulting locations still refer to the whole source. The range has to start
+/// with the POU's keyword (`PROGRAM`, `FUNCTION`, ...), otherwise nothing is parsed and an error is reported.
+pub fn reparse_pou(
+    source: &SourceCode,
+    pou_range: Range<usize>,
+    id_provider: IdProvider,
+) -> (Vec<Pou>, Vec<Implementation>, Vec<Diagnostic>) {
+    let location_factory = SourceLocationFactory::for_source(source);
+    if source.source.get(pou_range.clone()).is_none() {
+        let diagnostic = Diagnostic::new(format!("The range {pou_range:?} does not lie within the source"))
+            .with_error_code("E139")
+            .with_location(location_factory.create_file_only_location());
+        return (vec![], vec![], vec![diagnostic]);
+    }
+
+    let lexer = lexer::lex_range_with_ids(&source.source, pou_range, id_provider, location_factory);
+    if !matches!(lexer.token, KeywordProgram | KeywordClass | KeywordFunction | KeywordFunctionBlock) {
+        let diagnostic = Diagnostic::new(format!("Expected a POU declaration, found '{}'", lexer.slice()))
+            .with_error_code("E139")
+            .with_location(lexer.location());
+        return (vec![], vec![], vec![diagnostic]);
+    }
+
+    let (unit, diagnostics) = parse(lexer, LinkageType::Internal, source.get_location_str());
+    (unit.pous, unit.implementations, diagnostics)
+}
+
+/// A 1-based line and column position in a source file. The column counts characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineCol {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// maps the byte range of the given location to its 1-based start and end line/column in `source`
+///
+/// Returns `None` if the location carries no range (e.g. internal locations) or if the range
+/// does not fall on character boundaries within the source.
+pub fn location_to_line_col(source: &SourceCode, loc: &SourceLocation) -> Option<(LineCol, LineCol)> {
+    let range = loc.to_range()?;
+    Some((offset_to_line_col(&source.source, range.start)?, offset_to_line_col(&source.source, range.end)?))
+}
+
+fn offset_to_line_col(text: &str, offset: usize) -> Option<LineCol> {
+    let preceding = text.get(..offset)?;
+    let line_start = preceding.rfind('\n').map(|it| it + 1).unwrap_or_default();
+    // a `\r` directly before the offset belongs to the CRLF line break, not to the line's content
+    let current_line = preceding[line_start..].strip_suffix('\r').unwrap_or(&preceding[line_start..]);
+    Some(LineCol { line: preceding.matches('\n').count() + 1, column: current_line.chars().count() + 1 })
+}
+
+/// The kind of a user defined type, see [`TypeInfo`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeKind {
+    Struct,
+    Enum,
+    Alias,
+    SubRange,
+    Array,
+    Pointer,
+    String,
+}
+
+/// A member of a user defined type, i.e. a struct's field or an enum's element
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberInfo {
+    pub name: String,
+    /// the member's type name, `None` for enum elements and inline type definitions
+    pub type_name: Option<String>,
+}
+
+/// A summary of a user defined type declared in a `TYPE ... END_TYPE` block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeInfo {
+    pub name: String,
+    pub kind: TypeKind,
+    /// the struct's fields or the enum's elements, empty for all other kinds
+    pub members: Vec<MemberInfo>,
+    /// the aliased, ranged, element or pointed-to type, if any
+    pub referenced_type: Option<String>,
+}
+
+/// lists the named user types declared in the given unit with their kind and members
+pub fn type_declarations(unit: &CompilationUnit) -> Vec<TypeInfo> {
+    unit.user_types
+        .iter()
+        .filter_map(|user_type| {
+            let name = user_type.data_type.get_name()?.to_string();
+            let (kind, members, referenced_type) = match &user_type.data_type {
+                DataType::StructType { variables, .. } => {
+                    let members = variables
+                        .iter()
+                        .map(|it| MemberInfo {
+                            name: it.get_name().to_string(),
+                            type_name: it.data_type_declaration.get_name().map(str::to_string),
+                        })
+                        .collect();
+                    (TypeKind::Struct, members, None)
+                }
+                DataType::EnumType { elements, numeric_type, .. } => {
+                    let members = elements
+                        .get_as_list()
+                        .into_iter()
+                        .filter_map(|element| match element.get_stmt() {
+                            AstStatement::Assignment(Assignment { left, .. }) => {
+                                left.get_flat_reference_name()
+                            }
+                            _ => element.get_flat_reference_name(),
+                        })
+                        .map(|name| MemberInfo { name: name.to_string(), type_name: None })
+                        .collect();
+                    (TypeKind::Enum, members, Some(numeric_type.clone()))
+                }
+                DataType::SubRangeType { referenced_type, bounds, .. } => {
+                    let kind = if bounds.is_some() { TypeKind::SubRange } else { TypeKind::Alias };
+                    (kind, vec![], Some(referenced_type.clone()))
+                }
+                DataType::ArrayType { referenced_type, .. } => {
+                    (TypeKind::Array, vec![], referenced_type.get_name().map(str::to_string))
+                }
+                DataType::PointerType { referenced_type, .. } => {
+                    (TypeKind::Pointer, vec![], referenced_type.get_name().map(str::to_string))
+                }
+                DataType::StringType { .. } => (TypeKind::String, vec![], None),
+                DataType::VarArgs { .. } | DataType::GenericType { .. } => return None,
+            };
+            Some(TypeInfo { name, kind, members, referenced_type })
+        })
+        .collect()
+}
+
+/// returns the source span covering the POU with the given name (case-insensitive) together with all of
+/// its actions, which are parsed into separate implementations (e.g. from a following `ACTIONS` block)
+pub fn pou_full_extent(unit: &CompilationUnit, pou_name: &str) -> Option<SourceLocation> {
+    let pou = unit.pous.iter().find(|it| it.name.eq_ignore_ascii_case(pou_name))?;
+    let extent = unit
+        .implementations
+        .iter()
+        .filter(|it| it.pou_type == PouType::Action && it.type_name.eq_ignore_ascii_case(pou_name))
+        .fold(pou.location.clone(), |extent, action| {
+            // the action's end location is its `END_ACTION` keyword
+            match (extent.to_range(), action.end_location.to_range()) {
+                (Some(current), Some(end)) if end.end > current.end => extent.span(&action.end_location),
+                _ => extent,
+            }
+        });
+    Some(extent)
+}
+
+/// returns the locations of all assignments in the given implementation's body writing to the variable
+/// with the given name (case-insensitive), including writes to its members, elements or dereferences
+/// (e.g. `x.a := 1`, `x[2] := 1` or `x^ := 1`)
+pub fn assignments_to(implementation: &Implementation, name: &str) -> Vec<SourceLocation> {
+    let mut collector = AssignmentCollector { name, locations: vec![] };
+    implementation.statements().iter().for_each(|it| collector.visit(it));
+    collector.locations
+}
+
+struct AssignmentCollector<'a> {
+    name: &'a str,
+    locations: Vec<SourceLocation>,
+}
+
+impl AssignmentCollector<'_> {
+    fn collect(&mut self, assignment: &Assignment, node: &AstNode) {
+        if get_root_name(&assignment.left).is_some_and(|it| it.eq_ignore_ascii_case(self.name)) {
+            self.locations.push(node.get_location());
+        }
+        assignment.walk(self);
+    }
+}
+
+impl AstVisitor for AssignmentCollector<'_> {
+    fn visit_assignment(&mut self, stmt: &Assignment, node: &AstNode) {
+        self.collect(stmt, node)
+    }
+
+    fn visit_ref_assignment(&mut self, stmt: &Assignment, node: &AstNode) {
+        self.collect(stmt, node)
+    }
+
+    fn visit_call_statement(&mut self, stmt: &CallStatement, _node: &AstNode) {
+        // the left side of a parameter assignment (e.g. `x` in `foo(x := 1)`) names a parameter,
+        // so only the arguments themselves are visited
+        self.visit(&stmt.operator);
+        for parameter in stmt.parameters.iter().flat_map(|it| flatten_expression_list(it)) {
+            match parameter.get_stmt() {
+                AstStatement::Assignment(Assignment { right, .. })
+                | AstStatement::OutputAssignment(Assignment { right, .. })
+                | AstStatement::RefAssignment(Assignment { right, .. }) => self.visit(right),
+                _ => self.visit(parameter),
+            }
+        }
+    }
+}
+
+/// returns the name of the variable a reference is rooted at (e.g. `x` for `x.a[2]^`)
+fn get_root_name(node: &AstNode) -> Option<&str> {
+    match node.get_stmt() {
+        AstStatement::ReferenceExpr(ReferenceExpr { base: Some(base), .. }) => get_root_name(base),
+        AstStatement::ReferenceExpr(ReferenceExpr {
+            access: ReferenceAccess::Member(member),
+            base: None,
+        }) => member.get_flat_reference_name(),
+        AstStatement::Identifier(name) => Some(name),
+        AstStatement::ParenExpression(expression) => get_root_name(expression),
+        _ => None,
+    }
+}
+
+/// The kind of a literal, see [`LiteralEntry`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiteralKind {
+    Integer,
+    Real,
+    Bool,
+    String,
+    WideString,
+    Date,
+    DateAndTime,
+    TimeOfDay,
+    Time,
+}
+
+/// A literal constant found in a compilation unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiteralEntry {
+    pub kind: LiteralKind,
+    /// the literal's value, strings without their quotes and date and time literals in nanoseconds
+    pub value: String,
+    pub location: SourceLocation,
+}
+
+/// returns all literals of the given unit, both from declarations (e.g. initializers) and from bodies,
+/// array literals are not listed themselves but their elements are
+pub fn literals(unit: &CompilationUnit) -> Vec<LiteralEntry> {
+    let mut collector = LiteralCollector { literals: vec![] };
+    collector.visit_compilation_unit(unit);
+    collector.literals
+}
+
+struct LiteralCollector {
+    literals: Vec<LiteralEntry>,
+}
+
+impl AstVisitor for LiteralCollector {
+    fn visit_literal(&mut self, stmt: &AstLiteral, node: &AstNode) {
+        let nanos = |value: Result<i64, String>| value.map(|it| it.to_string()).unwrap_or_default();
+        let (kind, value) = match stmt {
+            AstLiteral::Integer(value) => (LiteralKind::Integer, value.to_string()),
+            AstLiteral::Real(value) => (LiteralKind::Real, value.clone()),
+            AstLiteral::Bool(value) => (LiteralKind::Bool, value.to_string()),
+            AstLiteral::String(StringValue { value, is_wide: false }) => (LiteralKind::String, value.clone()),
+            AstLiteral::String(StringValue { value, is_wide: true }) => {
+                (LiteralKind::WideString, value.clone())
+            }
+            AstLiteral::Date(date) => (LiteralKind::Date, nanos(date.value())),
+            AstLiteral::DateAndTime(date_time) => (LiteralKind::DateAndTime, nanos(date_time.value())),
+            AstLiteral::TimeOfDay(time_of_day) => (LiteralKind::TimeOfDay, nanos(time_of_day.value())),
+            AstLiteral::Time(time) => (LiteralKind::Time, time.value().to_string()),
+            AstLiteral::Array(Array { elements }) => {
+                elements.iter().for_each(|it| self.visit(it));
+                return;
+            }
+            AstLiteral::Null => return,
+        };
+        self.literals.push(LiteralEntry { kind, value, location: node.get_location() });
+    }
+}
+
+/// returns the control-flow nesting depth of every statement in the bodies of the given unit, keyed by
+/// the statement's id. statements on the top level of a body have a depth of 0, statements inside an
+/// IF, CASE, loop or TRY one more than the statement containing them
+pub fn statement_depths(unit: &CompilationUnit) -> FxHashMap<AstId, usize> {
+    let mut depths = FxHashMap::default();
+    for implementation in &unit.implementations {
+        collect_statement_depths(&implementation.statements, 0, &mut depths);
+    }
+    depths
+}
+
+fn collect_statement_depths(statements: &[AstNode], depth: usize, depths: &mut FxHashMap<AstId, usize>) {
+    for statement in statements {
+        depths.insert(statement.get_id(), depth);
+
+        let AstStatement::ControlStatement(control_statement) = statement.get_stmt() else { continue };
+        let bodies: Vec<&[AstNode]> = match control_statement {
+            AstControlStatement::If(stmt) => {
+                stmt.blocks.iter().map(|it| it.body.as_slice()).chain([stmt.else_block.as_slice()]).collect()
+            }
+            AstControlStatement::ForLoop(stmt) => vec![stmt.body.as_slice()],
+            AstControlStatement::WhileLoop(stmt) | AstControlStatement::RepeatLoop(stmt) => {
+                vec![stmt.body.as_slice()]
+            }
+            AstControlStatement::Case(stmt) => stmt
+                .case_blocks
+                .iter()
+                .map(|it| it.body.as_slice())
+                .chain([stmt.else_block.as_slice()])
+                .collect(),
+            AstControlStatement::Try(stmt) => {
+                vec![stmt.body.as_slice(), stmt.catch_body.as_slice(), stmt.finally_body.as_slice()]
+            }
+        };
+        for body in bodies {
+            collect_statement_depths(body, depth + 1, depths);
+        }
+    }
+}
+
+/// returns the source of the POU with the given name, e.g. to attach a minimal reproduction to a bug
+/// report. methods are part of the POU's source. The actions declared after the POU are included by
+/// extending the source up to the end of the last action (and its `END_ACTIONS`), so the returned text can
+/// be parsed on its own. It may contain declarations in between the POU and its actions
+pub fn extract_pou_source(source: &str, unit: &CompilationUnit, pou_name: &str) -> Option<String> {
+    let pou = unit.pous.iter().find(|it| it.name.eq_ignore_ascii_case(pou_name))?;
+    let range = pou.location.to_range()?;
+
+    let last_action_end = unit
+        .implementations
+        .iter()
+        .filter(|it| it.pou_type == PouType::Action && it.type_name.eq_ignore_ascii_case(&pou.name))
+        .filter_map(|it| it.end_location.to_range())
+        .map(|it| it.end)
+        .filter(|end| *end > range.end)
+        .max();
+    let end = match last_action_end {
+        Some(end) => {
+            // close the `ACTIONS` block the last action is declared in
+            let following = lexer::lex(source.get(end..)?);
+            if following.token == KeywordEndActions {
+                end + following.range().end
+            } else {
+                end
+            }
+        }
+        None => range.end,
+    };
+    source.get(range.start..end).map(str::to_string)
+}
+
+/// returns a hash of the signature of the given POU: its name, kind, generics, return type and parameters.
+/// The body, comments and locations are ignored, so a POU whose body changed keeps its hash, e.g. to decide
+/// whether the dependents of a POU have to be rebuilt. Names are compared case-insensitively, like in ST.
+/// The hash is stable across runs of the compiler.
+pub fn signature_hash(pou: &Pou) -> u64 {
+    let mut hasher = FxHasher::default();
+    pou.name.to_lowercase().hash(&mut hasher);
+    std::mem::discriminant(&pou.kind).hash(&mut hasher);
+    if let PouType::Method { parent, property, declaration_kind } = &pou.kind {
+        parent.to_lowercase().hash(&mut hasher);
+        property.as_ref().map(|(name, kind)| (name.to_lowercase(), kind)).hash(&mut hasher);
+        declaration_kind.hash(&mut hasher);
+    }
+    for generic in &pou.generics {
+        generic.name.to_lowercase().hash(&mut hasher);
+        generic.nature.hash(&mut hasher);
+        generic.constraint_types.iter().map(|it| it.to_lowercase()).for_each(|it| it.hash(&mut hasher));
+    }
+    pou.return_type.is_some().hash(&mut hasher);
+    if let Some(return_type) = &pou.return_type {
+        hash_type_declaration(return_type, &mut hasher);
+    }
+    let parameter_blocks = pou.variable_blocks.iter().filter(|it| {
+        matches!(it.kind, VariableBlockType::Input(_) | VariableBlockType::Output | VariableBlockType::InOut)
+    });
+    for block in parameter_blocks {
+        block.kind.hash(&mut hasher);
+        for variable in &block.variables {
+            variable.name.to_lowercase().hash(&mut hasher);
+            hash_type_declaration(&variable.data_type_declaration, &mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// hashes the given type declaration for `signature_hash`, ignoring locations and the case of names.
+/// Expressions within the type (e.g. array bounds) are hashed in their unparsed form
+fn hash_type_declaration(declaration: &DataTypeDeclaration, hasher: &mut FxHasher) {
+    std::mem::discriminant(declaration).hash(hasher);
+    match declaration {
+        DataTypeDeclaration::Reference { referenced_type, .. }
+        | DataTypeDeclaration::Aggregate { referenced_type, .. } => {
+            referenced_type.to_lowercase().hash(hasher);
+        }
+        DataTypeDeclaration::Definition { data_type, .. } => hash_data_type(data_type, hasher),
+    }
+}
+
+fn hash_data_type(data_type: &DataType, hasher: &mut FxHasher) {
+    let lowercase = |name: &Option<String>| name.as_ref().map(|it| it.to_lowercase());
+    let expression = |node: &AstNode| unparser::expression(node).to_lowercase();
+
+    std::mem::discriminant(data_type).hash(hasher);
+    match data_type {
+        DataType::StructType { name, variables } => {
+            lowercase(name).hash(hasher);
+            for variable in variables {
+                variable.name.to_lowercase().hash(hasher);
+                hash_type_declaration(&variable.data_type_declaration, hasher);
+            }
+        }
+        DataType::EnumType { name, numeric_type, elements } => {
+            lowercase(name).hash(hasher);
+            numeric_type.to_lowercase().hash(hasher);
+            expression(elements).hash(hasher);
+        }
+        DataType::SubRangeType { name, referenced_type, bounds } => {
+            lowercase(name).hash(hasher);
+            referenced_type.to_lowercase().hash(hasher);
+            bounds.as_ref().map(expression).hash(hasher);
+        }
+        DataType::ArrayType { name, bounds, referenced_type, is_variable_length } => {
+            lowercase(name).hash(hasher);
+            expression(bounds).hash(hasher);
+            hash_type_declaration(referenced_type, hasher);
+            is_variable_length.hash(hasher);
+        }
+        DataType::PointerType {
+            name,
+            referenced_type,
+            auto_deref,
+            type_safe,
+            is_function,
+            kind,
+            nullability,
+        } => {
+            lowercase(name).hash(hasher);
+            hash_type_declaration(referenced_type, hasher);
+            auto_deref.hash(hasher);
+            type_safe.hash(hasher);
+            is_function.hash(hasher);
+            kind.hash(hasher);
+            nullability.hash(hasher);
+        }
+        DataType::StringType { name, is_wide, size, encoding } => {
+            lowercase(name).hash(hasher);
+            is_wide.hash(hasher);
+            size.as_ref().map(expression).hash(hasher);
+            lowercase(encoding).hash(hasher);
+        }
+        DataType::VarArgs { referenced_type, sized } => {
+            referenced_type.is_some().hash(hasher);
+            if let Some(referenced_type) = referenced_type {
+                hash_type_declaration(referenced_type, hasher);
+            }
+            sized.hash(hasher);
+        }
+        DataType::GenericType { name, generic_symbol, nature } => {
+            name.to_lowercase().hash(hasher);
+            generic_symbol.to_lowercase().hash(hasher);
+            nature.hash(hasher);
+        }
+    }
+}
+
+/// returns the names of all POUs of the given unit implementing the given interface. Qualified names are
+/// matched by their last segment, so `ns.foo` and `foo` denote the same interface
+pub fn implementors(unit: &CompilationUnit, interface_name: &str) -> Vec<String> {
+    let last_segment = |name: &str| name.rsplit('.').next().unwrap_or_default().to_lowercase();
+    let interface_name = last_segment(interface_name);
+    unit.pous
+        .iter()
+        .filter(|pou| pou.interfaces.iter().any(|it| last_segment(&it.name) == interface_name))
+        .map(|pou| pou.name.clone())
+        .collect()
+}
+
+/// returns a `(derived, base)` pair for every POU of the given unit extending another POU, e.g. to visualize
+/// the class hierarchy. Of a POU extending multiple bases (see E114) only the first base is recorded
+pub fn inheritance_edges(unit: &CompilationUnit) -> Vec<(String, String)> {
+    unit.pous
+        .iter()
+        .filter_map(|pou| pou.super_class.as_ref().map(|base| (pou.name.clone(), base.name.clone())))
+        .collect()
+}
+
+/// returns the location and source text of every top-level statement in the body of the given implementation,
+/// in the order of the body, e.g. for a statement-level coverage tool. `source` is the text the implementation
+/// was parsed from, statements without a location in it (e.g. generated ones) are left out
+pub fn statements_with_text(source: &str, implementation: &Implementation) -> Vec<(SourceLocation, String)> {
+    implementation
+        .statements
+        .iter()
+        .filter_map(|statement| {
+            let text = source.get(statement.get_location().to_range()?)?;
+            Some((statement.get_location(), text.to_string()))
+        })
+        .collect()
+}
+
+/// Feature flags to adapt the parser to different dialects of Structured Text.
+///
+/// The default options reflect the parser's standard behavior.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Record the declaration keyword of every POU as written in the source (see [`Pou::keyword`]),
+    /// so formatters can reproduce the original casing
+    pub record_keyword_casing: bool,
+    /// Warn about POUs whose body contains more top-level statements than this threshold
+    pub max_statements_per_pou: Option<usize>,
+    /// Recognize the `__TRY`, `__CATCH`, `__FINALLY` and `END_TRY` keywords of dialects with exception
+    /// handling, otherwise they are treated as identifiers
+    pub exception_handling: bool,
+    /// Record the explicitly declared `EN`/`ENO` parameters of function blocks as their enable and
+    /// enable-out parameters (see [`Pou::enable`]), otherwise they are ordinary variables
+    pub en_eno_parameters: bool,
+    /// Accept methods declared in a `PROGRAM`, which is not covered by the standard. If disabled, such
+    /// methods are reported and skipped
+    pub allow_program_methods: bool,
+    /// Parse the file as a formula, a list of `;`-separated expressions without any POU. The expressions
+    /// are wrapped into an implicit program named [`FORMULA_NAME`], declarations are reported
+    pub formula: bool,
+    /// Tag every diagnostic reported within a POU, method or interface with its qualified name, e.g. `fb.m`
+    /// for the method `m` of `fb` (see [`Diagnostic::get_scope`]), to group the diagnostics in an outline
+    pub diagnostic_scopes: bool,
+    /// Report `CASE` labels repeating the integer literal of a previous label of the same `CASE` while
+    /// parsing, without waiting for the validation which also resolves constant labels
+    pub duplicate_case_labels: bool,
+}
+
+/// The name of the implicit program holding the expressions of a formula, see [`ParseOptions::formula`]
+pub const FORMULA_NAME: &str = "__formula";
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            record_keyword_casing: false,
+            max_statements_per_pou: None,
+            exception_handling: false,
+            en_eno_parameters: false,
+            allow_program_methods: true,
+            formula: false,
+            diagnostic_scopes: false,
+            duplicate_case_labels: false,
+        }
+    }
+}
+
+pub fn parse(lexer: ParseSession, lnk: LinkageType, file_name: &'static str) -> ParsedAst {
+    parse_with_options(lexer, lnk, file_name, ParseOptions::default())
+}
+
+pub fn parse_with_options(
+    mut lexer: ParseSession,
+    lnk: LinkageType,
+    file_name: &'static str,
+    options: ParseOptions,
+) -> ParsedAst {
+    lexer.options = options;
     let mut unit = CompilationUnit::new(file_name);
 
     let mut linkage = lnk;
     let mut constant = false;
+    let mut pure = None;
+    let mut formula = Vec::new();
     loop {
+        if lexer.options.formula && is_declaration_keyword(&lexer.token) {
+            lexer.accept_diagnostic(
+                Diagnostic::new("Declarations are not allowed in a formula")
+                    .with_error_code("E133")
+                    .with_location(lexer.location()),
+            );
+        }
         match lexer.token {
             PropertyExternal => {
-                linkage = LinkageType::External;
+                // builtins can not be turned into external declarations
+                if lnk == LinkageType::BuiltIn {
+                    lexer.accept_diagnostic(
+                        Diagnostic::new(
+                            "Pragma {external} contradicts the builtin linkage of the declaration",
+                        )
+                        .with_error_code("E136")
+                        .with_location(lexer.location()),
+                    );
+                } else {
+                    linkage = LinkageType::External;
+                }
                 lexer.advance();
                 //Don't reset linkage
                 continue;
@@ -84,6 +752,12 @@ pub fn parse(mut lexer: ParseSession, lnk: LinkageType, file_name: &'static str)
                 lexer.advance();
                 continue;
             }
+            PropertyPure => {
+                // the location is kept to report the pragma on anything but a function
+                pure = Some(lexer.location());
+                lexer.advance();
+                continue;
+            }
             KeywordInterface => {
                 // We ignore any method implementations in interfaces as we do not support default impls yet
                 let (interfaces, _) = parse_interface(&mut lexer);
@@ -91,6 +765,7 @@ pub fn parse(mut lexer: ParseSession, lnk: LinkageType, file_name: &'static str)
             }
             KeywordVarGlobal => unit.global_vars.push(parse_variable_block(&mut lexer, linkage)),
             KeywordVarConfig => unit.var_config.extend(parse_config_variables(&mut lexer)),
+            KeywordVarAccess => unit.var_access.extend(parse_access_variables(&mut lexer)),
 
             KeywordProgram | KeywordClass | KeywordFunction | KeywordFunctionBlock => {
                 let params = match lexer.token {
@@ -100,10 +775,7 @@ pub fn parse(mut lexer: ParseSession, lnk: LinkageType, file_name: &'static str)
                     _ => (PouType::FunctionBlock, KeywordEndFunctionBlock),
                 };
 
-                parse_pou(&mut lexer, &mut unit, params.0, linkage, params.1, constant);
-
-                // reset const pragma
-                constant = false;
+                parse_pou(&mut lexer, &mut unit, params.0, linkage, params.1, constant, pure.take());
             }
             KeywordAction => {
                 if let Some(implementation) = parse_action(&mut lexer, linkage, None) {
@@ -134,7 +806,14 @@ pub fn parse(mut lexer: ParseSession, lnk: LinkageType, file_name: &'static str)
                     unit.user_types.push(utype);
                 }
             }
-            KeywordEndActions | End => return (unit, lexer.diagnostics),
+            KeywordEndActions | End => break,
+            // redundant semicolons between top-level items, e.g. `END_FUNCTION;`, are tolerated
+            KeywordSemicolon if !lexer.options.formula => lexer.advance(),
+            // local variable blocks have no place in a formula, they are reported above and dropped
+            _ if lexer.options.formula && is_variable_block_keyword(&lexer.token) => {
+                parse_variable_block(&mut lexer, linkage);
+            }
+            _ if lexer.options.formula => parse_formula_statements(&mut lexer, &mut formula),
             _ => {
                 lexer.accept_diagnostic(Diagnostic::unexpected_token_found(
                     "StartKeyword",
@@ -144,9 +823,97 @@ pub fn parse(mut lexer: ParseSession, lnk: LinkageType, file_name: &'static str)
                 lexer.advance();
             }
         };
+        // pragmas only apply to the declaration following them
         linkage = lnk;
+        constant = false;
+        // a `{pure}` still pending here preceded a TYPE, VAR_GLOBAL, ACTION or INTERFACE
+        if let Some(location) = pure.take() {
+            report_misplaced_pure(&mut lexer, &location);
+        }
+    }
+
+    if lexer.options.formula {
+        add_formula(&mut lexer, &mut unit, lnk, formula);
     }
-    //the match in the loop will always return
+    (unit, lexer.diagnostics)
+}
+
+fn is_declaration_keyword(token: &Token) -> bool {
+    is_variable_block_keyword(token)
+        || matches!(
+            token,
+            KeywordVarGlobal
+                | KeywordVarConfig
+                | KeywordVarAccess
+                | KeywordType
+                | KeywordInterface
+                | KeywordProgram
+                | KeywordClass
+                | KeywordFunction
+                | KeywordFunctionBlock
+                | KeywordAction
+                | KeywordActions
+        )
+}
+
+/// Parses the statements of a formula up to the next declaration, see [`ParseOptions::formula`]
+fn parse_formula_statements(lexer: &mut ParseSession, statements: &mut Vec<AstNode>) {
+    while !matches!(lexer.token, End | KeywordEndActions) && !is_declaration_keyword(&lexer.token) {
+        if lexer.try_consume(KeywordSemicolon) {
+            continue;
+        }
+        statements.push(parse_control(lexer));
+    }
+}
+
+/// Wraps the statements of a formula into the implicit program [`FORMULA_NAME`]
+fn add_formula(
+    lexer: &mut ParseSession,
+    unit: &mut CompilationUnit,
+    linkage: LinkageType,
+    statements: Vec<AstNode>,
+) {
+    let location = match (statements.first(), statements.last()) {
+        (Some(first), Some(last)) => first.get_location().span(&last.get_location()),
+        _ => lexer.location(),
+    };
+    unit.pous.push(Pou {
+        id: lexer.next_id(),
+        name: FORMULA_NAME.into(),
+        namespace: None,
+        keyword: None,
+        kind: PouType::Program,
+        variable_blocks: vec![],
+        return_type: None,
+        location: location.clone(),
+        name_location: location.clone(),
+        poly_mode: None,
+        generics: vec![],
+        linkage,
+        super_class: None,
+        is_const: false,
+        interfaces: vec![],
+        properties: vec![],
+        enable: None,
+        enable_out: None,
+        task: None,
+        is_pure: false,
+        doc_comment: None,
+        attributes: vec![],
+    });
+    unit.implementations.push(Implementation {
+        name: FORMULA_NAME.into(),
+        type_name: FORMULA_NAME.into(),
+        linkage,
+        pou_type: PouType::Program,
+        statements,
+        location: location.clone(),
+        name_location: location.clone(),
+        end_location: lexer.location(),
+        overriding: false,
+        generic: false,
+        access: None,
+    });
 }
 fn parse_actions(
     lexer: &mut ParseSession,
@@ -214,20 +981,13 @@ fn parse_interface(lexer: &mut ParseSession) -> (Interface, Vec<Implementation>)
             lexer.try_consume(KeywordComma);
         }
     }
-    loop {
+    with_scope(lexer, name.clone(), |lexer| loop {
         match lexer.token {
             KeywordMethod => {
                 if let Some((method, imp)) =
                     parse_method(lexer, &name, DeclarationKind::Abstract, LinkageType::Internal, false)
                 {
-                    // This is temporary? At some point we'll support them but for now it's a diagnostic
-                    if !imp.statements.is_empty() {
-                        lexer.accept_diagnostic(
-                            Diagnostic::new("Interfaces can not have a default implementation")
-                                .with_error_code("E113")
-                                .with_location(&imp.statements.first().unwrap().location),
-                        );
-                    }
+                    report_default_implementation(lexer, imp.statements());
 
                     methods.push(method);
                     implementations.push(imp);
@@ -236,12 +996,8 @@ fn parse_interface(lexer: &mut ParseSession) -> (Interface, Vec<Implementation>)
 
             KeywordProperty => {
                 if let Some(property) = parse_property(lexer) {
-                    for property in property.implementations.iter().filter(|imp| !imp.body.is_empty()) {
-                        lexer.accept_diagnostic(
-                            Diagnostic::new("Interfaces can not have a default implementation")
-                                .with_error_code("E113")
-                                .with_location(&property.body.first().unwrap().location),
-                        );
+                    for implementation in &property.implementations {
+                        report_default_implementation(lexer, implementation.statements());
                     }
 
                     properties.push(property);
@@ -250,8 +1006,9 @@ fn parse_interface(lexer: &mut ParseSession) -> (Interface, Vec<Implementation>)
 
             _ => break,
         }
-    }
+    });
 
+    report_duplicate_interface_methods(lexer, &name, &methods);
     lexer.try_consume_or_report(KeywordEndInterface);
     let location_end = lexer.range().start;
 
@@ -268,6 +1025,72 @@ fn parse_interface(lexer: &mut ParseSession) -> (Interface, Vec<Implementation>)
     )
 }
 
+/// Returns the explicitly declared `EN` or `ENO` parameter with the given name, see `parse_variable_block`
+fn find_en_eno(variable_blocks: &[VariableBlock], name: &str) -> Option<Identifier> {
+    variable_blocks
+        .iter()
+        .flat_map(|it| &it.variables)
+        .find(|it| it.explicit_en_eno && it.name.eq_ignore_ascii_case(name))
+        .map(|it| Identifier { name: it.name.clone(), location: it.location.clone() })
+}
+
+fn report_misplaced_pure(lexer: &mut ParseSession, location: &SourceLocation) {
+    lexer.accept_diagnostic(
+        Diagnostic::new("Only functions can be marked as {pure}")
+            .with_error_code("E146")
+            .with_location(location),
+    );
+}
+
+/// Returns the current keyword as written in the source, if the parser is asked to record keyword casing
+fn record_keyword_casing(lexer: &ParseSession) -> Option<String> {
+    lexer.options.record_keyword_casing.then(|| lexer.slice().to_string())
+}
+
+/// Returns the comments between the previous and the current token without their delimiters, see
+/// [`Pou::doc_comment`]. A comment on the same line as the previous token (e.g. `END_FUNCTION // foo`)
+/// still belongs to the previous declaration and is skipped.
+fn parse_doc_comment(lexer: &ParseSession) -> Option<String> {
+    let skipped_range = lexer.last_range.end..lexer.range().start;
+    let skipped = lexer.get_src().get(skipped_range.clone())?;
+
+    let mut on_previous_line = lexer.last_token != End;
+    let mut comments = vec![];
+    for (token, range) in lexer::split_skipped_text(skipped, skipped_range.start) {
+        let text = &lexer.get_src()[range];
+        match token {
+            Whitespace => on_previous_line &= !text.contains('\n'),
+            // skipped pragmas are not comments
+            Comment if !on_previous_line && !text.starts_with('{') => {
+                comments.push(strip_comment_delimiters(text))
+            }
+            _ => {}
+        }
+    }
+    (!comments.is_empty()).then(|| comments.join("\n"))
+}
+
+fn strip_comment_delimiters(comment: &str) -> &str {
+    comment
+        .strip_prefix("//")
+        .or_else(|| comment.strip_prefix("(*").and_then(|it| it.strip_suffix("*)")))
+        .or_else(|| comment.strip_prefix("/*").and_then(|it| it.strip_suffix("*/")))
+        .unwrap_or(comment)
+        .trim()
+}
+
+/// Reports the given body if it is not empty, as interface methods and properties can not have one
+fn report_default_implementation(lexer: &mut ParseSession, statements: &[AstNode]) {
+    // This is temporary? At some point we'll support them but for now it's a diagnostic
+    if let Some(first) = statements.first() {
+        lexer.accept_diagnostic(
+            Diagnostic::new("Interfaces can not have a default implementation")
+                .with_error_code("E113")
+                .with_location(&first.location),
+        );
+    }
+}
+
 ///
 /// parse a pou
 /// # Arguments
@@ -276,6 +1099,7 @@ fn parse_interface(lexer: &mut ParseSession) -> (Interface, Vec<Implementation>)
 /// * `pou_type`    - the type of the pou currently parsed
 /// * `linkage`     - internal, external ?
 /// * `expected_end_token` - the token that ends this pou
+/// * `pure`        - the location of a preceding `{pure}` pragma, if any
 ///
 fn parse_pou(
     lexer: &mut ParseSession,
@@ -284,14 +1108,27 @@ fn parse_pou(
     linkage: LinkageType,
     expected_end_token: lexer::Token,
     constant: bool,
+    pure: Option<SourceLocation>,
 ) {
     if constant && !matches!(linkage, LinkageType::BuiltIn) {
         lexer.accept_diagnostic(Diagnostic::const_pragma_is_not_allowed(
             lexer.last_location().span(&lexer.location()),
         ));
     }
+    if let Some(location) = pure.as_ref().filter(|_| kind != PouType::Function) {
+        report_misplaced_pure(lexer, location);
+    }
+    let is_pure = pure.is_some() && kind == PouType::Function;
 
     let start = lexer.range().start;
+    let doc_comment = parse_doc_comment(lexer);
+    let attributes = lexer
+        .attributes
+        .clone()
+        .into_iter()
+        .map(|it| parse_attribute(lexer.slice_region(it)))
+        .collect::<Vec<_>>();
+    let keyword = record_keyword_casing(lexer);
     lexer.advance(); //Consume ProgramKeyword
     let closing_tokens = vec![
         expected_end_token,
@@ -306,15 +1143,15 @@ fn parse_pou(
         // check in validator if pou type allows polymorphism
         let poly_mode = parse_polymorphism_mode(lexer, &kind);
 
-        let (name, name_location) =
-            parse_identifier(lexer).unwrap_or_else(|| ("".to_string(), SourceLocation::undefined())); // parse POU name
+        let (namespace, name, name_location) = parse_pou_name(lexer);
 
         let generics = parse_generics(lexer);
 
+        let task = if kind == PouType::Program { parse_task_association(lexer) } else { None };
+
         with_scope(lexer, name.clone(), |lexer| {
             // TODO: Parse USING directives
-            let super_class = parse_super_class(lexer);
-            let interfaces = parse_interface_declarations(lexer);
+            let (super_class, interfaces) = parse_super_class_and_interfaces(lexer, &kind);
 
             // parse an optional return type
             // classes do not have a return type (check in validator)
@@ -335,6 +1172,12 @@ fn parse_pou(
                 variable_blocks.push(parse_variable_block(lexer, LinkageType::Internal));
             }
 
+            let (enable, enable_out) = if lexer.options.en_eno_parameters && kind == PouType::FunctionBlock {
+                (find_en_eno(&variable_blocks, "EN"), find_en_eno(&variable_blocks, "ENO"))
+            } else {
+                (None, None)
+            };
+
             let mut impl_pous = Vec::new();
             let mut implementations = Vec::new();
             let mut properties = Vec::new();
@@ -362,9 +1205,18 @@ fn parse_pou(
                     }
                 } else {
                     let is_const = lexer.try_consume(PropertyConstant);
-                    if let Some((pou, implementation)) =
-                        parse_method(lexer, &name, DeclarationKind::Concrete, linkage, is_const)
-                    {
+                    let is_rejected = kind == PouType::Program && !lexer.options.allow_program_methods;
+                    if is_rejected {
+                        lexer.accept_diagnostic(
+                            Diagnostic::new("Methods are not allowed in a PROGRAM")
+                                .with_error_code("E131")
+                                .with_location(lexer.location()),
+                        );
+                    }
+
+                    // a rejected method is still parsed to skip it
+                    let method = parse_method(lexer, &name, DeclarationKind::Concrete, linkage, is_const);
+                    if let Some((pou, implementation)) = method.filter(|_| !is_rejected) {
                         impl_pous.push(pou);
                         implementations.push(implementation);
                     }
@@ -373,7 +1225,7 @@ fn parse_pou(
 
             // a class may not contain an implementation
             // check in validator
-            implementations.push(parse_implementation(
+            let mut implementation = parse_implementation(
                 lexer,
                 linkage,
                 kind.clone(),
@@ -381,10 +1233,14 @@ fn parse_pou(
                 &name,
                 !generics.is_empty(),
                 name_location.clone(),
-            ));
+            );
+            parse_misplaced_variable_blocks(lexer, &mut variable_blocks, &mut implementation);
+            implementations.push(implementation);
 
             let mut pous = vec![Pou {
                 name,
+                namespace,
+                keyword,
                 id: lexer.next_id(),
                 kind,
                 variable_blocks,
@@ -398,6 +1254,12 @@ fn parse_pou(
                 interfaces,
                 is_const: constant,
                 properties,
+                enable,
+                enable_out,
+                task,
+                is_pure,
+                doc_comment,
+                attributes,
             }];
             pous.append(&mut impl_pous);
 
@@ -419,6 +1281,28 @@ fn parse_pou(
     unit.implementations.append(&mut implementations);
 }
 
+/// parses an optional `WITH task : program_type;` clause associating a program with a task, `WITH` is
+/// not reserved so it is only recognized following the name of a program
+fn parse_task_association(lexer: &mut ParseSession) -> Option<TaskAssociation> {
+    if lexer.token != Identifier || !lexer.slice().eq_ignore_ascii_case("WITH") {
+        return None;
+    }
+    let start = lexer.range().start;
+    lexer.advance();
+
+    let (name, location) = parse_identifier(lexer)?;
+    let task = Identifier { name, location };
+    let program_type = if lexer.try_consume(KeywordColon) {
+        parse_identifier(lexer).map(|(name, location)| Identifier { name, location })
+    } else {
+        None
+    };
+    let location = lexer.source_range_factory.create_range(start..lexer.last_range.end);
+    lexer.try_consume(KeywordSemicolon);
+
+    Some(TaskAssociation { task, program_type, location })
+}
+
 fn parse_generics(lexer: &mut ParseSession) -> Vec<GenericBinding> {
     if lexer.try_consume(Token::OperatorLess) {
         parse_any_in_region(lexer, vec![Token::OperatorGreater], |lexer| {
@@ -426,12 +1310,30 @@ fn parse_generics(lexer: &mut ParseSession) -> Vec<GenericBinding> {
             loop {
                 //identifier
                 if let Some((name, _)) = parse_identifier(lexer) {
+                    // a list of concrete types may replace the nature, e.g. `T : {INT, DINT}`
+                    let mut constraint_types = (lexer.token == Token::KeywordColon)
+                        .then(|| parse_generic_constraint_types(lexer, &name))
+                        .flatten();
                     lexer.try_consume_or_report(Token::KeywordColon);
 
-                    //Expect a type nature
-                    if let Some(nature) = parse_identifier(lexer).map(|(it, _)| parse_type_nature(lexer, &it))
-                    {
-                        generics.push(GenericBinding { name, nature });
+                    let nature = if constraint_types.is_none() || lexer.token == Identifier {
+                        // or follow it, e.g. `T : ANY_INT {INT, DINT}`
+                        let following = (lexer.token == Identifier)
+                            .then(|| parse_generic_constraint_types(lexer, &name))
+                            .flatten();
+                        //Expect a type nature
+                        let nature = parse_identifier(lexer).map(|(it, _)| parse_type_nature(lexer, &it));
+                        if let Some(following) = following {
+                            constraint_types.get_or_insert_with(Vec::new).extend(following);
+                        }
+                        nature
+                    } else {
+                        Some(TypeNature::Any)
+                    };
+
+                    if let Some(nature) = nature {
+                        let constraint_types = constraint_types.unwrap_or_default();
+                        generics.push(GenericBinding { name, nature, constraint_types });
                     }
                 }
 
@@ -447,6 +1349,77 @@ fn parse_generics(lexer: &mut ParseSession) -> Vec<GenericBinding> {
     }
 }
 
+/// parses the brace-delimited list of concrete types directly following the current token, constraining
+/// the generic `name`, e.g. `{INT, DINT}` in `T : {INT, DINT}`. Returns `None` if there is no such list.
+/// Entries which are not type names are reported and left out, whether a type exists is validated once all
+/// units are indexed
+fn parse_generic_constraint_types(lexer: &mut ParseSession, name: &str) -> Option<Vec<String>> {
+    let range = lexer.brace_list_after_token()?;
+
+    let mut list_lexer = lexer::lex_range_with_ids(
+        lexer.get_src(),
+        range,
+        lexer.id_provider.clone(),
+        lexer.source_range_factory.clone(),
+    );
+    let mut constraint_types = vec![];
+    loop {
+        // a type name, possibly qualified, e.g. `INT` or `ns.MyStruct`
+        let start = list_lexer.range().start;
+        let mut tokens = vec![];
+        while !matches!(list_lexer.token, KeywordComma | End) {
+            tokens.push(list_lexer.token);
+            list_lexer.advance();
+        }
+        let is_type_name = tokens.len() % 2 == 1
+            && tokens.iter().enumerate().all(|(index, token)| match index % 2 {
+                0 => matches!(token, Identifier | KeywordString | KeywordWideString),
+                _ => *token == KeywordDot,
+            });
+
+        if !tokens.is_empty() {
+            let type_name = list_lexer.slice_region(start..list_lexer.last_range.end).to_string();
+            if is_type_name {
+                constraint_types.push(type_name);
+            } else {
+                let location = list_lexer.source_range_factory.create_range(start..list_lexer.last_range.end);
+                lexer.accept_diagnostic(
+                    Diagnostic::new(format!(
+                        "`{type_name}` is not a valid type name in the constraint of generic `{name}`"
+                    ))
+                    .with_error_code("E144")
+                    .with_location(location),
+                );
+            }
+        }
+
+        if !list_lexer.try_consume(KeywordComma) {
+            break;
+        }
+    }
+    Some(constraint_types)
+}
+
+/// reports methods declared more than once (case-insensitive) directly in the same interface
+fn report_duplicate_interface_methods(lexer: &mut ParseSession, interface: &str, methods: &[Pou]) {
+    let mut declared: FxHashMap<String, &Pou> = FxHashMap::default();
+    for method in methods {
+        let name = method.get_return_name();
+        if let Some(first) = declared.get(&name.to_lowercase()) {
+            lexer.accept_diagnostic(
+                Diagnostic::new(format!(
+                    "Method `{name}` is declared multiple times in interface `{interface}`"
+                ))
+                .with_error_code("E004")
+                .with_location(&method.name_location)
+                .with_secondary_location(&first.name_location),
+            );
+        } else {
+            declared.insert(name.to_lowercase(), method);
+        }
+    }
+}
+
 /// Parses the comma seperated identifiers after an `IMPLEMENTS` keyword, e.g. `bar` and `baz` in
 /// `INTERFACE foo IMPLEMENTS bar`
 fn parse_interface_declarations(lexer: &mut ParseSession) -> Vec<Identifier> {
@@ -530,11 +1503,39 @@ fn parse_polymorphism_mode(lexer: &mut ParseSession, pou_type: &PouType) -> Opti
     }
 }
 
-fn parse_super_class(lexer: &mut ParseSession) -> Option<Identifier> {
+/// parses the `EXTENDS` and `IMPLEMENTS` clauses of a POU, which may appear in any order
+/// (e.g. `FUNCTION_BLOCK foo IMPLEMENTS bar EXTENDS baz`)
+fn parse_super_class_and_interfaces(
+    lexer: &mut ParseSession,
+    kind: &PouType,
+) -> (Option<Identifier>, Vec<Identifier>) {
     let mut extensions = vec![];
-    while lexer.try_consume(KeywordExtends) {
-        let name_and_location = parse_identifier(lexer)?;
-        extensions.push(name_and_location);
+    let mut interfaces = vec![];
+    loop {
+        match lexer.token {
+            KeywordExtends => {
+                lexer.advance();
+                let Some(name_and_location) = parse_identifier(lexer) else { break };
+                extensions.push(name_and_location);
+            }
+            KeywordImplements => {
+                // the interfaces are still parsed to recover from the error
+                let pou_keyword = match kind {
+                    PouType::Function => Some("FUNCTION"),
+                    PouType::Program => Some("PROGRAM"),
+                    _ => None,
+                };
+                if let Some(pou_keyword) = pou_keyword {
+                    lexer.accept_diagnostic(
+                        Diagnostic::new(format!("{pou_keyword} cannot implement interfaces"))
+                            .with_error_code("E110")
+                            .with_location(lexer.location()),
+                    );
+                }
+                interfaces.extend(parse_interface_declarations(lexer))
+            }
+            _ => break,
+        }
     }
     extensions.iter().skip(1).for_each(|(_, location)| {
         lexer.accept_diagnostic(
@@ -544,9 +1545,10 @@ fn parse_super_class(lexer: &mut ParseSession) -> Option<Identifier> {
         )
     });
 
-    extensions
+    let super_class = extensions
         .first()
-        .map(|(name, location)| Identifier { name: name.to_string(), location: location.clone() })
+        .map(|(name, location)| Identifier { name: name.to_string(), location: location.clone() });
+    (super_class, interfaces)
 }
 
 fn parse_return_type(lexer: &mut ParseSession) -> Option<DataTypeDeclaration> {
@@ -614,6 +1616,7 @@ fn parse_method(
         }
 
         let method_start = lexer.range().start;
+        let keyword = record_keyword_casing(lexer);
         lexer.advance(); // eat METHOD keyword
 
         let access = Some(parse_access_modifier(lexer));
@@ -624,56 +1627,69 @@ fn parse_method(
         let generics = parse_generics(lexer);
         let return_type = parse_return_type(lexer);
 
-        let mut variable_blocks = vec![];
-        while lexer.token == KeywordVar
-            || lexer.token == KeywordVarInput
-            || lexer.token == KeywordVarOutput
-            || lexer.token == KeywordVarInOut
-            || lexer.token == KeywordVarTemp
-        {
-            variable_blocks.push(parse_variable_block(lexer, LinkageType::Internal));
-        }
+        // the method's name qualifies the declarations within it, see `ParseSession::qualified_scope`
+        with_scope(lexer, name, |lexer| {
+            let mut variable_blocks = vec![];
+            while lexer.token == KeywordVar
+                || lexer.token == KeywordVarInput
+                || lexer.token == KeywordVarOutput
+                || lexer.token == KeywordVarInOut
+                || lexer.token == KeywordVarTemp
+            {
+                variable_blocks.push(parse_variable_block(lexer, LinkageType::Internal));
+            }
 
-        let call_name = qualified_name(parent, &name);
-        let implementation = parse_implementation(
-            lexer,
-            linkage,
-            pou_kind.clone(),
-            &call_name,
-            &call_name,
-            !generics.is_empty(),
-            name_location.clone(),
-        );
+            let call_name = lexer.qualified_scope().expect("the method's scope was entered above");
+            let mut implementation = parse_implementation(
+                lexer,
+                linkage,
+                pou_kind.clone(),
+                &call_name,
+                &call_name,
+                !generics.is_empty(),
+                name_location.clone(),
+            );
+            parse_misplaced_variable_blocks(lexer, &mut variable_blocks, &mut implementation);
 
-        // parse_implementation() will default-initialize the fields it
-        // doesn't know. thus, we have to complete the information.
-        let implementation = Implementation { overriding, access, ..implementation };
+            // parse_implementation() will default-initialize the fields it
+            // doesn't know. thus, we have to complete the information.
+            let implementation = Implementation { overriding, access, ..implementation };
 
-        let method_end = lexer.range().end;
-        Some((
-            Pou {
-                name: call_name,
-                id: lexer.next_id(),
-                kind: pou_kind,
-                variable_blocks,
-                return_type,
-                location: lexer.source_range_factory.create_range(method_start..method_end),
-                name_location,
-                poly_mode,
-                generics,
-                linkage,
-                super_class: None,
-                interfaces: Vec::new(),
-                properties: Vec::new(),
-                is_const: constant,
-            },
-            implementation,
-        ))
+            let method_end = lexer.range().end;
+            Some((
+                Pou {
+                    name: call_name,
+                    namespace: None,
+                    keyword,
+                    id: lexer.next_id(),
+                    kind: pou_kind,
+                    variable_blocks,
+                    return_type,
+                    location: lexer.source_range_factory.create_range(method_start..method_end),
+                    name_location,
+                    poly_mode,
+                    generics,
+                    linkage,
+                    super_class: None,
+                    interfaces: Vec::new(),
+                    properties: Vec::new(),
+                    is_const: constant,
+                    enable: None,
+                    enable_out: None,
+                    task: None,
+                    is_pure: false,
+                    doc_comment: None,
+                    attributes: vec![],
+                },
+                implementation,
+            ))
+        })
     })
 }
 
 fn parse_property(lexer: &mut ParseSession) -> Option<PropertyBlock> {
     lexer.advance(); // Move past `PROPERTY` keyword
+    let overriding = lexer.try_consume(KeywordOverride);
 
     let mut has_error = false;
 
@@ -740,7 +1756,12 @@ fn parse_property(lexer: &mut ParseSession) -> Option<PropertyBlock> {
 
     let (name, name_location) = identifier.expect("covered above");
     let datatype = datatype.expect("covered above");
-    Some(PropertyBlock { ident: Identifier { name, location: name_location }, datatype, implementations })
+    Some(PropertyBlock {
+        ident: Identifier { name, location: name_location },
+        datatype,
+        implementations,
+        overriding,
+    })
 }
 
 fn parse_access_modifier(lexer: &mut ParseSession) -> AccessModifier {
@@ -757,6 +1778,55 @@ fn parse_access_modifier(lexer: &mut ParseSession) -> AccessModifier {
     }
 }
 
+/// parses a POU's name which may be qualified with a namespace (e.g. `FUNCTION Math.Add`), returns the
+/// namespace, the fully qualified name and the location of the whole name
+fn parse_pou_name(lexer: &mut ParseSession) -> (Option<String>, String, SourceLocation) {
+    let Some((mut name, mut name_location)) = parse_identifier(lexer) else {
+        return (None, "".to_string(), SourceLocation::undefined());
+    };
+
+    let mut namespace_segments = vec![];
+    while lexer.try_consume(KeywordDot) {
+        let Some((segment, segment_location)) = parse_identifier(lexer) else { break };
+        namespace_segments.push(std::mem::replace(&mut name, segment));
+        name_location = name_location.span(&segment_location);
+    }
+
+    if namespace_segments.is_empty() {
+        return (None, name, name_location);
+    }
+    let namespace = namespace_segments.join(".");
+    let qualified_name = qualified_name(namespace.as_str(), name.as_str());
+    (Some(namespace), qualified_name, name_location)
+}
+
+/// whether the current token is a reserved keyword meant as the name of a declared variable, e.g. `TYPE` in
+/// `TYPE : INT;`. A keyword is only considered to be meant as a name if a `:`, `,` or `AT` follows it
+fn is_keyword_used_as_name(lexer: &ParseSession) -> bool {
+    let keyword = lexer.slice();
+    let is_word = keyword.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && keyword.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    lexer.token != Identifier && is_word && matches!(lexer.peek(), KeywordColon | KeywordComma | KeywordAt)
+}
+
+/// reports a reserved keyword used as the name of a declared variable (see `is_keyword_used_as_name`), the
+/// declaration is still parsed with the keyword as its name
+fn accept_keyword_as_name(lexer: &mut ParseSession) -> bool {
+    if !is_keyword_used_as_name(lexer) {
+        return false;
+    }
+
+    lexer.accept_diagnostic(
+        Diagnostic::new(format!(
+            "'{}' is a reserved keyword and cannot be used as an identifier",
+            lexer.slice()
+        ))
+        .with_error_code("E145")
+        .with_location(lexer.location()),
+    );
+    true
+}
+
 /// parse identifier and advance if successful
 /// returns the identifier as a String and the SourceRange of the parsed name
 fn parse_identifier(lexer: &mut ParseSession) -> Option<(String, SourceLocation)> {
@@ -787,6 +1857,16 @@ fn parse_implementation(
     let statements = parse_body_standalone(lexer);
     let end_location = lexer.location(); //Location of the current token, which shoudl be the
                                          //end token
+    if let Some(max) = lexer.options.max_statements_per_pou.filter(|max| statements.len() > *max) {
+        lexer.accept_diagnostic(
+            Diagnostic::new(format!(
+                "`{call_name}` contains {} statements, which exceeds the configured maximum of {max}",
+                statements.len()
+            ))
+            .with_error_code("E127")
+            .with_location(&name_location),
+        );
+    }
     Implementation {
         name: call_name.into(),
         type_name: type_name.into(),
@@ -802,6 +1882,39 @@ fn parse_implementation(
     }
 }
 
+/// Variable blocks have to precede the body of a POU, blocks following its first statements are reported.
+/// To recover, such a block is parsed into the POU's `variable_blocks` and the rest of the body is appended
+/// to the `implementation`
+fn parse_misplaced_variable_blocks(
+    lexer: &mut ParseSession,
+    variable_blocks: &mut Vec<VariableBlock>,
+    implementation: &mut Implementation,
+) {
+    while is_variable_block_keyword(&lexer.token) {
+        lexer.accept_diagnostic(
+            Diagnostic::new("Variable blocks must be declared before the body")
+                .with_error_code("E132")
+                .with_location(lexer.location()),
+        );
+        variable_blocks.push(parse_variable_block(lexer, LinkageType::Internal));
+        implementation.statements.append(&mut parse_body_standalone(lexer));
+        implementation.location = implementation.location.span(&lexer.last_location());
+        implementation.end_location = lexer.location();
+    }
+}
+
+fn is_variable_block_keyword(token: &Token) -> bool {
+    matches!(
+        token,
+        KeywordVar
+            | KeywordVarInput
+            | KeywordVarOutput
+            | KeywordVarInOut
+            | KeywordVarTemp
+            | KeywordVarExternal
+    )
+}
+
 fn parse_action(
     lexer: &mut ParseSession,
     linkage: LinkageType,
@@ -857,19 +1970,32 @@ fn parse_type(lexer: &mut ParseSession) -> Vec<UserTypeDeclaration> {
     parse_any_in_region(lexer, vec![KeywordEndType], |lexer| {
         let mut declarations = vec![];
         while !lexer.closes_open_region(&lexer.token) {
-            let name = lexer.slice_and_advance();
-            let name_location = lexer.last_location();
+            // stray semicolons between the declarations are tolerated
+            if lexer.try_consume(KeywordSemicolon) {
+                continue;
+            }
+
+            // read in a comma separated list of type names sharing the same definition
+            let mut names = vec![(lexer.slice_and_advance(), lexer.last_location())];
+            while lexer.try_consume(KeywordComma) {
+                names.extend(parse_identifier(lexer));
+            }
             lexer.try_consume_or_report(KeywordColon);
 
-            let result = parse_full_data_type_definition(lexer, Some(name));
+            let result = parse_full_data_type_definition(lexer, Some(names[0].0.clone()));
 
             if let Some((DataTypeDeclaration::Definition { data_type, .. }, initializer)) = result {
-                declarations.push(UserTypeDeclaration {
-                    data_type: *data_type,
-                    initializer,
-                    location: name_location,
-                    scope: lexer.scope.clone(),
-                });
+                // create a declaration with the same data type for each of the names
+                for (name, name_location) in names {
+                    let mut data_type = data_type.as_ref().clone();
+                    data_type.set_name(name);
+                    declarations.push(UserTypeDeclaration {
+                        data_type,
+                        initializer: initializer.clone(),
+                        location: name_location,
+                        scope: lexer.pou_scope(),
+                    });
+                }
             }
         }
         declarations
@@ -882,15 +2008,25 @@ fn parse_full_data_type_definition(
     lexer: &mut ParseSession,
     name: Option<String>,
 ) -> Option<DataTypeWithInitializer> {
-    let end_keyword = if lexer.token == KeywordStruct { KeywordEndStruct } else { KeywordSemicolon };
-    let parsed_datatype = parse_any_in_region(lexer, vec![end_keyword], |lexer| {
+    parse_full_data_type_definition_with(lexer, name, |_| ()).map(|(definition, _)| definition)
+}
+
+/// parses a full data type definition like `parse_full_data_type_definition`, but additionally runs
+/// `parse_trailing` right after the definition, before the terminating `;` closes its region
+fn parse_full_data_type_definition_with<T>(
+    lexer: &mut ParseSession,
+    name: Option<String>,
+    parse_trailing: impl FnOnce(&mut ParseSession) -> T,
+) -> Option<(DataTypeWithInitializer, T)> {
+    let is_struct = lexer.token == KeywordStruct;
+    let parse_definition = |lexer: &mut ParseSession| {
         let sized = lexer.try_consume(PropertySized);
-        if lexer.try_consume(KeywordDotDotDot) {
+        let definition = if lexer.try_consume(KeywordDotDotDot) {
             Some((
                 DataTypeDeclaration::Definition {
                     data_type: Box::new(DataType::VarArgs { referenced_type: None, sized }),
                     location: lexer.last_location(),
-                    scope: lexer.scope.clone(),
+                    scope: lexer.pou_scope(),
                 },
                 None,
             ))
@@ -904,7 +2040,7 @@ fn parse_full_data_type_definition(
                                 sized,
                             }),
                             location: lexer.last_location(),
-                            scope: lexer.scope.clone(),
+                            scope: lexer.pou_scope(),
                         },
                         None,
                     )
@@ -912,12 +2048,21 @@ fn parse_full_data_type_definition(
                     (type_def, initializer)
                 }
             })
-        }
-    });
+        };
+        definition.map(|definition| (definition, parse_trailing(lexer)))
+    };
+
+    // structs open their own `END_STRUCT` region (see `parse_struct_type_definition`), so that nested
+    // anonymous structs each consume exactly their own `END_STRUCT`
+    let parsed_datatype = if is_struct {
+        parse_definition(lexer)
+    } else {
+        parse_any_in_region(lexer, vec![KeywordSemicolon], parse_definition)
+    };
 
     // The standard allows semicolons at the end of an `END_STRUCT` keyword, hence if we parsed
     // a struct, try to also consume a semicolon if it exists
-    if end_keyword == KeywordEndStruct {
+    if is_struct {
         lexer.try_consume(KeywordSemicolon);
     }
 
@@ -930,17 +2075,15 @@ fn parse_data_type_definition(
     name: Option<String>,
 ) -> Option<DataTypeWithInitializer> {
     let start = lexer.location();
-    if lexer.try_consume(KeywordStruct) {
-        // Parse struct
-        let variables = parse_variable_list(lexer);
-        Some((
-            DataTypeDeclaration::Definition {
-                data_type: Box::new(DataType::StructType { name, variables }),
-                location: start.span(&lexer.location()),
-                scope: lexer.scope.clone(),
-            },
-            None,
-        ))
+    // e.g. `{not_null} REF_TO INT`
+    if let Some(nullability) = parse_nullability(lexer) {
+        return parse_data_type_definition(lexer, name).map(|(definition, initializer)| {
+            (apply_nullability(lexer, definition, nullability, start), initializer)
+        });
+    }
+
+    if lexer.try_consume(KeywordStruct) {
+        parse_struct_type_definition(lexer, name, start)
     } else if lexer.try_consume(KeywordArray) {
         parse_array_type_definition(lexer, name)
     } else if lexer.try_consume(KeywordPointer) {
@@ -959,9 +2102,9 @@ fn parse_data_type_definition(
             lexer.advance();
         }
 
-        parse_pointer_definition(lexer, name, start_pos, None, false, false)
+        parse_pointer_definition(lexer, name, start_pos, PointerKind::PointerTo)
     } else if lexer.try_consume(KeywordRef) {
-        parse_pointer_definition(lexer, name, lexer.last_range.start, None, true, false)
+        parse_pointer_definition(lexer, name, lexer.last_range.start, PointerKind::RefTo)
     } else if lexer.try_consume(KeywordParensOpen) {
         parse_enum_type_definition(lexer, name)
     } else if lexer.token == KeywordString || lexer.token == KeywordWideString {
@@ -979,15 +2122,81 @@ fn parse_data_type_definition(
     }
 }
 
+/// parses the members of a `STRUCT` up to and including its `END_STRUCT` keyword. The members are
+/// parsed in a region of their own, hence a nested anonymous struct never consumes the `END_STRUCT`
+/// of its parent and an unbalanced `STRUCT` is reported as a missing `END_STRUCT`
+fn parse_struct_type_definition(
+    lexer: &mut ParseSession,
+    name: Option<String>,
+    start: SourceLocation,
+) -> Option<DataTypeWithInitializer> {
+    let variables = parse_any_in_region(lexer, vec![KeywordEndStruct], parse_variable_list);
+    Some((
+        DataTypeDeclaration::Definition {
+            data_type: Box::new(DataType::StructType { name, variables }),
+            location: start.span(&lexer.last_location()),
+            scope: lexer.pou_scope(),
+        },
+        None,
+    ))
+}
+
+/// parses an optional `{not_null}` or `{nullable}` pragma
+fn parse_nullability(lexer: &mut ParseSession) -> Option<Nullability> {
+    let nullability = match lexer.token {
+        PropertyNotNull => Nullability::NotNull,
+        PropertyNullable => Nullability::Nullable,
+        _ => return None,
+    };
+    lexer.advance();
+    Some(nullability)
+}
+
+/// records the nullability of the pragma at `location` preceding the given data type, only pointer types
+/// can be annotated
+fn apply_nullability(
+    lexer: &mut ParseSession,
+    mut declaration: DataTypeDeclaration,
+    nullability: Nullability,
+    location: SourceLocation,
+) -> DataTypeDeclaration {
+    if let DataTypeDeclaration::Definition { data_type, .. } = &mut declaration {
+        if let DataType::PointerType { nullability: pointer_nullability, .. } = data_type.as_mut() {
+            *pointer_nullability = Some(nullability);
+            return declaration;
+        }
+    }
+
+    let pragma = match nullability {
+        Nullability::NotNull => "{not_null}",
+        Nullability::Nullable => "{nullable}",
+    };
+    lexer.accept_diagnostic(
+        Diagnostic::new(format!("The pragma {pragma} can only be applied to pointer types"))
+            .with_error_code("E141")
+            .with_location(location),
+    );
+    declaration
+}
+
 fn parse_pointer_definition(
     lexer: &mut ParseSession,
     name: Option<String>,
     start_pos: usize,
-    auto_deref: Option<AutoDerefType>,
-    type_safe: bool,
-    is_function: bool,
+    kind: PointerKind,
 ) -> Option<(DataTypeDeclaration, Option<AstNode>)> {
+    let (auto_deref, type_safe, is_function) = match kind {
+        PointerKind::PointerTo => (None, false, false),
+        PointerKind::RefTo => (None, true, false),
+        PointerKind::ReferenceTo => (Some(AutoDerefType::Reference), true, false),
+        PointerKind::Alias => (Some(AutoDerefType::Alias), true, false),
+        PointerKind::Function => (None, false, true),
+    };
+    // e.g. `REF_TO {not_null} INT`
+    let nullability = parse_nullability(lexer);
     parse_data_type_definition(lexer, None).map(|(decl, initializer)| {
+        // the pointer spans up to the end of the referenced type, excluding a possible initializer
+        let end = decl.get_location().to_range().map_or(lexer.last_range.end, |it| it.end);
         (
             DataTypeDeclaration::Definition {
                 data_type: Box::new(DataType::PointerType {
@@ -996,10 +2205,11 @@ fn parse_pointer_definition(
                     auto_deref,
                     type_safe,
                     is_function,
+                    kind,
+                    nullability,
                 }),
-                // FIXME: this currently includes the initializer in the sourcelocation, resulting in 'REF_TO A := B' when creating a slice
-                location: lexer.source_range_factory.create_range(start_pos..lexer.last_range.end),
-                scope: lexer.scope.clone(),
+                location: lexer.source_range_factory.create_range(start_pos..end),
+                scope: lexer.pou_scope(),
             },
             initializer,
         )
@@ -1041,21 +2251,21 @@ fn parse_type_reference_type_definition(
         let data_type = match bounds {
             Some(AstNode { stmt: AstStatement::ExpressionList(expressions), id, location, .. }) => {
                 //this is an enum
+                let elements = AstFactory::create_expression_list(expressions, location, id);
+                validate_enum_element_values(lexer, &referenced_type, &elements);
                 DataTypeDeclaration::Definition {
-                    data_type: Box::new(DataType::EnumType {
-                        name,
-                        numeric_type: referenced_type,
-                        elements: AstFactory::create_expression_list(expressions, location, id),
-                    }),
+                    data_type: Box::new(DataType::EnumType { name, numeric_type: referenced_type, elements }),
                     location: lexer.source_range_factory.create_range(start..end),
-                    scope: lexer.scope.clone(),
+                    scope: lexer.pou_scope(),
                 }
             }
             Some(AstNode {
                 stmt: AstStatement::ReferenceExpr(ReferenceExpr { access: ReferenceAccess::Member(_), .. }),
                 ..
-            }) => {
+            })
+            | Some(AstNode { stmt: AstStatement::Assignment(_), .. }) => {
                 // a enum with just one element
+                validate_enum_element_values(lexer, &referenced_type, bounds.as_ref().unwrap());
                 DataTypeDeclaration::Definition {
                     data_type: Box::new(DataType::EnumType {
                         name,
@@ -1063,15 +2273,18 @@ fn parse_type_reference_type_definition(
                         elements: bounds.unwrap(),
                     }),
                     location: lexer.source_range_factory.create_range(start..end),
-                    scope: lexer.scope.clone(),
+                    scope: lexer.pou_scope(),
                 }
             }
-            _ => DataTypeDeclaration::Definition {
+            _ => {
                 //something else inside the brackets -> probably a subrange?
-                data_type: Box::new(DataType::SubRangeType { name, referenced_type, bounds }),
-                location: lexer.source_range_factory.create_range(start..end),
-                scope: lexer.scope.clone(),
-            },
+                validate_subrange_initializer(lexer, bounds.as_ref(), initial_value.as_ref());
+                DataTypeDeclaration::Definition {
+                    data_type: Box::new(DataType::SubRangeType { name, referenced_type, bounds }),
+                    location: lexer.source_range_factory.create_range(start..end),
+                    scope: lexer.pou_scope(),
+                }
+            }
         };
         Some((data_type, initial_value))
     } else {
@@ -1123,6 +2336,25 @@ fn parse_string_size_expression(lexer: &mut ParseSession) -> Option<AstNode> {
     }
 }
 
+/// parses an optional code-page annotation following a string's size, e.g. the `(CP1252)` in
+/// `STRING[10](CP1252)`. The encoding is not validated, but kept exactly as written.
+fn parse_string_encoding(lexer: &mut ParseSession) -> Option<String> {
+    if !lexer.try_consume(KeywordParensOpen) {
+        return None;
+    }
+
+    let start = lexer.range().start;
+    parse_any_in_region(lexer, vec![KeywordParensClose], |lexer| {
+        let mut end = start;
+        while !lexer.closes_open_region(&lexer.token) {
+            end = lexer.range().end;
+            lexer.advance();
+        }
+        let encoding = lexer.slice_region(start..end);
+        (!encoding.is_empty()).then(|| encoding.to_string())
+    })
+}
+
 fn parse_string_type_definition(
     lexer: &mut ParseSession,
     name: Option<String>,
@@ -1146,14 +2378,17 @@ fn parse_string_type_definition(
             Some(DataTypeDeclaration::Definition {
                 data_type: Box::new(DataType::EnumType { name, numeric_type: text, elements: size }),
                 location,
-                scope: lexer.scope.clone(),
+                scope: lexer.pou_scope(),
+            })
+        }
+        (Some(size), _, false) => {
+            let encoding = parse_string_encoding(lexer);
+            Some(DataTypeDeclaration::Definition {
+                data_type: Box::new(DataType::StringType { name, is_wide, size: Some(size), encoding }),
+                location: lexer.source_range_factory.create_range(start..lexer.last_range.end),
+                scope: lexer.pou_scope(),
             })
         }
-        (Some(size), _, false) => Some(DataTypeDeclaration::Definition {
-            data_type: Box::new(DataType::StringType { name, is_wide, size: Some(size) }),
-            location,
-            scope: lexer.scope.clone(),
-        }),
         (None, Some(name), _) => Some(DataTypeDeclaration::Definition {
             data_type: Box::new(DataType::SubRangeType {
                 name: Some(name.into()),
@@ -1161,14 +2396,41 @@ fn parse_string_type_definition(
                 bounds: None,
             }),
             location,
-            scope: lexer.scope.clone(),
+            scope: lexer.pou_scope(),
         }),
         _ => Some(DataTypeDeclaration::Reference { referenced_type: text, location }),
     }
-    .zip(Some(
-        (lexer.try_consume(KeywordAssignment) || lexer.try_consume(KeywordReferenceAssignment))
-            .then(|| parse_expression(lexer)),
-    ))
+    .zip(Some({
+        let initializer = (lexer.try_consume(KeywordAssignment)
+            || lexer.try_consume(KeywordReferenceAssignment))
+        .then(|| parse_expression(lexer));
+        if let Some(initializer) = &initializer {
+            report_mismatched_string_quotes(lexer, is_wide, initializer);
+        }
+        initializer
+    }))
+}
+
+/// `STRING` literals are written in single quotes and `WSTRING` literals in double quotes, a string
+/// literal initializing the other kind of string is reported but kept as is
+fn report_mismatched_string_quotes(lexer: &mut ParseSession, is_wide: bool, initializer: &AstNode) {
+    let AstStatement::Literal(AstLiteral::String(StringValue { value, is_wide: is_wide_literal })) =
+        initializer.get_stmt()
+    else {
+        return;
+    };
+
+    if *is_wide_literal != is_wide {
+        let (type_name, quote) = if is_wide { ("WSTRING", '"') } else { ("STRING", '\'') };
+        lexer.accept_diagnostic(
+            Diagnostic::new(format!(
+                "{type_name} literals are written in {} quotes, use {quote}{value}{quote} instead",
+                if is_wide { "double" } else { "single" }
+            ))
+            .with_error_code("E148")
+            .with_location(initializer.get_location()),
+        );
+    }
 }
 
 fn parse_enum_type_definition(
@@ -1186,32 +2448,114 @@ fn parse_enum_type_definition(
     // TYPE COLOR : (...) DWORD;
     let numeric_type =
         if lexer.token == Identifier { lexer.slice_and_advance() } else { DINT_TYPE.to_string() };
+    validate_enum_element_values(lexer, &numeric_type, &elements);
 
     let initializer = lexer.try_consume(KeywordAssignment).then(|| parse_expression(lexer));
     Some((
         DataTypeDeclaration::Definition {
             data_type: Box::new(DataType::EnumType { name, elements, numeric_type }),
             location: start.span(&lexer.last_location()),
-            scope: lexer.scope.clone(),
+            scope: lexer.pou_scope(),
         },
         initializer,
     ))
 }
 
+/// Reports enum elements whose literal value does not fit into the enum's numeric base type,
+/// e.g. `TYPE Color : BYTE (Red := 300); END_TYPE`
+fn validate_enum_element_values(lexer: &mut ParseSession, numeric_type: &str, elements: &AstNode) {
+    let range = match numeric_type.to_uppercase().as_str() {
+        SINT_TYPE => i8::MIN as i128..=i8::MAX as i128,
+        USINT_TYPE | BYTE_TYPE => u8::MIN as i128..=u8::MAX as i128,
+        INT_TYPE => i16::MIN as i128..=i16::MAX as i128,
+        UINT_TYPE | WORD_TYPE => u16::MIN as i128..=u16::MAX as i128,
+        DINT_TYPE => i32::MIN as i128..=i32::MAX as i128,
+        UDINT_TYPE | DWORD_TYPE => u32::MIN as i128..=u32::MAX as i128,
+        LINT_TYPE => i64::MIN as i128..=i64::MAX as i128,
+        ULINT_TYPE | LWORD_TYPE => u64::MIN as i128..=u64::MAX as i128,
+        // aliases and other user types are checked once they are resolved
+        _ => return,
+    };
+
+    for element in flatten_expression_list(elements) {
+        let AstStatement::Assignment(Assignment { right, .. }) = element.get_stmt() else { continue };
+        let AstStatement::Literal(AstLiteral::Integer(value)) = right.get_stmt() else { continue };
+        if !range.contains(value) {
+            lexer.accept_diagnostic(
+                Diagnostic::new(format!("This will overflow for type {numeric_type}"))
+                    .with_error_code("E039")
+                    .with_location(right.get_location()),
+            );
+        }
+    }
+}
+
+/// Reports a literal initial value outside the literal bounds of a subrange (e.g. `INT(0..100) := 200`),
+/// bounds referring to constants are not known yet and therefore not checked
+fn validate_subrange_initializer(
+    lexer: &mut ParseSession,
+    bounds: Option<&AstNode>,
+    initializer: Option<&AstNode>,
+) {
+    let (Some(bounds), Some(initializer)) = (bounds, initializer) else { return };
+    let AstStatement::RangeStatement(RangeStatement { start, end }) = bounds.get_stmt() else { return };
+    let (Some(start), Some(end), Some(value)) = (
+        start.get_literal_integer_value(),
+        end.get_literal_integer_value(),
+        initializer.get_literal_integer_value(),
+    ) else {
+        return;
+    };
+
+    if !(start..=end).contains(&value) {
+        lexer.accept_diagnostic(
+            Diagnostic::new(format!("Initial value {value} is outside the subrange {start}..{end}"))
+                .with_error_code("E135")
+                .with_location(initializer.get_location()),
+        );
+    }
+}
+
 fn parse_array_type_definition(
     lexer: &mut ParseSession,
     name: Option<String>,
 ) -> Option<(DataTypeDeclaration, Option<AstNode>)> {
     let start = lexer.last_range.start;
     let range = parse_any_in_region(lexer, vec![KeywordOf], |lexer| {
-        // Parse Array range
+        // `ARRAY OF INT` is accepted as a shorthand for the variable length array `ARRAY[*] OF INT`
+        if lexer.token == KeywordOf {
+            let location = lexer.last_location().span(&lexer.location());
+            lexer.accept_diagnostic(
+                Diagnostic::new("Missing array bounds, use `ARRAY[*] OF` to declare a variable length array")
+                    .with_error_code("E134")
+                    .with_location(&location),
+            );
+            return Some(AstFactory::create_vla_range_statement(location, lexer.next_id()));
+        }
 
-        expect_token!(lexer, KeywordSquareParensOpen, None);
+        // Parse Array range
+        // round parentheses are accepted for recovery, but reported (e.g. `ARRAY (0..5) OF INT`)
+        let opening_token = lexer.token;
+        let opening_location = lexer.range().start;
+        if opening_token != KeywordParensOpen {
+            expect_token!(lexer, KeywordSquareParensOpen, None);
+        }
         lexer.advance();
 
         let range_statement = parse_expression(lexer);
 
-        expect_token!(lexer, KeywordSquareParensClose, None);
+        if lexer.token != KeywordParensClose {
+            expect_token!(lexer, KeywordSquareParensClose, None);
+        }
+        if opening_token == KeywordParensOpen || lexer.token == KeywordParensClose {
+            lexer.accept_diagnostic(
+                Diagnostic::new("Array bounds must use square brackets '[]'")
+                    .with_location(
+                        lexer.source_range_factory.create_range(opening_location..lexer.range().end),
+                    )
+                    .with_error_code("E009"),
+            );
+        }
         lexer.advance();
 
         Some(range_statement)
@@ -1258,7 +2602,7 @@ fn parse_array_type_definition(
                     is_variable_length,
                 }),
                 location,
-                scope: lexer.scope.clone(),
+                scope: lexer.pou_scope(),
             },
             initializer,
         )
@@ -1271,8 +2615,18 @@ fn parse_body_in_region(lexer: &mut ParseSession, end_keywords: Vec<Token>) -> V
 }
 
 fn parse_body_standalone(lexer: &mut ParseSession) -> Vec<AstNode> {
+    // a variable block ends the body, see `parse_misplaced_variable_blocks`
+    parse_statements_until(lexer, is_variable_block_keyword)
+}
+
+/// parses statements up to a token closing an open region or a token matching `is_end`
+fn parse_statements_until(lexer: &mut ParseSession, is_end: impl Fn(&Token) -> bool) -> Vec<AstNode> {
     let mut statements = Vec::new();
-    while !lexer.closes_open_region(&lexer.token) {
+    while !lexer.closes_open_region(&lexer.token) && !is_end(&lexer.token) {
+        // a standalone `;` is an empty statement, it is skipped to keep the body clean
+        if lexer.try_consume(KeywordSemicolon) {
+            continue;
+        }
         statements.push(parse_control(lexer));
     }
     statements
@@ -1294,9 +2648,9 @@ pub fn with_scope<T, F: FnOnce(&mut ParseSession) -> T>(
     scope: String,
     parse_fn: F,
 ) -> T {
-    lexer.scope = Some(scope);
+    lexer.scope.push(scope);
     let result = parse_fn(lexer);
-    lexer.scope = None;
+    lexer.scope.pop();
     result
 }
 
@@ -1365,28 +2719,169 @@ fn parse_variable_block(lexer: &mut ParseSession, linkage: LinkageType) -> Varia
     let location = lexer.location();
     let variable_block_type = parse_variable_block_type(lexer);
 
-    let constant = lexer.try_consume(KeywordConstant);
-
-    let retain = lexer.try_consume(KeywordRetain);
-    lexer.try_consume(KeywordNonRetain);
+    // the qualifiers may be given in any order, e.g. `VAR RETAIN PERSISTENT` or `VAR PERSISTENT RETAIN`
+    let (mut constant, mut retain, mut persistent) = (false, false, false);
+    loop {
+        match lexer.token {
+            KeywordConstant => constant = true,
+            KeywordRetain => retain = true,
+            KeywordNonRetain => {}
+            // PERSISTENT is only a keyword in this position, `VAR persistent : BOOL;` or
+            // `VAR persistent AT %IX1.0 : BOOL;` declare a variable
+            Identifier
+                if lexer.slice().eq_ignore_ascii_case("PERSISTENT")
+                    && !matches!(lexer.peek(), KeywordColon | KeywordComma | KeywordAt) =>
+            {
+                persistent = true
+            }
+            _ => break,
+        }
+        lexer.advance();
+    }
+    if constant && (retain || persistent) {
+        let message = match (retain, persistent) {
+            (true, true) => "RETAIN and PERSISTENT have no effect on CONSTANT variables",
+            (true, false) => "RETAIN has no effect on CONSTANT variables",
+            _ => "PERSISTENT has no effect on CONSTANT variables",
+        };
+        lexer.accept_diagnostic(Diagnostic::new(message).with_error_code("E137").with_location(&location));
+    }
 
     let access = parse_access_modifier(lexer);
 
     let mut variables = parse_any_in_region(lexer, vec![KeywordEndVar], parse_variable_list);
 
+    if variable_block_type == VariableBlockType::Global {
+        // inputs mapped to hardware are written by the hardware, an initial value would be overwritten
+        for variable in variables.iter().filter(|it| it.initializer.is_some()) {
+            if let Some(AstStatement::HardwareAccess(plc_ast::ast::HardwareAccess {
+                direction: HardwareAccessType::Input,
+                ..
+            })) = variable.address.as_ref().map(|it| it.get_stmt())
+            {
+                lexer.accept_diagnostic(
+                    Diagnostic::new(format!(
+                        "Global variable `{}` is mapped to an input address and should not have an initializer",
+                        variable.name
+                    ))
+                    .with_error_code("E129")
+                    .with_location(&variable.location),
+                );
+            }
+        }
+    }
+
+    if variable_block_type == VariableBlockType::Input(ArgumentProperty::ByRef) {
+        // a `{ref}` input is already passed as a pointer, an explicit pointer type is redundant
+        for variable in &variables {
+            let DataTypeDeclaration::Definition { data_type, location, .. } = &variable.data_type_declaration
+            else {
+                continue;
+            };
+            if matches!(data_type.as_ref(), DataType::PointerType { auto_deref: None, .. }) {
+                let type_name =
+                    location.to_range().map_or_else(String::new, |it| lexer.slice_region(it).to_string());
+                lexer.accept_diagnostic(
+                    Diagnostic::new(format!(
+                        "Input `{}` is passed by reference with `{{ref}}` and declared as `{type_name}`, remove one of them",
+                        variable.name
+                    ))
+                    .with_error_code("E130")
+                    .with_location(&variable.location),
+                );
+            }
+        }
+    }
+
+    // a reference returned through an output is set by the callee, an initial target makes no sense
+    if variable_block_type == VariableBlockType::Output {
+        for variable in variables.iter().filter(|it| it.initializer.is_some()) {
+            if let DataTypeDeclaration::Definition { data_type, .. } = &variable.data_type_declaration {
+                if matches!(data_type.as_ref(), DataType::PointerType { kind: PointerKind::RefTo, .. }) {
+                    lexer.accept_diagnostic(
+                        Diagnostic::new(format!(
+                            "Output '{}' of type REF_TO can not have an initializer",
+                            variable.name
+                        ))
+                        .with_error_code("E140")
+                        .with_location(&variable.location),
+                    );
+                }
+            }
+        }
+    }
+
+    // inputs with a default value may be omitted at call sites, the DefaultValue-Statements of
+    // constant blocks below are no default values in that sense
+    if matches!(variable_block_type, VariableBlockType::Input(_)) {
+        variables.iter_mut().for_each(|it| it.optional = it.initializer.is_some());
+    }
+
     if constant && !matches!(variable_block_type, VariableBlockType::External) {
         // sneak in the DefaultValue-Statements if no initializers were defined
-        variables.iter_mut().filter(|it| it.initializer.is_none()).for_each(|it| {
-            it.initializer = Some(AstFactory::create_default_value(it.location.clone(), lexer.next_id()));
-        });
+        for variable in variables.iter_mut().filter(|it| it.initializer.is_none()) {
+            let pointer_location = match &variable.data_type_declaration {
+                DataTypeDeclaration::Definition { data_type, location, .. }
+                    if matches!(data_type.as_ref(), DataType::PointerType { .. }) =>
+                {
+                    Some(location)
+                }
+                _ => None,
+            };
+            if let Some(type_location) = pointer_location {
+                // there is no sensible default for a constant pointer
+                let type_name = type_location
+                    .to_range()
+                    .map_or_else(String::new, |it| lexer.slice_region(it).to_string());
+                lexer.accept_diagnostic(
+                    Diagnostic::new(format!(
+                        "CONSTANT variable '{}' of type {type_name} requires an explicit initializer",
+                        variable.name
+                    ))
+                    .with_error_code("E128")
+                    .with_location(&variable.location),
+                );
+                continue;
+            }
+            variable.initializer =
+                Some(AstFactory::create_default_value(variable.location.clone(), lexer.next_id()));
+        }
+    }
+
+    // tag explicitly declared `EN`/`ENO` parameters, so they are not confused with the implicit ones
+    let en_eno_name = match variable_block_type {
+        VariableBlockType::Input(_) => Some("EN"),
+        VariableBlockType::Output => Some("ENO"),
+        _ => None,
+    };
+    if let Some(en_eno_name) = en_eno_name {
+        variables
+            .iter_mut()
+            .filter(|it| {
+                it.name.eq_ignore_ascii_case(en_eno_name)
+                    && it
+                        .data_type_declaration
+                        .get_name()
+                        .is_some_and(|it| it.eq_ignore_ascii_case(BOOL_TYPE))
+            })
+            .for_each(|it| it.explicit_en_eno = true);
     }
 
-    VariableBlock { access, constant, retain, variables, kind: variable_block_type, linkage, location }
+    VariableBlock {
+        access,
+        constant,
+        retain,
+        persistent,
+        variables,
+        kind: variable_block_type,
+        linkage,
+        location,
+    }
 }
 
 fn parse_variable_list(lexer: &mut ParseSession) -> Vec<Variable> {
     let mut variables = vec![];
-    while lexer.token == Identifier {
+    while lexer.token == Identifier || is_keyword_used_as_name(lexer) {
         let mut line_vars = parse_variable_line(lexer);
         variables.append(&mut line_vars);
     }
@@ -1411,8 +2906,28 @@ fn parse_config_variables(lexer: &mut ParseSession) -> Vec<ConfigVariable> {
 fn try_parse_config_var(lexer: &mut ParseSession) -> Option<ConfigVariable> {
     let start = lexer.location();
     let qualified_reference = parse_reference(lexer);
+
+    // an empty reference was already reported while parsing it
+    let malformed = !qualified_reference.is_empty_statement()
+        && (!is_config_target(&qualified_reference) || !matches!(lexer.token, KeywordAt | HardwareAccess(_)));
+    if malformed {
+        // skip the rest of the reference, the address may still be intact
+        while !matches!(lexer.token, KeywordAt | HardwareAccess(_) | KeywordSemicolon)
+            && !lexer.closes_open_region(&lexer.token)
+        {
+            lexer.advance();
+        }
+        lexer.accept_diagnostic(
+            Diagnostic::new(
+                "Invalid configuration target, expected a qualified reference like `main.instances[2].input`",
+            )
+            .with_error_code("E138")
+            .with_location(start.span(&lexer.last_location())),
+        );
+    }
+
     let location = start.span(&lexer.last_location());
-    if !lexer.try_consume(KeywordAt) {
+    if !lexer.try_consume(KeywordAt) && !malformed {
         lexer.accept_diagnostic(Diagnostic::missing_token("AT", lexer.location()));
     }
 
@@ -1442,6 +2957,89 @@ fn try_parse_config_var(lexer: &mut ParseSession) -> Option<ConfigVariable> {
     })
 }
 
+fn parse_access_variables(lexer: &mut ParseSession) -> Vec<AccessVariable> {
+    parse_any_in_region(lexer, vec![KeywordEndVar], |lexer| {
+        lexer.advance();
+        let mut variables = vec![];
+        while lexer.token == Identifier {
+            if let Some(access_var) = parse_any_in_region(lexer, vec![KeywordSemicolon], try_parse_access_var)
+            {
+                variables.push(access_var);
+            }
+        }
+        variables
+    })
+}
+
+fn try_parse_access_var(lexer: &mut ParseSession) -> Option<AccessVariable> {
+    let start = lexer.location();
+    let name = Identifier { name: lexer.slice_and_advance(), location: start.clone() };
+    if !lexer.try_consume(KeywordColon) {
+        lexer.accept_diagnostic(Diagnostic::missing_token(
+            format!("{KeywordColon:?}").as_str(),
+            lexer.location(),
+        ));
+    }
+
+    let reference = parse_reference(lexer);
+    if !lexer.try_consume(KeywordColon) {
+        lexer.accept_diagnostic(Diagnostic::missing_token(
+            format!("{KeywordColon:?}").as_str(),
+            lexer.location(),
+        ));
+    }
+
+    let (data_type, initializer) = parse_data_type_definition(lexer, None)?;
+    if let Some(initializer) = initializer {
+        lexer.accept_diagnostic(Diagnostic::unexpected_token_found(
+            "access direction",
+            "Initializer",
+            initializer.get_location(),
+        ));
+    }
+
+    // READ_WRITE and READ_ONLY are only keywords in this position
+    let direction = match lexer.slice().to_uppercase().as_str() {
+        "READ_WRITE" if lexer.token == Identifier => Some(AccessDirection::ReadWrite),
+        "READ_ONLY" if lexer.token == Identifier => Some(AccessDirection::ReadOnly),
+        _ => None,
+    };
+    let direction = match direction {
+        Some(direction) => {
+            lexer.advance();
+            direction
+        }
+        None => {
+            lexer.accept_diagnostic(
+                Diagnostic::new(format!(
+                    "Missing access direction for '{}', defaulting to READ_ONLY",
+                    name.name
+                ))
+                .with_error_code("E147")
+                .with_location(name.location.clone()),
+            );
+            AccessDirection::ReadOnly
+        }
+    };
+
+    let location = start.span(&lexer.last_location());
+    Some(AccessVariable { name, reference, data_type, direction, location })
+}
+
+/// a configuration target is a chain of member and index accesses, e.g. `main.instances[2].input`
+fn is_config_target(reference: &AstNode) -> bool {
+    match reference.get_stmt() {
+        AstStatement::ReferenceExpr(ReferenceExpr { access: ReferenceAccess::Member(member), base }) => {
+            member.is_identifier() && base.as_deref().map_or(true, is_config_target)
+        }
+        AstStatement::ReferenceExpr(ReferenceExpr {
+            access: ReferenceAccess::Index(_),
+            base: Some(base),
+        }) => is_config_target(base),
+        _ => false,
+    }
+}
+
 fn parse_aliasing(lexer: &mut ParseSession, names: &(String, Range<usize>)) -> Option<Variable> {
     let reference = parse_reference(lexer);
     if !lexer.try_consume(KeywordColon) {
@@ -1452,7 +3050,7 @@ fn parse_aliasing(lexer: &mut ParseSession, names: &(String, Range<usize>)) -> O
     }
 
     let start = &lexer.location().get_span().to_range().unwrap_or(lexer.last_range.clone()).start;
-    let datatype = parse_pointer_definition(lexer, None, *start, Some(AutoDerefType::Alias), true, false);
+    let datatype = parse_pointer_definition(lexer, None, *start, PointerKind::Alias);
     if !lexer.try_consume(KeywordSemicolon) {
         lexer.accept_diagnostic(Diagnostic::missing_token(
             format!("{KeywordSemicolon:?}").as_str(),
@@ -1467,16 +3065,59 @@ fn parse_aliasing(lexer: &mut ParseSession, names: &(String, Range<usize>)) -> O
             location: lexer.source_range_factory.create_range(names.1.clone()),
             initializer: Some(reference),
             address: None,
+            retain: None,
+            attributes: vec![],
+            explicit_en_eno: false,
+            optional: false,
+            absolute_address: None,
         });
     }
 
     None
 }
 
+/// names of the elementary data types a variable should not be named after (see E123)
+const ELEMENTARY_TYPE_NAMES: &[&str] = &[
+    BOOL_TYPE,
+    BYTE_TYPE,
+    WORD_TYPE,
+    DWORD_TYPE,
+    LWORD_TYPE,
+    SINT_TYPE,
+    USINT_TYPE,
+    INT_TYPE,
+    UINT_TYPE,
+    DINT_TYPE,
+    UDINT_TYPE,
+    LINT_TYPE,
+    ULINT_TYPE,
+    REAL_TYPE,
+    LREAL_TYPE,
+    TIME_TYPE,
+    LONG_TIME_TYPE,
+    DATE_TYPE,
+    LONG_DATE_TYPE,
+    TIME_OF_DAY_TYPE,
+    SHORT_TIME_OF_DAY_TYPE,
+    LONG_TIME_OF_DAY_TYPE,
+    DATE_AND_TIME_TYPE,
+    SHORT_DATE_AND_TIME_TYPE,
+    LONG_DATE_AND_TIME_TYPE,
+    STRING_TYPE,
+    WSTRING_TYPE,
+    CHAR_TYPE,
+    WCHAR_TYPE,
+];
+
 fn parse_variable_line(lexer: &mut ParseSession) -> Vec<Variable> {
+    let pragmas = lexer.attributes.clone();
+    let attributes =
+        pragmas.iter().map(|it| parse_attribute(lexer.slice_region(it.clone()))).collect::<Vec<_>>();
+    let absolute_address = parse_address_pragma(lexer);
+
     // read in a comma separated list of variable names
     let mut var_names: Vec<(String, Range<usize>)> = vec![];
-    while lexer.token == Identifier {
+    while lexer.token == Identifier || accept_keyword_as_name(lexer) {
         let location = lexer.range();
         let identifier_end = location.end;
         var_names.push((lexer.slice_and_advance(), location));
@@ -1494,6 +3135,26 @@ fn parse_variable_line(lexer: &mut ParseSession) -> Vec<Variable> {
         }
     }
 
+    for (name, range) in &var_names {
+        if ELEMENTARY_TYPE_NAMES.iter().any(|it| it.eq_ignore_ascii_case(name)) {
+            lexer.accept_diagnostic(
+                Diagnostic::new(format!("Variable `{name}` has the same name as an elementary data type"))
+                    .with_error_code("E123")
+                    .with_location(lexer.source_range_factory.create_range(range.clone())),
+            );
+        }
+    }
+
+    if let Some((_, pragma_location)) = absolute_address.as_ref().filter(|_| lexer.token == KeywordAt) {
+        lexer.accept_diagnostic(
+            Diagnostic::new("The `{address}` pragma conflicts with the `AT` clause of the variable")
+                .with_error_code("E143")
+                .with_location(pragma_location)
+                .with_secondary_location(lexer.location()),
+        );
+    }
+    let absolute_address = absolute_address.map(|(address, _)| address);
+
     //See if there's an AT keyword
     let mut address: Option<AstNode> = None;
     if lexer.try_consume(KeywordAt) {
@@ -1504,7 +3165,7 @@ fn parse_variable_line(lexer: &mut ParseSession) -> Vec<Variable> {
 
             Identifier => {
                 return match parse_aliasing(lexer, &var_names[0]) {
-                    Some(aliased_variable) => vec![aliased_variable],
+                    Some(aliased_variable) => vec![Variable { attributes, ..aliased_variable }],
                     None => vec![],
                 };
             }
@@ -1529,26 +3190,46 @@ fn parse_variable_line(lexer: &mut ParseSession) -> Vec<Variable> {
     // create variables with the same data type for each of the names
     let mut variables = vec![];
 
+    // a pragma preceding the data type, e.g. `{not_null} REFERENCE TO INT`
+    let nullability_location = lexer.location();
+    let nullability = parse_nullability(lexer);
+
     let parse_definition_opt = if lexer.try_consume(KeywordReferenceTo) {
-        parse_pointer_definition(
-            lexer,
-            None,
-            lexer.last_range.start,
-            Some(AutoDerefType::Reference),
-            true,
-            false,
-        )
+        parse_pointer_definition(lexer, None, lexer.last_range.start, PointerKind::ReferenceTo)
+            .map(|definition| (definition, parse_retain_qualifier(lexer)))
     } else if lexer.try_consume(KeywordFunctionPointer) {
-        parse_pointer_definition(lexer, None, lexer.last_range.start, None, false, true)
+        parse_pointer_definition(lexer, None, lexer.last_range.start, PointerKind::Function)
+            .map(|definition| (definition, parse_retain_qualifier(lexer)))
     } else if address.is_some() {
-        parse_pointer_definition(lexer, None, lexer.last_range.start, Some(AutoDerefType::Alias), true, false)
+        parse_pointer_definition(lexer, None, lexer.last_range.start, PointerKind::Alias)
+            .map(|definition| (definition, parse_retain_qualifier(lexer)))
     } else {
-        parse_full_data_type_definition(lexer, None)
+        parse_full_data_type_definition_with(lexer, None, parse_retain_qualifier)
+    };
+    let parse_definition_opt = match nullability {
+        Some(nullability) => parse_definition_opt.map(|((data_type, initializer), retain)| {
+            ((apply_nullability(lexer, data_type, nullability, nullability_location), initializer), retain)
+        }),
+        None => parse_definition_opt,
     };
 
     lexer.try_consume(KeywordSemicolon);
 
-    if let Some((data_type, initializer)) = parse_definition_opt {
+    if let Some(((data_type, initializer), retain)) = parse_definition_opt {
+        // an `{attribute 'initial_value' := '...'}` pragma initializes the variable if no initializer is given
+        let initializer = match (initializer, parse_initial_value_attribute(lexer, &pragmas)) {
+            (Some(initializer), Some(attribute_value)) => {
+                lexer.accept_diagnostic(
+                    Diagnostic::new("Conflicting initial values, the `initial_value` attribute is ignored")
+                        .with_error_code("E126")
+                        .with_location(attribute_value.get_location())
+                        .with_secondary_location(initializer.get_location()),
+                );
+                Some(initializer)
+            }
+            (initializer, attribute_value) => initializer.or(attribute_value),
+        };
+
         for (name, range) in var_names {
             variables.push(Variable {
                 name,
@@ -1556,6 +3237,11 @@ fn parse_variable_line(lexer: &mut ParseSession) -> Vec<Variable> {
                 location: lexer.source_range_factory.create_range(range),
                 initializer: initializer.clone(),
                 address: address.clone(),
+                retain,
+                attributes: attributes.clone(),
+                explicit_en_eno: false,
+                optional: false,
+                absolute_address,
             });
         }
     }
@@ -1563,6 +3249,103 @@ fn parse_variable_line(lexer: &mut ParseSession) -> Vec<Variable> {
     variables
 }
 
+/// parses an `{address 16#1000}` pragma preceding the current token, which places a variable at an absolute
+/// address. The address may be written in hex (`16#1000` or `0x1000`) or decimal and may be quoted
+/// (`{address '0x1000'}`). Returns the address and the location of the pragma
+fn parse_address_pragma(lexer: &mut ParseSession) -> Option<(u64, SourceLocation)> {
+    let range = lexer.address_pragma.clone()?;
+    let pragma = lexer.slice_region(range.clone());
+    let value =
+        pragma.trim_start_matches('{').trim_end_matches('}').trim_start().trim_start_matches("address");
+    let text = value.trim().trim_matches('\'').replace('_', "");
+    let location = lexer.source_range_factory.create_range(range);
+
+    let address = if let Some(hex) = text.strip_prefix("16#").or_else(|| text.strip_prefix("0x")) {
+        u64::from_str_radix(hex, 16).ok()
+    } else {
+        text.parse().ok()
+    };
+    if address.is_none() {
+        lexer.accept_diagnostic(
+            Diagnostic::new(format!("Invalid absolute address '{text}', expected a hex or decimal number"))
+                .with_error_code("E142")
+                .with_location(&location),
+        );
+    }
+    address.map(|it| (it, location))
+}
+
+/// parses the value of an `{attribute 'initial_value' := '...'}` pragma among the given pragmas as an
+/// expression, e.g. the `5` in `{attribute 'initial_value' := '5'}`
+fn parse_initial_value_attribute(lexer: &mut ParseSession, pragmas: &[Range<usize>]) -> Option<AstNode> {
+    let pragma = pragmas.iter().find(|it| {
+        let attribute = parse_attribute(lexer.slice_region((*it).clone()));
+        attribute.name.eq_ignore_ascii_case("initial_value") && attribute.value.is_some()
+    })?;
+
+    // the value is the last quoted string of the pragma
+    let text = lexer.slice_region(pragma.clone());
+    let value_end = text.rfind('\'')?;
+    let value_start = text[..value_end].rfind('\'')? + 1;
+    let value_range = pragma.start + value_start..pragma.start + value_end;
+
+    // lex only the value, its locations still point into the pragma
+    let (value, diagnostics) = {
+        let mut value_lexer = lexer::lex_range_with_ids(
+            lexer.get_src(),
+            value_range,
+            lexer.id_provider.clone(),
+            lexer.source_range_factory.clone(),
+        );
+        let value = parse_expression(&mut value_lexer);
+        if value_lexer.token != End {
+            value_lexer.accept_diagnostic(Diagnostic::unexpected_token_found(
+                "end of initial value",
+                value_lexer.slice(),
+                value_lexer.location(),
+            ));
+        }
+        (value, value_lexer.diagnostics)
+    };
+    for diagnostic in diagnostics {
+        lexer.accept_diagnostic(diagnostic);
+    }
+    Some(value)
+}
+
+/// splits an `{attribute 'name' := 'value'}` pragma into the attribute's name and optional value
+fn parse_attribute(pragma: &str) -> Attribute {
+    let mut quoted = pragma.split('\'').skip(1).step_by(2);
+    Attribute {
+        name: quoted.next().unwrap_or_default().to_string(),
+        value: quoted.next().map(|it| it.to_string()),
+    }
+}
+
+/// parses an optional `RETAIN` or `NON_RETAIN` qualifier following a variable's data type
+/// (e.g. `a : INT NON_RETAIN;`), returns `Some(true)` for `RETAIN` and `Some(false)` for `NON_RETAIN`
+fn parse_retain_qualifier(lexer: &mut ParseSession) -> Option<bool> {
+    let start = lexer.location();
+    let mut retain = None;
+    let mut conflicting = false;
+    while matches!(lexer.token, KeywordRetain | KeywordNonRetain) {
+        let is_retain = lexer.token == KeywordRetain;
+        conflicting |= retain.is_some_and(|it| it != is_retain);
+        retain = retain.or(Some(is_retain));
+        lexer.advance();
+    }
+
+    if conflicting {
+        lexer.accept_diagnostic(
+            Diagnostic::new("A variable cannot be both `RETAIN` and `NON_RETAIN`")
+                .with_error_code("E124")
+                .with_location(start.span(&lexer.last_location())),
+        );
+    }
+
+    retain
+}
+
 fn parse_hardware_access(
     lexer: &mut ParseSession,
     hardware_access_type: HardwareAccessType,
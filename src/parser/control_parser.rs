@@ -1,18 +1,26 @@
 use plc_ast::{
-    ast::{AstFactory, AstNode, AstStatement},
-    control_statements::{CaseStatement, ConditionalBlock, ForLoopStatement, IfStatement, LoopStatement},
+    ast::{flatten_expression_list, AstFactory, AstNode, AstStatement},
+    control_statements::{
+        CaseLabel, CaseStatement, ConditionalBlock, ForLoopStatement, IfStatement, LoopStatement,
+        TryStatement,
+    },
 };
 use plc_diagnostics::diagnostics::Diagnostic;
+use plc_source::source_location::SourceLocation;
+use rustc_hash::FxHashMap;
 
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use crate::{
     expect_token,
-    lexer::Token::*,
-    parser::{parse_any_in_region, parse_body_in_region},
+    lexer::Token::{self, *},
+    parser::parse_body_in_region,
 };
 
 use super::ParseSession;
-use super::{parse_expression, parse_reference, parse_statement};
+use super::{
+    is_declaration_keyword, is_variable_block_keyword, parse_expression, parse_reference, parse_statement,
+    parse_statements_until,
+};
 
 pub fn parse_control_statement(lexer: &mut ParseSession) -> AstNode {
     match lexer.token {
@@ -24,6 +32,7 @@ pub fn parse_control_statement(lexer: &mut ParseSession) -> AstNode {
         KeywordReturn => parse_return_statement(lexer),
         KeywordContinue => parse_continue_statement(lexer),
         KeywordExit => parse_exit_statement(lexer),
+        KeywordTry => parse_try_statement(lexer),
         _ => parse_statement(lexer),
     }
 }
@@ -31,21 +40,40 @@ pub fn parse_control_statement(lexer: &mut ParseSession) -> AstNode {
 fn parse_return_statement(lexer: &mut ParseSession) -> AstNode {
     let location = lexer.location();
     lexer.advance();
-    AstFactory::create_return_statement(None, location, lexer.next_id())
+    // an optional value may follow, e.g. `RETURN (a + b).field;`
+    if lexer.closes_open_region(&lexer.token) {
+        return AstFactory::create_return_statement(None, location, lexer.next_id());
+    }
+    let value = parse_expression(lexer);
+    let location = location.span(&value.get_location());
+    AstFactory::create_return_statement_with_value(value, location, lexer.next_id())
 }
 
 fn parse_exit_statement(lexer: &mut ParseSession) -> AstNode {
     let location = lexer.location();
+    report_outside_of_loop(lexer, "EXIT");
     lexer.advance();
     AstFactory::create_exit_statement(location, lexer.next_id())
 }
 
 fn parse_continue_statement(lexer: &mut ParseSession) -> AstNode {
     let location = lexer.location();
+    report_outside_of_loop(lexer, "CONTINUE");
     lexer.advance();
     AstFactory::create_continue_statement(location, lexer.next_id())
 }
 
+/// reports the current `EXIT` or `CONTINUE` statement if it is not nested inside a loop
+fn report_outside_of_loop(lexer: &mut ParseSession, keyword: &str) {
+    if lexer.loop_depth == 0 {
+        lexer.accept_diagnostic(
+            Diagnostic::new(format!("{keyword} used outside of a loop"))
+                .with_error_code("E125")
+                .with_location(lexer.location()),
+        );
+    }
+}
+
 fn parse_if_statement(lexer: &mut ParseSession) -> AstNode {
     let start = lexer.range().start;
     lexer.advance(); //If
@@ -99,7 +127,16 @@ fn parse_for_statement(lexer: &mut ParseSession) -> AstNode {
     let start_expression = parse_expression(lexer);
     expect_token!(lexer, KeywordTo, AstFactory::create_empty_statement(lexer.location(), lexer.next_id()));
     lexer.advance();
-    let end_expression = parse_expression(lexer);
+    let end_expression = if matches!(lexer.token, KeywordBy | KeywordDo) {
+        lexer.accept_diagnostic(
+            Diagnostic::new("Missing end value of the FOR loop after TO")
+                .with_error_code("E006")
+                .with_location(lexer.last_location()),
+        );
+        AstFactory::create_empty_statement(lexer.location(), lexer.next_id())
+    } else {
+        parse_expression(lexer)
+    };
 
     let step = if lexer.token == KeywordBy {
         lexer.advance(); // BY
@@ -115,9 +152,10 @@ fn parse_for_statement(lexer: &mut ParseSession) -> AstNode {
         start: Box::new(start_expression),
         end: Box::new(end_expression),
         by_step: step.map(Box::new),
-        body: parse_body_in_region(lexer, vec![KeywordEndFor]),
+        body: parse_loop_body_in_region(lexer, KeywordEndFor, vec![]),
         end_location: lexer.last_location(),
     };
+    report_mismatched_loop_end(lexer, KeywordEndFor);
     AstFactory::create_for_loop(
         stmt,
         lexer.source_range_factory.create_range(start..lexer.last_range.end),
@@ -134,9 +172,10 @@ fn parse_while_statement(lexer: &mut ParseSession) -> AstNode {
 
     let stmt = LoopStatement {
         condition: Box::new(condition),
-        body: parse_body_in_region(lexer, vec![KeywordEndWhile]),
+        body: parse_loop_body_in_region(lexer, KeywordEndWhile, vec![]),
         end_location: lexer.last_location(),
     };
+    report_mismatched_loop_end(lexer, KeywordEndWhile);
     AstFactory::create_while_statement(
         stmt,
         lexer.source_range_factory.create_range(start..lexer.last_range.end),
@@ -148,12 +187,17 @@ fn parse_repeat_statement(lexer: &mut ParseSession) -> AstNode {
     let start = lexer.range().start;
     lexer.advance(); //REPEAT
 
-    let body = parse_body_in_region(lexer, vec![KeywordUntil, KeywordEndRepeat]); //UNTIL
+    let body = parse_loop_body_in_region(lexer, KeywordEndRepeat, vec![KeywordUntil]); //UNTIL
     let condition = if lexer.last_token == KeywordUntil {
-        parse_any_in_region(lexer, vec![KeywordEndRepeat], parse_expression)
+        let foreign_end_keywords = foreign_loop_end_keywords(lexer, KeywordEndRepeat);
+        lexer.enter_region(vec![KeywordEndRepeat]);
+        let condition = parse_expression(lexer);
+        close_loop_region(lexer, &foreign_end_keywords);
+        condition
     } else {
         AstFactory::create_empty_statement(lexer.location(), lexer.next_id())
     };
+    report_mismatched_loop_end(lexer, KeywordEndRepeat);
 
     let stmt = LoopStatement { condition: Box::new(condition), body, end_location: lexer.last_location() };
     AstFactory::create_repeat_statement(
@@ -163,8 +207,67 @@ fn parse_repeat_statement(lexer: &mut ParseSession) -> AstNode {
     )
 }
 
+/// the keywords ending a loop. A loop may also be closed with the end keyword of another kind of loop (e.g.
+/// `FOR ... END_WHILE`), which is reported once instead of cascading into the enclosing POU
+const LOOP_END_KEYWORDS: [Token; 3] = [KeywordEndFor, KeywordEndWhile, KeywordEndRepeat];
+
+/// returns the end keywords of other kinds of loops that may close a loop expecting `end_keyword`. An end
+/// keyword an enclosing region is waiting for is left to that region, so a nested loop missing its end
+/// keyword does not steal the end keyword of the enclosing loop
+fn foreign_loop_end_keywords(lexer: &ParseSession, end_keyword: Token) -> Vec<Token> {
+    LOOP_END_KEYWORDS.into_iter().filter(|it| *it != end_keyword && !lexer.closes_open_region(it)).collect()
+}
+
+/// parses a loop's body until its `end_keyword`, any of the `additional_closing_tokens` or the
+/// end keyword of another kind of loop
+fn parse_loop_body_in_region(
+    lexer: &mut ParseSession,
+    end_keyword: Token,
+    mut additional_closing_tokens: Vec<Token>,
+) -> Vec<AstNode> {
+    let foreign_end_keywords = foreign_loop_end_keywords(lexer, end_keyword);
+    additional_closing_tokens.push(end_keyword);
+    lexer.enter_region(additional_closing_tokens);
+    lexer.loop_depth += 1;
+    let body = parse_statements_until(lexer, |token| {
+        is_variable_block_keyword(token) || foreign_end_keywords.contains(token)
+    });
+    lexer.loop_depth -= 1;
+    close_loop_region(lexer, &foreign_end_keywords);
+    body
+}
+
+/// closes the region of a loop, accepting the end keyword of another kind of loop as well
+fn close_loop_region(lexer: &mut ParseSession, foreign_end_keywords: &[Token]) {
+    if foreign_end_keywords.contains(&lexer.token) {
+        // reported by `report_mismatched_loop_end` once the loop is complete
+        lexer.leave_region_unclosed();
+        lexer.advance();
+    } else {
+        lexer.recover_until_close();
+        lexer.close_region();
+    }
+}
+
+/// reports a loop that was closed with the end keyword of another kind of loop
+fn report_mismatched_loop_end(lexer: &mut ParseSession, expected_end_keyword: Token) {
+    if LOOP_END_KEYWORDS.contains(&lexer.last_token) && lexer.last_token != expected_end_keyword {
+        let expected = match expected_end_keyword {
+            KeywordEndFor => "END_FOR",
+            KeywordEndWhile => "END_WHILE",
+            _ => "END_REPEAT",
+        };
+        lexer.accept_diagnostic(Diagnostic::unexpected_token_found(
+            expected,
+            lexer.slice_region(lexer.last_range.clone()),
+            lexer.source_range_factory.create_range(lexer.last_range.clone()),
+        ));
+    }
+}
+
 fn parse_case_statement(lexer: &mut ParseSession) -> AstNode {
     let start = lexer.range().start;
+    let case_location = lexer.location();
     lexer.advance(); // CASE
 
     let selector = parse_expression(lexer);
@@ -174,15 +277,18 @@ fn parse_case_statement(lexer: &mut ParseSession) -> AstNode {
     lexer.advance();
 
     let mut case_blocks = Vec::new();
-    if lexer.token != KeywordEndCase && lexer.token != KeywordElse {
-        let body = parse_body_in_region(lexer, vec![KeywordEndCase, KeywordElse]);
+    // a CASE without any branch may still have an ELSE, e.g. `CASE x OF ELSE a := 1; END_CASE`
+    let only_else = lexer.try_consume(KeywordElse);
+    if !only_else && lexer.token != KeywordEndCase {
+        let body = parse_case_body(lexer, vec![KeywordEndCase, KeywordElse], &case_location);
 
         let mut current_condition = None;
         let mut current_body = vec![];
         for statement in body {
             if let AstNode { stmt: AstStatement::CaseCondition(condition), .. } = statement {
                 if let Some(condition) = current_condition {
-                    let block = ConditionalBlock { condition, body: current_body };
+                    let block =
+                        ConditionalBlock { condition: normalize_case_labels(condition), body: current_body };
                     case_blocks.push(block);
                     current_body = vec![];
                 }
@@ -202,19 +308,24 @@ fn parse_case_statement(lexer: &mut ParseSession) -> AstNode {
             }
         }
         if let Some(condition) = current_condition {
-            let block = ConditionalBlock { condition, body: current_body };
+            let block = ConditionalBlock { condition: normalize_case_labels(condition), body: current_body };
             case_blocks.push(block);
         }
     }
 
+    if lexer.options.duplicate_case_labels {
+        report_duplicate_case_labels(lexer, &case_blocks);
+    }
+
     let else_block = if lexer.last_token == KeywordElse {
-        parse_body_in_region(lexer, vec![KeywordEndCase])
+        parse_case_body(lexer, vec![KeywordEndCase], &case_location)
     } else {
         vec![]
     };
 
     let end = lexer.last_range.end;
     let stmt = CaseStatement {
+        selector_is_reference: selector.is_reference(),
         selector: Box::new(selector),
         case_blocks,
         else_block,
@@ -226,3 +337,90 @@ fn parse_case_statement(lexer: &mut ParseSession) -> AstNode {
         lexer.next_id(),
     )
 }
+
+/// parses the statements of a `CASE` up to one of the given keywords. A `CASE` without `END_CASE` ends at
+/// the end of its POU or at the start of another declaration, so the following code is not swallowed
+/// by the recovery. This is reported as "Missing END_CASE" at the `CASE` keyword
+fn parse_case_body(
+    lexer: &mut ParseSession,
+    end_keywords: Vec<Token>,
+    case_location: &SourceLocation,
+) -> Vec<AstNode> {
+    lexer.enter_region(end_keywords.clone());
+    let statements = parse_statements_until(lexer, is_declaration_keyword);
+
+    if end_keywords.contains(&lexer.token) {
+        lexer.close_region();
+    } else {
+        lexer.leave_region_unclosed();
+        lexer.accept_diagnostic(
+            Diagnostic::new("Missing END_CASE").with_error_code("E150").with_location(case_location.clone()),
+        );
+    }
+    statements
+}
+
+/// normalizes the labels of a `CASE` branch into one flat list of values and ranges without parentheses,
+/// e.g. `1, (3..5), (9)` becomes `1, 3..5, 9`. A single label is kept as a single node rather than a
+/// one-element list, since the validator evaluates it as one value
+fn normalize_case_labels(condition: Box<AstNode>) -> Box<AstNode> {
+    let labels = flatten_expression_list(&condition);
+    if let [label] = labels.as_slice() {
+        return Box::new((*label).clone());
+    }
+
+    let labels = labels.into_iter().cloned().collect();
+    Box::new(AstFactory::create_expression_list(labels, condition.get_location(), condition.get_id()))
+}
+
+/// reports every integer literal label that was already used by a previous label of the same `CASE`
+fn report_duplicate_case_labels(lexer: &mut ParseSession, case_blocks: &[ConditionalBlock]) {
+    let mut seen = FxHashMap::default();
+    for label in case_blocks.iter().flat_map(ConditionalBlock::case_labels) {
+        let CaseLabel::Value(label) = label else { continue };
+        let Some(value) = label.get_literal_integer_value() else { continue };
+        if let Some(first) = seen.get(&value) {
+            lexer.accept_diagnostic(
+                Diagnostic::new(format!("Duplicate case label `{value}`"))
+                    .with_error_code("E151")
+                    .with_location(label)
+                    .with_secondary_location(*first),
+            );
+        } else {
+            seen.insert(value, label);
+        }
+    }
+}
+
+/// parses a `__TRY ... __CATCH(e) ... __FINALLY ... END_TRY` block, the `__CATCH` and `__FINALLY`
+/// blocks are optional
+fn parse_try_statement(lexer: &mut ParseSession) -> AstNode {
+    let start = lexer.range().start;
+    lexer.advance(); // __TRY
+
+    let body = parse_body_in_region(lexer, vec![KeywordEndTry, KeywordCatch, KeywordFinally]);
+
+    let mut catch_variable = None;
+    let mut catch_body = vec![];
+    if lexer.last_token == KeywordCatch {
+        lexer.try_consume_or_report(KeywordParensOpen);
+        catch_variable = Some(Box::new(parse_reference(lexer)));
+        lexer.try_consume_or_report(KeywordParensClose);
+        catch_body = parse_body_in_region(lexer, vec![KeywordEndTry, KeywordFinally]);
+    }
+
+    let finally_body = if lexer.last_token == KeywordFinally {
+        parse_body_in_region(lexer, vec![KeywordEndTry])
+    } else {
+        vec![]
+    };
+
+    let end = lexer.last_range.end;
+    let stmt =
+        TryStatement { body, catch_variable, catch_body, finally_body, end_location: lexer.last_location() };
+    AstFactory::create_try_statement(
+        stmt,
+        lexer.source_range_factory.create_range(start..end),
+        lexer.next_id(),
+    )
+}
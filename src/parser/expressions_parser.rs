@@ -8,7 +8,7 @@ use crate::{
 };
 use core::str::Split;
 use plc_ast::{
-    ast::{AstFactory, AstId, AstNode, AstStatement, DirectAccessType, Operator},
+    ast::{Assignment, AstFactory, AstId, AstNode, AstStatement, DirectAccessType, Operator, RangeStatement},
     literals::{AstLiteral, Time},
 };
 use plc_diagnostics::diagnostics::Diagnostic;
@@ -340,7 +340,16 @@ fn parse_array_literal(lexer: &mut ParseSession) -> Option<AstNode> {
     let start = lexer.range().start;
     expect_token!(lexer, KeywordSquareParensOpen, None);
     lexer.advance();
-    let elements = Some(Box::new(parse_expression(lexer)));
+    let elements = match parse_expression(lexer) {
+        AstNode { stmt: AstStatement::ExpressionList(expressions), id, location, metadata } => {
+            let expressions =
+                expressions.into_iter().map(|it| lift_index_range_assignment(lexer, it)).collect();
+            AstNode { stmt: AstStatement::ExpressionList(expressions), id, location, metadata }
+        }
+        element => lift_index_range_assignment(lexer, element),
+    };
+    report_overlapping_index_initializers(lexer, &elements);
+    let elements = Some(Box::new(elements));
     let end = lexer.range().end;
     expect_token!(lexer, KeywordSquareParensClose, None);
     lexer.advance();
@@ -352,6 +361,53 @@ fn parse_array_literal(lexer: &mut ParseSession) -> Option<AstNode> {
     ))
 }
 
+/// an index-range initializer like `1..3 := 0` is parsed as the range `1..(3 := 0)`, this turns it into
+/// the assignment `(1..3) := 0` targeting the index range
+fn lift_index_range_assignment(lexer: &mut ParseSession, element: AstNode) -> AstNode {
+    match element.stmt {
+        AstStatement::RangeStatement(RangeStatement { start, end }) if end.is_assignment() => {
+            let AstStatement::Assignment(Assignment { left, right }) = end.stmt else {
+                unreachable!("already matched as an assignment")
+            };
+            let range = AstFactory::create_range_statement(*start, *left, lexer.next_id());
+            AstFactory::create_assignment(range, *right, element.id)
+        }
+        stmt => AstNode { stmt, ..element },
+    }
+}
+
+/// reports index-targeted initializers (e.g. `[1..3 := 0, 2 := 9]`) whose literal indices overlap
+fn report_overlapping_index_initializers(lexer: &mut ParseSession, elements: &AstNode) {
+    let index_ranges = elements
+        .get_as_list()
+        .into_iter()
+        .filter_map(|element| {
+            let AstStatement::Assignment(Assignment { left, .. }) = element.get_stmt() else { return None };
+            let (start, end) = match left.get_stmt() {
+                AstStatement::RangeStatement(RangeStatement { start, end }) => {
+                    (start.get_literal_integer_value()?, end.get_literal_integer_value()?)
+                }
+                _ => (left.get_literal_integer_value()?, left.get_literal_integer_value()?),
+            };
+            Some((start.min(end)..=start.max(end), left.get_location()))
+        })
+        .collect::<Vec<_>>();
+
+    for (index, (range, location)) in index_ranges.iter().enumerate() {
+        let previous = index_ranges[..index]
+            .iter()
+            .find(|(other, _)| range.start() <= other.end() && other.start() <= range.end());
+        if let Some((_, previous_location)) = previous {
+            lexer.accept_diagnostic(
+                Diagnostic::new("Array initializer index overlaps with a previously initialized index")
+                    .with_error_code("E149")
+                    .with_location(location)
+                    .with_secondary_location(previous_location),
+            );
+        }
+    }
+}
+
 #[allow(clippy::unnecessary_wraps)]
 //Allowing the unnecessary wrap here because this method is used along other methods that need to return Results
 fn parse_bool_literal(lexer: &mut ParseSession, value: bool) -> Option<AstNode> {
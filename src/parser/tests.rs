@@ -35,8 +35,3 @@ pub fn ref_to(name: &str) -> AstNode {
 pub fn literal_int(value: i128) -> AstNode {
     AstNode::new_literal(AstLiteral::new_integer(value), 0, SourceLocation::internal())
 }
-
-/// helper function to create empty statements
-pub fn empty_stmt() -> AstNode {
-    AstFactory::create_empty_statement(SourceLocation::internal(), 0)
-}
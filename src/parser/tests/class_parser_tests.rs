@@ -3,7 +3,10 @@ use plc_ast::ast::{
     AccessModifier, ArgumentProperty, DeclarationKind, PolymorphismMode, PouType, VariableBlockType,
 };
 
-use crate::test_utils::tests::{parse, parse_and_validate_buffered};
+use crate::{
+    parser::inheritance_edges,
+    test_utils::tests::{parse, parse_and_validate_buffered},
+};
 
 #[test]
 fn simple_class_with_defaults_can_be_parsed() {
@@ -665,3 +668,131 @@ fn function_block_can_only_be_extended_once() {
        │                                                             ^^^ Multiple inheritance. POUs can only be extended once.
     ")
 }
+
+#[test]
+fn implements_can_precede_extends() {
+    let src = r#"
+    FUNCTION_BLOCK MyFb2 IMPLEMENTS MyInterface, MyOtherInterface EXTENDS MyFb
+    END_FUNCTION_BLOCK
+
+    CLASS MyClass2 IMPLEMENTS MyInterface EXTENDS MyClass
+    END_CLASS
+    "#;
+    let (unit, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let fb = &unit.pous[0];
+    assert_eq!(fb.super_class.as_ref().unwrap().name, "MyFb");
+    assert_eq!(
+        fb.interfaces.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(),
+        vec!["MyInterface", "MyOtherInterface"]
+    );
+
+    let class = &unit.pous[1];
+    assert_eq!(class.super_class.as_ref().unwrap().name, "MyClass");
+    assert_eq!(class.interfaces.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(), vec!["MyInterface"]);
+}
+
+#[test]
+fn implements_clauses_around_extends_are_merged() {
+    let src = r#"
+    FUNCTION_BLOCK MyFb2 IMPLEMENTS MyInterface EXTENDS MyFb IMPLEMENTS MyOtherInterface
+    END_FUNCTION_BLOCK
+
+    FUNCTION_BLOCK MyFb3 IMPLEMENTS MyInterface IMPLEMENTS MyOtherInterface
+    END_FUNCTION_BLOCK
+    "#;
+    let (unit, diagnostics) = parse(src);
+
+    // only a repeated `EXTENDS` is reported
+    assert_eq!(diagnostics, vec![]);
+    for fb in &unit.pous {
+        assert_eq!(
+            fb.interfaces.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(),
+            vec!["MyInterface", "MyOtherInterface"]
+        );
+    }
+    assert_eq!(unit.pous[0].super_class.as_ref().unwrap().name, "MyFb");
+    assert_eq!(unit.pous[1].super_class, None);
+}
+
+#[test]
+fn repeated_extends_is_reported_regardless_of_implements() {
+    let src = r#"
+    FUNCTION_BLOCK quux EXTENDS bar IMPLEMENTS MyInterface EXTENDS baz
+    END_FUNCTION_BLOCK
+    "#;
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.iter().map(|it| it.get_error_code()).collect::<Vec<_>>(), vec!["E114"]);
+    assert_eq!(unit.pous[0].super_class.as_ref().unwrap().name, "bar");
+    assert_eq!(unit.pous[0].interfaces[0].name, "MyInterface");
+}
+
+#[test]
+fn methods_of_nested_declarations_are_named_after_the_whole_scope() {
+    let src = r#"
+    CLASS A.B.cls
+        METHOD m
+        END_METHOD
+    END_CLASS
+
+    INTERFACE iface
+        METHOD m
+        END_METHOD
+    END_INTERFACE
+
+    FUNCTION foo : INT
+    END_FUNCTION
+    "#;
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(
+        unit.pous.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(),
+        vec!["A.B.cls", "A.B.cls.m", "foo"]
+    );
+    assert_eq!(
+        unit.pous[1].kind,
+        PouType::Method {
+            parent: "A.B.cls".into(),
+            property: None,
+            declaration_kind: DeclarationKind::Concrete
+        }
+    );
+    assert_eq!(unit.interfaces[0].methods[0].name, "iface.m");
+    assert_eq!(
+        unit.implementations.iter().map(|it| (it.name.as_str(), it.type_name.as_str())).collect::<Vec<_>>(),
+        vec![("A.B.cls.m", "A.B.cls.m"), ("A.B.cls", "A.B.cls"), ("foo", "foo")]
+    );
+}
+
+#[test]
+fn inheritance_edges_of_a_class_chain_are_collected() {
+    let src = "
+        CLASS base
+        END_CLASS
+
+        CLASS middle EXTENDS base
+            METHOD m
+            END_METHOD
+        END_CLASS
+
+        CLASS derived EXTENDS middle
+        END_CLASS
+
+        FUNCTION_BLOCK multiple EXTENDS derived EXTENDS base
+        END_FUNCTION_BLOCK
+        ";
+    let (unit, _) = parse(src);
+
+    assert_eq!(
+        inheritance_edges(&unit),
+        vec![
+            ("middle".to_string(), "base".to_string()),
+            ("derived".to_string(), "middle".to_string()),
+            // only the first base of a multiple inheritance is recorded
+            ("multiple".to_string(), "derived".to_string()),
+        ]
+    );
+}
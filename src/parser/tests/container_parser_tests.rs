@@ -1,4 +1,4 @@
-use crate::test_utils::tests::parse;
+use crate::{parser::pou_full_extent, test_utils::tests::parse};
 use insta::assert_debug_snapshot;
 use pretty_assertions::*;
 
@@ -158,3 +158,25 @@ fn two_actions_parsed() {
     assert_eq!(prg2.name, "fuz.bar");
     assert_eq!(prg2.type_name, "fuz");
 }
+
+#[test]
+fn pou_full_extent_covers_the_pou_and_its_actions() {
+    let src = "
+        PROGRAM foo END_PROGRAM
+        ACTIONS foo
+            ACTION bar END_ACTION
+            ACTION baz END_ACTION
+        END_ACTIONS
+        PROGRAM qux END_PROGRAM
+        ";
+    let result = parse(src).0;
+
+    let extent = pou_full_extent(&result, "FOO").unwrap();
+    let extent = &src[extent.to_range().unwrap()];
+    assert!(extent.starts_with("PROGRAM foo END_PROGRAM"));
+    assert!(extent.ends_with("ACTION baz END_ACTION"));
+
+    let extent = pou_full_extent(&result, "qux").unwrap();
+    assert_eq!(&src[extent.to_range().unwrap()], "PROGRAM qux END_PROGRAM");
+    assert_eq!(pou_full_extent(&result, "unknown"), None);
+}
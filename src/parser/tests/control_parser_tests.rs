@@ -1,10 +1,20 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
-use crate::test_utils::tests::parse;
+use crate::{
+    parser::{
+        tests::{literal_int, ref_to},
+        ParseOptions,
+    },
+    test_utils::tests::{parse, parse_with_options},
+};
 use insta::assert_debug_snapshot;
 use plc_ast::{
-    ast::AstStatement,
-    control_statements::{AstControlStatement, ForLoopStatement, IfStatement},
+    ast::{AstFactory, AstNode, AstStatement, BinaryExpression, Operator, ReferenceAccess, ReferenceExpr},
+    control_statements::{
+        AstControlStatement, CaseLabel, CaseStatement, ForLoopStatement, IfStatement, ReturnStatement,
+        TryStatement,
+    },
 };
+use plc_source::source_location::SourceLocation;
 
 use pretty_assertions::*;
 
@@ -46,6 +56,42 @@ fn test_return_statement() {
     assert_eq!(format!("{stmt:?}"), "ReturnStatement { condition: None }");
 }
 
+#[test]
+fn return_with_member_access_on_parenthesized_expression() {
+    let src = "FUNCTION foo : INT RETURN (a + b).field; END_FUNCTION";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let statements = &result.implementations[0].statements;
+    assert_eq!(statements.len(), 1);
+    let AstStatement::ReturnStatement(ReturnStatement { condition: None, value: Some(value) }) =
+        statements[0].get_stmt()
+    else {
+        panic!("expected a return statement with a value, got {:#?}", statements[0]);
+    };
+    assert_eq!(&src[value.get_location().to_range().unwrap()], "(a + b).field");
+
+    // the value is a member access over the parenthesized expression
+    let AstStatement::ReferenceExpr(ReferenceExpr {
+        access: ReferenceAccess::Member(member),
+        base: Some(base),
+    }) = value.get_stmt()
+    else {
+        panic!("expected a member access, got {value:#?}");
+    };
+    assert_eq!(member.get_flat_reference_name(), Some("field"));
+    let AstStatement::ParenExpression(inner) = base.get_stmt() else {
+        panic!("expected a parenthesized expression, got {base:#?}");
+    };
+    let AstStatement::BinaryExpression(BinaryExpression { operator: Operator::Plus, left, right }) =
+        inner.get_stmt()
+    else {
+        panic!("expected an addition, got {inner:#?}");
+    };
+    assert_eq!(left.get_flat_reference_name(), Some("a"));
+    assert_eq!(right.get_flat_reference_name(), Some("b"));
+}
+
 #[test]
 fn test_continue_statement() {
     let src = "PROGRAM ret CONTINUE END_PROGRAM";
@@ -423,6 +469,71 @@ fn case_statement_with_multiple_expressions_per_condition() {
     assert_debug_snapshot!(statement);
 }
 
+#[test]
+fn case_statement_with_negative_label() {
+    let src = "
+        PROGRAM exp
+        CASE StateMachine OF
+            -1: x;
+            1: y;
+        END_CASE
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let prg = &result.implementations[0];
+    let AstStatement::ControlStatement(AstControlStatement::Case(CaseStatement { case_blocks, .. })) =
+        prg.statements[0].get_stmt()
+    else {
+        panic!("expected a case statement, got {:?}", prg.statements[0]);
+    };
+
+    assert_eq!(case_blocks.len(), 2);
+    assert_eq!(format!("{:?}", case_blocks[0].condition), format!("{:?}", literal_int(-1)));
+    assert_eq!(format!("{:?}", case_blocks[0].body), format!("{:?}", vec![ref_to("x")]));
+    assert_eq!(format!("{:?}", case_blocks[1].condition), format!("{:?}", literal_int(1)));
+}
+
+#[test]
+fn case_statement_with_negated_constant_label() {
+    let src = "
+        PROGRAM exp
+        CASE StateMachine OF
+            -MAX_STATE: x;
+            -2, -MIN_STATE: y;
+        END_CASE
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let prg = &result.implementations[0];
+    let AstStatement::ControlStatement(AstControlStatement::Case(CaseStatement { case_blocks, .. })) =
+        prg.statements[0].get_stmt()
+    else {
+        panic!("expected a case statement, got {:?}", prg.statements[0]);
+    };
+
+    let negated = |name: &str| {
+        AstFactory::create_unary_expression(Operator::Minus, ref_to(name), SourceLocation::internal(), 0)
+    };
+
+    assert_eq!(case_blocks.len(), 2);
+    assert_eq!(format!("{:?}", case_blocks[0].condition), format!("{:?}", negated("MAX_STATE")));
+    assert_eq!(
+        format!("{:?}", case_blocks[1].condition),
+        format!(
+            "{:?}",
+            AstFactory::create_expression_list(
+                vec![literal_int(-2), negated("MIN_STATE")],
+                SourceLocation::internal(),
+                0
+            )
+        )
+    );
+}
+
 #[test]
 fn if_stmnt_location_test() {
     let source = "
@@ -573,3 +684,512 @@ fn call_stmnt_location_test() {
         assert_eq!(source[parameters_location.to_range().unwrap()].to_string(), "a:=3, b:=4");
     }
 }
+
+#[test]
+fn exit_and_continue_outside_of_a_loop_are_reported() {
+    let src = "
+        PROGRAM prg
+            EXIT;
+            CONTINUE;
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(
+        diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>(),
+        vec!["EXIT used outside of a loop", "CONTINUE used outside of a loop"]
+    );
+    let statements = &result.implementations[0].statements;
+    assert_eq!(format!("{statements:?}"), "[ExitStatement, ContinueStatement]");
+}
+
+#[test]
+fn exit_and_continue_inside_of_loops_are_not_reported() {
+    let src = "
+        PROGRAM prg
+            FOR x := 0 TO 10 DO
+                EXIT;
+            END_FOR
+            WHILE x < 10 DO
+                IF x = 5 THEN
+                    CONTINUE;
+                END_IF
+            END_WHILE
+            REPEAT
+                FOR x := 0 TO 10 DO
+                    CONTINUE;
+                END_FOR
+                EXIT;
+            UNTIL x > 10
+            END_REPEAT
+        END_PROGRAM
+        ";
+    let (_, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn exit_and_continue_inside_of_a_case_inside_of_a_loop() {
+    let src = "
+        PROGRAM prg
+            WHILE x < 10 DO
+                CASE x OF
+                    1: EXIT;
+                    2: x := x + 1;
+                       CONTINUE;
+                    3: CASE y OF
+                           1: CONTINUE;
+                       ELSE
+                           EXIT;
+                       END_CASE
+                ELSE
+                    CONTINUE;
+                END_CASE
+                x := x + 2;
+            END_WHILE
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    // the statement after the CASE still belongs to the loop, which is the only statement of the body
+    let statements = &result.implementations[0].statements;
+    assert_eq!(statements.len(), 1);
+    let AstStatement::ControlStatement(AstControlStatement::WhileLoop(while_loop)) = statements[0].get_stmt()
+    else {
+        panic!("expected a while loop, got {:#?}", statements[0]);
+    };
+    assert_eq!(while_loop.body.len(), 2);
+
+    let case_bodies = |node: &AstNode| match node.get_stmt() {
+        AstStatement::ControlStatement(AstControlStatement::Case(CaseStatement {
+            case_blocks,
+            else_block,
+            ..
+        })) => (case_blocks.iter().map(|it| it.body.clone()).collect::<Vec<_>>(), else_block.clone()),
+        _ => panic!("expected a case statement, got {node:#?}"),
+    };
+    let (blocks, else_block) = case_bodies(&while_loop.body[0]);
+    assert_eq!(format!("{:?}", blocks[0]), "[ExitStatement]");
+    assert_eq!(blocks[1].len(), 2);
+    assert_eq!(format!("{:?}", blocks[1][1]), "ContinueStatement");
+    assert_eq!(format!("{else_block:?}"), "[ContinueStatement]");
+
+    let (nested_blocks, nested_else_block) = case_bodies(&blocks[2][0]);
+    assert_eq!(format!("{nested_blocks:?}"), "[[ContinueStatement]]");
+    assert_eq!(format!("{nested_else_block:?}"), "[ExitStatement]");
+}
+
+#[test]
+fn case_statement_records_whether_the_selector_is_a_reference() {
+    let src = "
+        PROGRAM prg
+            CASE state OF
+                1: x := 1;
+            END_CASE
+            CASE fb.state OF
+                1: x := 1;
+            END_CASE
+            CASE state + 1 OF
+                1: x := 1;
+            END_CASE
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let flags = result.implementations[0]
+        .statements
+        .iter()
+        .map(|it| match it.get_stmt() {
+            AstStatement::ControlStatement(AstControlStatement::Case(CaseStatement {
+                selector_is_reference,
+                ..
+            })) => *selector_is_reference,
+            _ => panic!("expected a case statement, got {it:#?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(flags, vec![true, true, false]);
+}
+
+#[test]
+fn case_statements_with_empty_branches_and_else_blocks() {
+    let src = "
+        PROGRAM prg
+            CASE x OF
+                1: ;
+                2: ;
+            END_CASE
+            CASE x OF
+                1: a := 1;
+            ELSE
+            END_CASE
+            CASE x OF
+            ELSE
+                a := 1; ;
+            END_CASE
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let cases = result.implementations[0]
+        .statements
+        .iter()
+        .map(|it| match it.get_stmt() {
+            AstStatement::ControlStatement(AstControlStatement::Case(case)) => case,
+            _ => panic!("expected a case statement, got {it:#?}"),
+        })
+        .collect::<Vec<_>>();
+
+    // empty branches without an ELSE
+    let labels = cases[0].case_blocks.iter().map(|it| it.condition.get_literal_integer_value());
+    assert_eq!(labels.collect::<Vec<_>>(), vec![Some(1), Some(2)]);
+    assert!(cases[0].case_blocks.iter().all(|it| it.body.is_empty()));
+    assert!(cases[0].else_block.is_empty());
+
+    // an empty ELSE
+    assert_eq!(assigned_names(&cases[1].case_blocks[0].body), vec!["a"]);
+    assert!(cases[1].else_block.is_empty());
+
+    // only an ELSE
+    assert!(cases[2].case_blocks.is_empty());
+    assert_eq!(assigned_names(&cases[2].else_block), vec!["a"]);
+    assert_eq!(cases[2].else_block.len(), 1);
+}
+
+/// returns the names of the assigned variables, e.g. `[a, b]` for `a := 1; b := 2;`
+fn assigned_names(statements: &[plc_ast::ast::AstNode]) -> Vec<&str> {
+    statements
+        .iter()
+        .filter_map(|it| match it.get_stmt() {
+            AstStatement::Assignment(assignment) => assignment.left.get_flat_reference_name(),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn try_catch_finally_statement() {
+    let src = "
+        PROGRAM prg
+            __TRY
+                a := 1;
+                b := 2;
+            __CATCH(e)
+                c := 3;
+            __FINALLY
+                d := 4;
+            END_TRY
+        END_PROGRAM
+        ";
+    let (result, diagnostics) =
+        parse_with_options(src, ParseOptions { exception_handling: true, ..Default::default() });
+
+    assert_eq!(diagnostics, vec![]);
+    let statement = &result.implementations[0].statements[0];
+    let AstStatement::ControlStatement(AstControlStatement::Try(TryStatement {
+        body,
+        catch_variable,
+        catch_body,
+        finally_body,
+        ..
+    })) = statement.get_stmt()
+    else {
+        panic!("expected a try statement, got {statement:#?}");
+    };
+    assert_eq!(assigned_names(body), vec!["a", "b"]);
+    assert_eq!(catch_variable.as_ref().and_then(|it| it.get_flat_reference_name()), Some("e"));
+    assert_eq!(assigned_names(catch_body), vec!["c"]);
+    assert_eq!(assigned_names(finally_body), vec!["d"]);
+    assert_eq!(
+        &src[statement.get_location().to_range().unwrap()],
+        &src[src.find("__TRY").unwrap()..src.find("END_TRY").unwrap() + 7]
+    );
+}
+
+#[test]
+fn try_catch_statement_without_finally() {
+    let src = "
+        PROGRAM prg
+            __TRY
+                a := 1;
+            __CATCH(e)
+                c := 3;
+            END_TRY
+            x := 5;
+        END_PROGRAM
+        ";
+    let (result, diagnostics) =
+        parse_with_options(src, ParseOptions { exception_handling: true, ..Default::default() });
+
+    assert_eq!(diagnostics, vec![]);
+    let statements = &result.implementations[0].statements;
+    assert_eq!(statements.len(), 2);
+    let AstStatement::ControlStatement(AstControlStatement::Try(TryStatement {
+        body,
+        catch_variable,
+        catch_body,
+        finally_body,
+        ..
+    })) = statements[0].get_stmt()
+    else {
+        panic!("expected a try statement, got {:#?}", statements[0]);
+    };
+    assert_eq!(assigned_names(body), vec!["a"]);
+    assert_eq!(catch_variable.as_ref().and_then(|it| it.get_flat_reference_name()), Some("e"));
+    assert_eq!(assigned_names(catch_body), vec!["c"]);
+    assert!(finally_body.is_empty());
+    assert_eq!(assigned_names(&statements[1..]), vec!["x"]);
+}
+
+#[test]
+fn try_keywords_are_identifiers_without_exception_handling() {
+    let src = "
+        PROGRAM prg
+            __try := 1;
+            end_try := __catch;
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(assigned_names(&result.implementations[0].statements), vec!["__try", "end_try"]);
+}
+
+#[test]
+fn duplicate_case_labels_are_reported_when_enabled() {
+    let src = "
+        PROGRAM exp
+        CASE x OF
+            1:      y := 1;
+            2, 3:   y := 2;
+            4, 1:   y := 3;
+        END_CASE
+        END_PROGRAM
+        ";
+
+    let (_, diagnostics) =
+        parse_with_options(src, ParseOptions { duplicate_case_labels: true, ..Default::default() });
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_message(), "Duplicate case label `1`");
+    assert_eq!(diagnostics[0].get_error_code(), "E151");
+    // the second label is reported, pointing back to the first one
+    let range = diagnostics[0].get_location().to_range().unwrap();
+    assert_eq!(range.start, src.find("4, 1").unwrap() + 3);
+    let first = diagnostics[0].get_secondary_locations().unwrap()[0].to_range().unwrap();
+    assert_eq!(first.start, src.find("1:").unwrap());
+
+    // the check is opt-in
+    let (_, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn distinct_case_labels_are_not_reported() {
+    let src = "
+        PROGRAM exp
+        CASE x OF
+            1:      y := 1;
+            2, 3:   y := 2;
+            4..6:   y := 3;
+        ELSE
+            y := 1;
+        END_CASE
+        END_PROGRAM
+        ";
+
+    let (_, diagnostics) =
+        parse_with_options(src, ParseOptions { duplicate_case_labels: true, ..Default::default() });
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn case_labels_with_ranges_and_values_are_normalized_into_a_list() {
+    let src = "
+        PROGRAM prg
+            CASE x OF
+                1, 3..5, (9): a := 1;
+                7: a := 2;
+                (10..12): a := 3;
+            END_CASE
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let AstStatement::ControlStatement(AstControlStatement::Case(case)) =
+        result.implementations[0].statements[0].get_stmt()
+    else {
+        panic!("expected a case statement");
+    };
+    let labels = case
+        .case_blocks
+        .iter()
+        .map(|block| {
+            block
+                .case_labels()
+                .into_iter()
+                .map(|label| match label {
+                    CaseLabel::Value(value) => (value.get_literal_integer_value(), None),
+                    CaseLabel::Range { start, end } => {
+                        (start.get_literal_integer_value(), end.get_literal_integer_value())
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        labels,
+        vec![
+            vec![(Some(1), None), (Some(3), Some(5)), (Some(9), None)],
+            // a single value still yields a list
+            vec![(Some(7), None)],
+            vec![(Some(10), Some(12))],
+        ]
+    );
+
+    // the parser stores the labels without nested lists or parentheses
+    let conditions = case.case_blocks.iter().map(|it| it.condition.get_as_list()).collect::<Vec<_>>();
+    assert_eq!(conditions.iter().map(Vec::len).collect::<Vec<_>>(), vec![3, 1, 1]);
+    assert!(conditions.iter().flatten().all(|it| !it.is_paren() && !it.is_expression_list()));
+    assert!(matches!(case.case_blocks[2].condition.get_stmt(), AstStatement::RangeStatement(_)));
+}
+
+#[test]
+fn case_without_end_case_does_not_swallow_the_following_code() {
+    let src = "
+        PROGRAM prg
+            IF c THEN
+                CASE x OF
+                    1: a := 1;
+                    2: a := 2;
+            END_IF
+            b := 2;
+            CASE x OF
+                1: a := 1;
+            ELSE
+                a := 3;
+        END_PROGRAM
+
+        FUNCTION foo : INT
+        END_FUNCTION
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 2, "{diagnostics:#?}");
+    for (diagnostic, case_offset) in
+        diagnostics.iter().zip([src.find("CASE").unwrap(), src.rfind("CASE").unwrap()])
+    {
+        assert_eq!(diagnostic.get_message(), "Missing END_CASE");
+        assert_eq!(diagnostic.get_location().to_range().unwrap(), case_offset..case_offset + 4);
+    }
+
+    // the parsed branches are preserved
+    let statements = &result.implementations[0].statements;
+    let AstStatement::ControlStatement(AstControlStatement::If(if_statement)) = statements[0].get_stmt()
+    else {
+        panic!("expected an if statement, got {:#?}", statements[0]);
+    };
+    let AstStatement::ControlStatement(AstControlStatement::Case(case)) =
+        if_statement.blocks[0].body[0].get_stmt()
+    else {
+        panic!("expected a case statement");
+    };
+    assert_eq!(case.case_blocks.len(), 2);
+    assert_eq!(assigned_names(&case.case_blocks[1].body), vec!["a"]);
+
+    // the statement following the enclosing IF is not part of the CASE
+    assert_eq!(assigned_names(&statements[1..2]), vec!["b"]);
+    let AstStatement::ControlStatement(AstControlStatement::Case(case)) = statements[2].get_stmt() else {
+        panic!("expected a case statement, got {:#?}", statements[2]);
+    };
+    assert_eq!(case.case_blocks.len(), 1);
+    assert_eq!(case.else_block.len(), 1);
+
+    // the following POU is parsed as well
+    assert_eq!(result.implementations.len(), 2);
+    assert_eq!(result.implementations[1].name, "foo");
+}
+
+#[test]
+fn case_without_end_case_at_the_end_of_the_pou_keeps_its_branches() {
+    let src = "
+        PROGRAM prg
+            CASE x OF
+                1: a := 1;
+                2, 3: a := 2; b := 3;
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_message(), "Missing END_CASE");
+    assert_eq!(diagnostics[0].get_error_code(), "E150");
+
+    let AstStatement::ControlStatement(AstControlStatement::Case(case)) =
+        result.implementations[0].statements[0].get_stmt()
+    else {
+        panic!("expected a case statement");
+    };
+    let labels = case.case_blocks.iter().map(|block| block.case_labels().len()).collect::<Vec<_>>();
+    assert_eq!(labels, vec![1, 2]);
+    assert_eq!(assigned_names(&case.case_blocks[0].body), vec!["a"]);
+    assert_eq!(assigned_names(&case.case_blocks[1].body), vec!["a", "b"]);
+}
+
+#[test]
+fn for_loop_end_value_can_be_any_expression() {
+    for (bound, is_expected_kind) in [
+        ("10", (|it: &AstNode| it.is_literal()) as fn(&AstNode) -> bool),
+        ("count", |it: &AstNode| it.is_reference()),
+        ("GetCount()", |it: &AstNode| it.is_call()),
+        ("GetCount() - 1", |it: &AstNode| it.is_binary_expression()),
+    ] {
+        let src = format!(
+            "
+            PROGRAM exp
+            FOR i := 1 TO {bound} DO
+            END_FOR
+            END_PROGRAM
+            "
+        );
+        let (unit, diagnostics) = parse(&src);
+
+        assert_eq!(diagnostics, vec![], "{bound}");
+        let AstStatement::ControlStatement(AstControlStatement::ForLoop(ForLoopStatement { end, .. })) =
+            unit.implementations[0].statements[0].get_stmt()
+        else {
+            panic!("expected a FOR loop for {bound}");
+        };
+        assert!(is_expected_kind(end), "{bound}: {end:#?}");
+        assert_eq!(&src[end.get_location().to_range().unwrap()], bound);
+    }
+}
+
+#[test]
+fn for_loop_without_end_value_is_reported() {
+    let src = "
+        PROGRAM exp
+        FOR i := 1 TO DO
+            i := 2;
+        END_FOR
+        END_PROGRAM
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E006");
+    assert_eq!(diagnostics[0].get_message(), "Missing end value of the FOR loop after TO");
+    assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "TO");
+
+    let AstStatement::ControlStatement(AstControlStatement::ForLoop(ForLoopStatement { end, body, .. })) =
+        unit.implementations[0].statements[0].get_stmt()
+    else {
+        panic!("expected a FOR loop");
+    };
+    assert!(end.is_empty_statement());
+    assert_eq!(body.len(), 1);
+}
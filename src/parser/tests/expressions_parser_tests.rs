@@ -2,7 +2,7 @@
 use crate::parser::tests::ref_to;
 use crate::test_utils::tests::parse;
 use insta::{assert_debug_snapshot, assert_snapshot};
-use plc_ast::ast::{Assignment, AstFactory, AstNode, AstStatement, Operator};
+use plc_ast::ast::{Assignment, AstFactory, AstNode, AstStatement, Operator, ReferenceExpr};
 use plc_ast::literals::AstLiteral;
 use plc_source::source_location::SourceLocation;
 use pretty_assertions::*;
@@ -1586,6 +1586,7 @@ fn sized_string_as_function_return() {
                             value: 10,
                         },
                     ),
+                    encoding: None,
                 },
             },
         ),
@@ -2249,3 +2250,28 @@ fn unary_plus_expression_test() {
     }
     "#);
 }
+
+#[test]
+fn this_and_dereferenced_this_are_distinguished() {
+    let src = "
+    FUNCTION_BLOCK fb
+        THIS^.x;
+        THIS.x;
+    END_FUNCTION_BLOCK
+        ";
+
+    let parse_result = parse(src).0;
+    let bases = parse_result.implementations[0]
+        .statements
+        .iter()
+        .map(|it| match it.get_stmt() {
+            AstStatement::ReferenceExpr(ReferenceExpr { base: Some(base), .. }) => {
+                (base.is_this(), base.is_this_deref())
+            }
+            _ => unreachable!("expected a member access, got {it:?}"),
+        })
+        .collect::<Vec<_>>();
+
+    // `THIS^` dereferences the `THIS` pointer, like `SUPER^` does for `SUPER`
+    assert_eq!(bases, vec![(false, true), (true, false)]);
+}
@@ -1,7 +1,15 @@
-use crate::test_utils::tests::{parse, parse_and_validate_buffered, parse_buffered};
+use crate::{
+    lexer,
+    parser::{self, ParseOptions},
+    test_utils::tests::{parse, parse_and_validate_buffered, parse_buffered, parse_with_options},
+};
 use insta::{assert_debug_snapshot, assert_snapshot};
-use plc_ast::ast::PouType;
+use plc_ast::{
+    ast::{AstStatement, Attribute, CallStatement, DataType, DataTypeDeclaration, LinkageType, PouType},
+    provider::IdProvider,
+};
 use plc_diagnostics::diagnostics::Diagnostic;
+use plc_source::source_location::SourceLocationFactory;
 use pretty_assertions::*;
 
 #[test]
@@ -495,6 +503,27 @@ fn var_input_by_ref_parsed() {
     "###)
 }
 
+#[test]
+fn var_input_by_ref_with_pointer_type_is_reported() {
+    let (_, diagnostics) = parse("FUNCTION buz VAR_INPUT {ref} x : REF_TO INT; END_VAR END_FUNCTION");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].get_error_code(), "E130");
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "Input `x` is passed by reference with `{ref}` and declared as `REF_TO INT`, remove one of them"
+    );
+}
+
+#[test]
+fn var_input_by_ref_or_pointer_type_alone_are_accepted() {
+    let (_, diagnostics) = parse("FUNCTION buz VAR_INPUT {ref} x : INT; END_VAR END_FUNCTION");
+    assert_eq!(diagnostics, vec![]);
+
+    let (_, diagnostics) = parse("FUNCTION buz VAR_INPUT x : REF_TO INT; END_VAR END_FUNCTION");
+    assert_eq!(diagnostics, vec![]);
+}
+
 #[test]
 fn constant_pragma_can_be_parsed_but_errs() {
     let src = r#"
@@ -552,6 +581,57 @@ fn constant_pragma_can_be_parsed_but_errs() {
     ");
 }
 
+#[test]
+fn stacked_pragmas_only_apply_to_the_following_pou() {
+    let src = r#"
+        {external} {constant}
+        FUNCTION f : INT END_FUNCTION
+        FUNCTION g : INT END_FUNCTION
+        {constant}
+        TYPE t : INT; END_TYPE
+        FUNCTION h : INT END_FUNCTION
+    "#;
+    let (unit, diagnostics) = parse(src);
+
+    // the constant pragma is still only allowed for builtins
+    assert_eq!(diagnostics.iter().map(|it| it.get_error_code()).collect::<Vec<_>>(), vec!["E105"]);
+    let pous = unit.pous.iter().map(|it| (it.name.as_str(), it.linkage, it.is_const)).collect::<Vec<_>>();
+    assert_eq!(
+        pous,
+        vec![
+            ("f", LinkageType::External, true),
+            ("g", LinkageType::Internal, false),
+            ("h", LinkageType::Internal, false),
+        ]
+    );
+}
+
+#[test]
+fn external_pragma_contradicting_builtin_linkage_is_reported() {
+    let src = r#"
+        {external} {constant}
+        FUNCTION f : INT END_FUNCTION
+        FUNCTION g : INT END_FUNCTION
+    "#;
+    let (unit, diagnostics) = parser::parse(
+        lexer::lex_with_ids(src, IdProvider::default(), SourceLocationFactory::internal(src)),
+        LinkageType::BuiltIn,
+        "test.st",
+    );
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "Pragma {external} contradicts the builtin linkage of the declaration"
+    );
+    assert_eq!(diagnostics[0].get_error_code(), "E136");
+    assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "{external}");
+
+    // the pragma is ignored, the constant pragma still applies
+    let pous = unit.pous.iter().map(|it| (it.name.as_str(), it.linkage, it.is_const)).collect::<Vec<_>>();
+    assert_eq!(pous, vec![("f", LinkageType::BuiltIn, true), ("g", LinkageType::BuiltIn, false)]);
+}
+
 // TODO(volsa): https://github.com/PLC-lang/rusty/issues/1408
 #[test]
 fn reserved_keywords_as_variable_names_are_recognized_as_errors() {
@@ -704,3 +784,410 @@ fn use_incorrect_end_keyword() {
        │         ^^^^^^^^^^^^^^^^^^ Unexpected token: expected KeywordSemicolon but found 'END_FUNCTION_BLOCK'
     ");
 }
+
+#[test]
+fn function_with_namespace_qualified_name_can_be_parsed() {
+    let (unit, diagnostics) = parse(
+        "
+        FUNCTION A.B.foo : INT
+        VAR_INPUT
+            x : INT;
+        END_VAR
+            foo := x;
+        END_FUNCTION
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+    let pou = &unit.pous[0];
+    assert_eq!(pou.name, "A.B.foo");
+    assert_eq!(pou.namespace.as_deref(), Some("A.B"));
+    assert_eq!(pou.get_return_name(), "foo");
+
+    let implementation = &unit.implementations[0];
+    assert_eq!(implementation.name, "A.B.foo");
+    assert_eq!(implementation.type_name, "A.B.foo");
+}
+
+#[test]
+fn function_with_single_segment_namespace_keeps_the_qualified_name() {
+    let src = "
+        FUNCTION Ns.Foo : INT
+        END_FUNCTION
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let pou = &unit.pous[0];
+    assert_eq!(pou.name, "Ns.Foo");
+    assert_eq!(pou.namespace.as_deref(), Some("Ns"));
+    assert_eq!(&src[pou.name_location.to_range().unwrap()], "Ns.Foo");
+    assert_eq!(unit.implementations[0].name, "Ns.Foo");
+}
+
+#[test]
+fn function_with_simple_name_has_no_namespace() {
+    let (unit, diagnostics) = parse(
+        "
+        FUNCTION foo : INT
+        END_FUNCTION
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(unit.pous[0].name, "foo");
+    assert_eq!(unit.pous[0].namespace, None);
+    assert_eq!(unit.implementations[0].name, "foo");
+    assert_eq!(unit.implementations[0].type_name, "foo");
+}
+
+#[test]
+fn explicit_en_eno_declarations_are_tagged() {
+    let (unit, diagnostics) = parse(
+        "
+        FUNCTION_BLOCK fb
+        VAR_INPUT
+            EN : BOOL;
+            x : BOOL;
+        END_VAR
+        VAR_OUTPUT
+            ENO : BOOL;
+            en : BOOL;
+        END_VAR
+        VAR
+            eno : BOOL;
+        END_VAR
+        END_FUNCTION_BLOCK
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+    let tagged = unit.pous[0]
+        .variable_blocks
+        .iter()
+        .flat_map(|block| block.variables.iter())
+        .map(|it| (it.get_name(), it.explicit_en_eno))
+        .collect::<Vec<_>>();
+    assert_eq!(tagged, vec![("EN", true), ("x", false), ("ENO", true), ("en", false), ("eno", false)]);
+}
+
+#[test]
+fn inputs_with_default_values_are_optional() {
+    let (unit, diagnostics) = parse(
+        "
+        FUNCTION foo : INT
+        VAR_INPUT
+            x : INT := 5;
+            y : INT;
+        END_VAR
+        VAR_INPUT CONSTANT
+            z : INT;
+        END_VAR
+        VAR_IN_OUT
+            io : INT;
+        END_VAR
+        VAR
+            local : INT := 1;
+        END_VAR
+        END_FUNCTION
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+    let optional = unit.pous[0]
+        .variable_blocks
+        .iter()
+        .flat_map(|block| block.variables.iter())
+        .map(|it| (it.get_name(), it.optional))
+        .collect::<Vec<_>>();
+    assert_eq!(optional, vec![("x", true), ("y", false), ("z", false), ("io", false), ("local", false)]);
+}
+
+#[test]
+fn en_eno_parameters_of_function_blocks_are_recorded_on_the_pou() {
+    let src = "
+        FUNCTION_BLOCK fb
+        VAR_INPUT
+            x : BOOL;
+            EN : BOOL;
+        END_VAR
+        VAR_OUTPUT
+            Eno : BOOL;
+        END_VAR
+        END_FUNCTION_BLOCK
+        ";
+    let (unit, diagnostics) =
+        parse_with_options(src, ParseOptions { en_eno_parameters: true, ..ParseOptions::default() });
+
+    assert_eq!(diagnostics, vec![]);
+    let pou = &unit.pous[0];
+    let enable = pou.enable.as_ref().unwrap();
+    assert_eq!(enable.name, "EN");
+    assert_eq!(&src[enable.location.to_range().unwrap()], "EN");
+    assert_eq!(pou.enable_out.as_ref().map(|it| it.name.as_str()), Some("Eno"));
+}
+
+#[test]
+fn en_eno_parameters_are_only_recorded_for_function_blocks_in_en_eno_mode() {
+    let src = "
+        FUNCTION_BLOCK fb
+        VAR_INPUT EN : BOOL; END_VAR
+        VAR_OUTPUT ENO : BOOL; END_VAR
+        END_FUNCTION_BLOCK
+        FUNCTION foo : INT
+        VAR_INPUT EN : BOOL; END_VAR
+        VAR_OUTPUT ENO : BOOL; END_VAR
+        END_FUNCTION
+        FUNCTION_BLOCK bar
+        VAR_INPUT EN : INT; END_VAR
+        VAR ENO : BOOL; END_VAR
+        END_FUNCTION_BLOCK
+        ";
+
+    let (unit, _) = parse(src);
+    assert!(unit.pous.iter().all(|it| it.enable.is_none() && it.enable_out.is_none()));
+
+    let (unit, _) =
+        parse_with_options(src, ParseOptions { en_eno_parameters: true, ..ParseOptions::default() });
+    let tagged = unit
+        .pous
+        .iter()
+        .map(|it| (it.name.as_str(), it.enable.is_some(), it.enable_out.is_some()))
+        .collect::<Vec<_>>();
+    assert_eq!(tagged, vec![("fb", true, true), ("foo", false, false), ("bar", false, false)]);
+
+    // the tagged parameters remain declared in their variable blocks
+    assert_eq!(unit.pous[0].variable_blocks[0].variables[0].get_name(), "EN");
+}
+
+#[test]
+fn declaration_only_pous_parse_with_an_empty_body() {
+    let (unit, diagnostics) = parse(
+        "
+        FUNCTION_BLOCK FB VAR x : INT; END_VAR END_FUNCTION_BLOCK
+        PROGRAM prg VAR x : INT; END_VAR END_PROGRAM
+        FUNCTION foo : INT VAR_INPUT x : INT; END_VAR END_FUNCTION
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+    let pous = unit.pous.iter().map(|it| (it.name.as_str(), &it.kind)).collect::<Vec<_>>();
+    assert_eq!(
+        pous,
+        vec![("FB", &PouType::FunctionBlock), ("prg", &PouType::Program), ("foo", &PouType::Function)]
+    );
+
+    assert_eq!(unit.implementations.len(), 3);
+    for implementation in &unit.implementations {
+        assert!(implementation.statements.is_empty(), "{} should have an empty body", implementation.name);
+    }
+}
+
+#[test]
+fn ref_to_return_type_with_initialized_return_variable() {
+    let (unit, diagnostics) = parse(
+        "
+        FUNCTION F : REF_TO INT
+        VAR
+            F : REF_TO INT := REF(g);
+        END_VAR
+        END_FUNCTION
+        ",
+    );
+
+    assert_eq!(diagnostics, vec![]);
+    let pou = &unit.pous[0];
+    let is_ref_to_int = |declaration: &DataTypeDeclaration| match declaration {
+        DataTypeDeclaration::Definition { data_type, .. } => matches!(
+            data_type.as_ref(),
+            DataType::PointerType { referenced_type, type_safe: true, auto_deref: None, .. }
+                if referenced_type.get_name() == Some("INT")
+        ),
+        _ => false,
+    };
+    assert!(is_ref_to_int(pou.return_type.as_ref().unwrap()), "{:?}", pou.return_type);
+
+    let variable = &pou.variable_blocks[0].variables[0];
+    assert_eq!(variable.get_name(), "F");
+    assert!(is_ref_to_int(&variable.data_type_declaration), "{:?}", variable.data_type_declaration);
+    let Some(AstStatement::CallStatement(CallStatement { operator, parameters: Some(parameters) })) =
+        variable.initializer.as_ref().map(|it| it.get_stmt())
+    else {
+        panic!("expected a call as initializer, got {:?}", variable.initializer);
+    };
+    assert_eq!(operator.get_flat_reference_name(), Some("REF"));
+    assert_eq!(parameters.get_flat_reference_name(), Some("g"));
+}
+
+#[test]
+fn doc_comments_between_pous_are_attached_to_the_following_pou() {
+    let src = "
+        (* adds
+           two numbers *)
+        FUNCTION foo : INT
+        END_FUNCTION // not a doc comment of bar
+
+        // negates a number
+        // (used by foo)
+        FUNCTION bar : INT
+        END_FUNCTION
+        FUNCTION baz : INT
+        END_FUNCTION
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(
+        unit.pous.iter().map(|it| it.doc_comment.as_deref()).collect::<Vec<_>>(),
+        vec![Some("adds\n           two numbers"), Some("negates a number\n(used by foo)"), None]
+    );
+}
+
+#[test]
+fn enable_init_attribute_is_captured_on_a_function_block() {
+    let src = "
+        {attribute 'enable_init'}
+        FUNCTION_BLOCK enabled
+        END_FUNCTION_BLOCK
+
+        {attribute 'enable_init' := 'false'}
+        FUNCTION_BLOCK negated
+        END_FUNCTION_BLOCK
+
+        {attribute 'disable_init'}
+        FUNCTION_BLOCK disabled
+        END_FUNCTION_BLOCK
+
+        FUNCTION_BLOCK unspecified
+        END_FUNCTION_BLOCK
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(unit.pous[0].attributes, vec![Attribute { name: "enable_init".into(), value: None }]);
+    assert_eq!(
+        unit.pous.iter().map(|it| (it.name.as_str(), it.init_enabled())).collect::<Vec<_>>(),
+        vec![
+            ("enabled", Some(true)),
+            ("negated", Some(false)),
+            ("disabled", Some(false)),
+            ("unspecified", None)
+        ]
+    );
+}
+
+#[test]
+fn pure_pragma_marks_functions_as_pure() {
+    let src = "
+        {pure}
+        FUNCTION add : INT
+        VAR_INPUT
+            a, b : INT;
+        END_VAR
+            add := a + b;
+        END_FUNCTION
+
+        FUNCTION impure : INT
+        END_FUNCTION
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(unit.pous.iter().map(|it| it.is_pure).collect::<Vec<_>>(), vec![true, false]);
+}
+
+#[test]
+fn pure_pragma_on_a_function_block_is_reported() {
+    let src = "
+        {pure}
+        FUNCTION_BLOCK counter
+        END_FUNCTION_BLOCK
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E146");
+    assert_eq!(diagnostics[0].get_message(), "Only functions can be marked as {pure}");
+    assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "{pure}");
+    assert!(!unit.pous[0].is_pure);
+}
+
+#[test]
+fn pure_pragma_before_other_declarations_is_reported() {
+    let src = "
+        {pure}
+        TYPE MyInt : INT; END_TYPE
+        {pure}
+        VAR_GLOBAL x : INT; END_VAR
+        {pure}
+        INTERFACE foo
+        END_INTERFACE
+        {pure}
+        FUNCTION bar : INT
+        END_FUNCTION
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.iter().map(|it| it.get_error_code()).collect::<Vec<_>>(), vec!["E146"; 3]);
+    let reported =
+        diagnostics.iter().map(|it| it.get_location().to_range().unwrap().start).collect::<Vec<_>>();
+    let pragmas = src.match_indices("{pure}").map(|(it, _)| it).take(3).collect::<Vec<_>>();
+    assert_eq!(reported, pragmas);
+    // the pragma still applies to the following function
+    assert!(unit.pous[0].is_pure);
+}
+
+#[test]
+fn signature_string_renders_function_inputs_and_return_type() {
+    let src = "
+        FUNCTION Add : INT
+        VAR_INPUT
+            a, b : INT;
+        END_VAR
+        VAR
+            tmp : DINT;
+        END_VAR
+        END_FUNCTION
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(unit.pous[0].signature_string(), "FUNCTION Add(a: INT, b: INT): INT");
+}
+
+#[test]
+fn signature_string_renders_function_block_inputs() {
+    let src = "
+        FUNCTION_BLOCK Timer
+        VAR_INPUT
+            start : BOOL;
+            values : ARRAY[0..2] OF REAL;
+        END_VAR
+        VAR_OUTPUT
+            done : BOOL;
+        END_VAR
+        END_FUNCTION_BLOCK
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(unit.pous[0].signature_string(), "FUNCTION_BLOCK Timer(start: BOOL, values: ARRAY OF REAL)");
+}
+
+#[test]
+fn signature_string_renders_generic_parameters() {
+    let src = "
+        FUNCTION Max<T: ANY_NUM, U: ANY_INT {INT, DINT}> : T
+        VAR_INPUT
+            a, b : T;
+        END_VAR
+        END_FUNCTION
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(
+        unit.pous[0].signature_string(),
+        "FUNCTION Max<T: ANY_NUM, U: ANY_INT {INT, DINT}>(a: T, b: T): T"
+    );
+}
@@ -1,4 +1,8 @@
 use insta::assert_debug_snapshot;
+use plc_ast::{
+    ast::{Assignment, AstNode, AstStatement},
+    literals::{Array, AstLiteral},
+};
 
 use crate::test_utils::tests::parse;
 
@@ -703,3 +707,131 @@ fn unary_plus_initializer_test() {
     )
     "#);
 }
+
+#[test]
+fn global_constant_initializer_can_reference_other_namespace() {
+    let src = "
+            VAR_GLOBAL CONSTANT
+                g : INT := Other.MAX;
+            END_VAR
+            ";
+    let (parse_result, diagnostics) = parse(src);
+    assert!(diagnostics.is_empty());
+
+    let block = &parse_result.global_vars[0];
+    assert!(block.constant);
+    // the qualified reference must be kept as-is rather than being replaced by a default value
+    assert_debug_snapshot!(block.variables[0].initializer, @r#"
+    Some(
+        ReferenceExpr {
+            kind: Member(
+                Identifier {
+                    name: "MAX",
+                },
+            ),
+            base: Some(
+                ReferenceExpr {
+                    kind: Member(
+                        Identifier {
+                            name: "Other",
+                        },
+                    ),
+                    base: None,
+                },
+            ),
+        },
+    )
+    "#);
+}
+
+#[test]
+fn struct_initializers_in_variable_lines_carry_their_members() {
+    let src = "
+            VAR_GLOBAL
+                named : Point := (x := 1, y := 2);
+                positional : Point := (1, 2);
+                single : Point := (x := 1);
+                call : Point := foo(x := 1, y := 2);
+                scalar : INT := (1);
+            END_VAR
+           ";
+    let (parse_result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let members = |name: &str| {
+        let variable = parse_result.global_vars[0].variables.iter().find(|it| it.name == name).unwrap();
+        variable.initializer.as_ref().and_then(AstNode::get_struct_initializer_members).map(|members| {
+            members
+                .iter()
+                .map(|it| (it.name.map(str::to_string), it.value.get_literal_integer_value()))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    assert_eq!(
+        members("named"),
+        Some(vec![(Some("x".to_string()), Some(1)), (Some("y".to_string()), Some(2))])
+    );
+    assert_eq!(members("positional"), Some(vec![(None, Some(1)), (None, Some(2))]));
+    assert_eq!(members("single"), Some(vec![(Some("x".to_string()), Some(1))]));
+    assert_eq!(members("call"), None);
+    assert_eq!(members("scalar"), None);
+}
+
+#[test]
+fn index_targeted_array_initializers_can_be_parsed() {
+    let src = "
+            VAR_GLOBAL
+                single : ARRAY[1..5] OF INT := [5 := 9];
+                sparse : ARRAY[1..5] OF INT := [1..3 := 0, 5 := 9];
+            END_VAR
+            ";
+    let (parse_result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let text = |node: &AstNode| src[node.get_location().to_range().unwrap()].to_string();
+    let assignments = |index: usize| {
+        let initializer = parse_result.global_vars[0].variables[index].initializer.as_ref().unwrap();
+        let AstStatement::Literal(AstLiteral::Array(Array { elements: Some(elements) })) =
+            initializer.get_stmt()
+        else {
+            panic!("expected an array literal, got {initializer:#?}");
+        };
+        elements
+            .get_as_list()
+            .into_iter()
+            .map(|element| {
+                let AstStatement::Assignment(Assignment { left, right }) = element.get_stmt() else {
+                    panic!("expected an index assignment, got {element:#?}");
+                };
+                (matches!(left.get_stmt(), AstStatement::RangeStatement(..)), text(left), text(right))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(assignments(0), vec![(false, "5".to_string(), "9".to_string())]);
+    assert_eq!(
+        assignments(1),
+        vec![(true, "1..3".to_string(), "0".to_string()), (false, "5".to_string(), "9".to_string())]
+    );
+}
+
+#[test]
+fn overlapping_array_initializer_indices_are_reported() {
+    let src = "
+            VAR_GLOBAL
+                values : ARRAY[1..10] OF INT := [1..5 := 0, 7 := 1, 4 := 9, 6..8 := 2];
+            END_VAR
+            ";
+    let (_, diagnostics) = parse(src);
+
+    let reported = diagnostics
+        .iter()
+        .map(|it| (it.get_error_code(), &src[it.get_location().to_range().unwrap()]))
+        .collect::<Vec<_>>();
+    assert_eq!(reported, vec![("E149", "4"), ("E149", "6..8")]);
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "Array initializer index overlaps with a previously initialized index"
+    );
+}
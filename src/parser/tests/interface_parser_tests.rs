@@ -1,4 +1,4 @@
-use crate::test_utils::tests::parse;
+use crate::{parser::implementors, test_utils::tests::parse};
 
 #[test]
 fn empty_interface() {
@@ -460,6 +460,7 @@ fn interface_with_property() {
                     },
                 },
             ],
+            overriding: false,
         },
     ]
     "###);
@@ -570,4 +571,141 @@ mod error_handling {
         let (_, diagnostics) = parse(source);
         assert_eq!(diagnostics.len(), 0, "Expected no diagnostics but got {:#?}", diagnostics);
     }
+
+    #[test]
+    fn missing_end_interface_keeps_parsed_methods_and_properties() {
+        let source = r"
+        INTERFACE interfaceA
+            METHOD methodA : INT
+            END_METHOD
+
+            PROPERTY propA : INT
+                GET END_GET
+            END_PROPERTY
+
+        FUNCTION_BLOCK foo IMPLEMENTS interfaceA
+        END_FUNCTION_BLOCK
+        ";
+
+        let (unit, diagnostics) = parse(source);
+        let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+        assert_eq!(messages, vec!["Missing expected Token KeywordEndInterface"]);
+
+        let interface = &unit.interfaces[0];
+        assert_eq!(interface.ident.name, "interfaceA");
+        assert_eq!(
+            interface.methods.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(),
+            vec!["interfaceA.methodA"]
+        );
+        assert_eq!(
+            interface.properties.iter().map(|it| it.ident.name.as_str()).collect::<Vec<_>>(),
+            vec!["propA"]
+        );
+
+        // the following POU is still parsed
+        assert_eq!(unit.pous[0].name, "foo");
+        assert_eq!(unit.pous[0].interfaces[0].name, "interfaceA");
+    }
+
+    #[test]
+    fn function_implementing_interfaces_is_reported_at_the_implements_keyword() {
+        let source = "FUNCTION foo IMPLEMENTS interfaceA, interfaceB END_FUNCTION";
+
+        let (unit, diagnostics) = parse(source);
+        let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+        assert_eq!(messages, vec!["FUNCTION cannot implement interfaces"]);
+        assert_eq!(diagnostics[0].get_error_code(), "E110");
+        assert_eq!(diagnostics[0].get_location().to_range(), Some(13..23));
+
+        // the interfaces are still parsed
+        assert_eq!(
+            unit.pous[0].interfaces.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(),
+            vec!["interfaceA", "interfaceB"]
+        );
+    }
+
+    #[test]
+    fn program_implementing_interfaces_is_reported_at_the_implements_keyword() {
+        let source = "PROGRAM foo IMPLEMENTS interfaceA END_PROGRAM";
+
+        let (unit, diagnostics) = parse(source);
+        let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+        assert_eq!(messages, vec!["PROGRAM cannot implement interfaces"]);
+        assert_eq!(diagnostics[0].get_location().to_range(), Some(12..22));
+        assert_eq!(unit.pous[0].interfaces[0].name, "interfaceA");
+    }
+}
+
+#[test]
+fn implementors_of_an_interface_are_listed() {
+    let source = r"
+    INTERFACE counter
+    END_INTERFACE
+
+    INTERFACE resettable
+    END_INTERFACE
+
+    FUNCTION_BLOCK up IMPLEMENTS counter, resettable
+    END_FUNCTION_BLOCK
+
+    FUNCTION_BLOCK down IMPLEMENTS Counter
+    END_FUNCTION_BLOCK
+
+    FUNCTION_BLOCK other IMPLEMENTS resettable
+    END_FUNCTION_BLOCK
+    ";
+
+    let (unit, diagnostics) = parse(source);
+
+    assert_eq!(diagnostics.len(), 0, "Expected no diagnostics but got {:#?}", diagnostics);
+    assert_eq!(implementors(&unit, "counter"), vec!["up", "down"]);
+    // a qualified name is matched by its last segment
+    assert_eq!(implementors(&unit, "ns.counter"), vec!["up", "down"]);
+    assert_eq!(implementors(&unit, "resettable"), vec!["up", "other"]);
+    assert!(implementors(&unit, "unknown").is_empty());
+}
+
+#[test]
+fn duplicate_method_names_in_an_interface_are_reported() {
+    let src = "
+        INTERFACE foo
+            METHOD bar : INT
+            END_METHOD
+
+            METHOD BAR
+            END_METHOD
+        END_INTERFACE
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E004");
+    assert_eq!(diagnostics[0].get_message(), "Method `BAR` is declared multiple times in interface `foo`");
+    let range = diagnostics[0].get_location().to_range().unwrap();
+    assert_eq!(&src[range.clone()], "BAR");
+    assert!(range.start > src.find("bar").unwrap());
+    assert_eq!(unit.interfaces[0].method_names(), vec!["bar", "BAR"]);
+}
+
+#[test]
+fn distinct_method_names_in_an_interface_are_accepted() {
+    let src = "
+        INTERFACE foo
+            METHOD bar
+            END_METHOD
+
+            METHOD baz
+            END_METHOD
+        END_INTERFACE
+
+        INTERFACE qux EXTENDS foo
+            METHOD bar
+            END_METHOD
+        END_INTERFACE
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(unit.interfaces[0].method_names(), vec!["bar", "baz"]);
+    assert_eq!(unit.interfaces[1].method_names(), vec!["bar"]);
 }
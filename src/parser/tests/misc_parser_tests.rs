@@ -1,14 +1,27 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use core::panic;
 
-use crate::test_utils::tests::parse;
+use crate::{
+    parser::{
+        extract_pou_source, literals, location_to_line_col, parse_container, parse_file, parse_file_lossy,
+        reparse_pou, signature_hash, statements_with_text, unparse, LineCol, LiteralKind, ParseOptions,
+    },
+    test_utils::tests::{parse, parse_with_options},
+};
 use insta::assert_debug_snapshot;
 use plc_ast::{
     ast::{
-        Assignment, AstNode, AstStatement, BinaryExpression, CallStatement, LinkageType, ReferenceAccess,
-        ReferenceExpr, UnaryExpression,
+        Assignment, AstFactory, AstId, AstNode, AstStatement, BinaryExpression, CallStatement,
+        CompilationUnit, LinkageType, PouType, ReferenceAccess, ReferenceExpr, UnaryExpression,
     },
     control_statements::{AstControlStatement, CaseStatement, ForLoopStatement, IfStatement, LoopStatement},
+    provider::IdProvider,
+    visitor::{AstVisitor, Walker},
+};
+use plc_diagnostics::diagnostician::Diagnostician;
+use plc_source::{
+    source_location::{SourceLocation, SourceLocationFactory},
+    SourceCode,
 };
 use pretty_assertions::*;
 use rustc_hash::FxHashSet;
@@ -51,7 +64,7 @@ fn exponent_literals_parsed_as_variables() {
 fn ids_are_assigned_to_parsed_literals() {
     let src = "
     PROGRAM PRG
-        ;
+        x;
         (* literals *)
         1;
         D#2021-10-01;
@@ -296,9 +309,9 @@ fn ids_are_assigned_to_if_statements() {
     let src = "
     PROGRAM PRG
         IF TRUE THEN
-            ;
+            a;
         ELSE
-            ;
+            b;
         END_IF
     END_PROGRAM
     ";
@@ -327,9 +340,9 @@ fn ids_are_assigned_to_for_statements() {
     let src = "
     PROGRAM PRG
         FOR x := 1 TO 7 BY 2 DO
-            ;
-            ;
-            ;
+            a;
+            b;
+            c;
         END_FOR;
     END_PROGRAM
     ";
@@ -368,7 +381,7 @@ fn ids_are_assigned_to_while_statements() {
     let src = "
     PROGRAM PRG
        WHILE TRUE DO
-            ;;
+            a; b;
         END_WHILE
     END_PROGRAM
     ";
@@ -400,7 +413,7 @@ fn ids_are_assigned_to_repeat_statements() {
     let src = "
     PROGRAM PRG
        REPEAT
-            ;;
+            a; b;
        UNTIL TRUE END_REPEAT
     END_PROGRAM
     ";
@@ -434,11 +447,11 @@ fn ids_are_assigned_to_case_statements() {
     PROGRAM PRG
     CASE PumpState OF
     0:
-        ;
+        a;
     1,2:
-        ;
+        b;
     ELSE
-        ;
+        c;
     END_CASE;
     END_PROGRAM
     ";
@@ -481,3 +494,643 @@ fn ids_are_assigned_to_case_statements() {
         _ => panic!("invalid statement"),
     }
 }
+
+#[test]
+fn location_to_line_col_counts_characters_after_multibyte_text_and_crlf() {
+    let src = "PROGRAM prg\r\nVAR\r\n    s : STRING := 'äöü'; x : INT;\r\nEND_VAR\r\nEND_PROGRAM";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let x = &result.pous[0].variable_blocks[0].variables[1];
+    let (start, end) = location_to_line_col(&SourceCode::from(src), &x.location).unwrap();
+
+    assert_eq!(start, LineCol { line: 3, column: 26 });
+    assert_eq!(end, LineCol { line: 3, column: 27 });
+}
+
+#[test]
+fn location_to_line_col_maps_unicode_identifier_range() {
+    let src = "(* Grüße *)\r\nVAR_GLOBAL größe : INT; END_VAR";
+    let start_offset = src.find("größe").unwrap();
+    let location =
+        SourceLocationFactory::internal(src).create_range(start_offset..start_offset + "größe".len());
+
+    let (start, end) = location_to_line_col(&SourceCode::from(src), &location).unwrap();
+
+    assert_eq!(start, LineCol { line: 2, column: 12 });
+    assert_eq!(end, LineCol { line: 2, column: 17 });
+}
+
+#[test]
+fn location_to_line_col_returns_none_for_locations_without_range() {
+    let src = "PROGRAM prg END_PROGRAM";
+    assert_eq!(location_to_line_col(&SourceCode::from(src), &SourceLocation::internal()), None);
+
+    let out_of_bounds = SourceLocationFactory::internal(src).create_range(0..src.len() + 1);
+    assert_eq!(location_to_line_col(&SourceCode::from(src), &out_of_bounds), None);
+}
+
+#[test]
+fn parse_with_default_options_behaves_like_parse() {
+    let src = "
+        FUNCTION foo : INT
+        VAR_INPUT
+            a : INT;
+        END_VAR
+            foo := a + 1;
+        END_FUNCTION
+
+        PROGRAM prg
+            foo(
+        END_PROGRAM
+        ";
+    let (expected, expected_diagnostics) = parse(src);
+    let (unit, diagnostics) = parse_with_options(src, ParseOptions::default());
+
+    assert_eq!(format!("{unit:#?}"), format!("{expected:#?}"));
+    assert_eq!(diagnostics, expected_diagnostics);
+}
+
+#[test]
+fn keyword_casing_of_pous_is_recorded_if_requested() {
+    let src = "
+        Function foo : INT END_FUNCTION
+        function_block fb
+            method bar END_METHOD
+        END_FUNCTION_BLOCK
+        PROGRAM prg END_PROGRAM
+        ";
+    let (unit, diagnostics) =
+        parse_with_options(src, ParseOptions { record_keyword_casing: true, ..Default::default() });
+
+    assert_eq!(diagnostics, vec![]);
+    let keywords = unit.pous.iter().map(|it| (it.name.as_str(), it.keyword.as_deref())).collect::<Vec<_>>();
+    assert_eq!(
+        keywords,
+        vec![
+            ("foo", Some("Function")),
+            ("fb", Some("function_block")),
+            ("fb.bar", Some("method")),
+            ("prg", Some("PROGRAM")),
+        ]
+    );
+}
+
+#[test]
+fn keyword_casing_of_pous_is_not_recorded_by_default() {
+    let (unit, _) = parse("Function foo : INT END_FUNCTION");
+    assert_eq!(unit.pous[0].keyword, None);
+}
+
+#[test]
+fn lossy_file_parsing_keeps_the_unit_despite_critical_errors() {
+    let source = SourceCode::new(
+        "
+        FUNCTION foo : INT END_FUNCTION
+        PROGRAM prg
+            x := 1 +;
+        END_PROGRAM
+        ",
+        "test.st",
+    );
+
+    let result =
+        parse_file(&source, LinkageType::Internal, IdProvider::default(), &mut Diagnostician::buffered());
+    assert!(result.is_err());
+
+    let (unit, diagnostics) = parse_file_lossy(&source, LinkageType::Internal, IdProvider::default());
+    assert!(!diagnostics.is_empty());
+    let pous = unit.pous.iter().map(|it| it.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(pous, vec!["foo", "prg"]);
+}
+
+#[test]
+fn pou_exceeding_the_statement_limit_is_reported() {
+    let src = "
+        PROGRAM prg
+            a := 1;
+            b := 2;
+            IF a THEN
+                c := 3;
+                d := 4;
+            END_IF
+        END_PROGRAM
+        ";
+    let (_, diagnostics) =
+        parse_with_options(src, ParseOptions { max_statements_per_pou: Some(2), ..Default::default() });
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].get_error_code(), "E127");
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "`prg` contains 3 statements, which exceeds the configured maximum of 2"
+    );
+    assert_eq!(
+        diagnostics[0].get_location().to_range(),
+        Some(src.find("prg").unwrap()..src.find("prg").unwrap() + 3)
+    );
+}
+
+#[test]
+fn pou_within_the_statement_limit_is_not_reported() {
+    let src = "
+        PROGRAM prg
+            a := 1;
+            b := 2;
+        END_PROGRAM
+        ";
+    let (_, diagnostics) =
+        parse_with_options(src, ParseOptions { max_statements_per_pou: Some(2), ..Default::default() });
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn literals_are_collected_from_declarations_and_bodies() {
+    let src = r#"
+        PROGRAM prg
+        VAR
+            a : INT := 5;
+            s : STRING := 'hello';
+            arr : ARRAY[0..1] OF REAL := [1.5, 2.5];
+        END_VAR
+            a := a + 7;
+            w := "wide";
+            b := TRUE;
+        END_PROGRAM
+        "#;
+    let (unit, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let literals = literals(&unit)
+        .into_iter()
+        .map(|it| (it.kind, it.value, &src[it.location.to_range().unwrap()]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        literals,
+        vec![
+            (LiteralKind::Integer, "5".to_string(), "5"),
+            (LiteralKind::String, "hello".to_string(), "'hello'"),
+            (LiteralKind::Integer, "0".to_string(), "0"),
+            (LiteralKind::Integer, "1".to_string(), "1"),
+            (LiteralKind::Real, "1.5".to_string(), "1.5"),
+            (LiteralKind::Real, "2.5".to_string(), "2.5"),
+            (LiteralKind::Integer, "7".to_string(), "7"),
+            (LiteralKind::WideString, "wide".to_string(), "\"wide\""),
+            (LiteralKind::Bool, "true".to_string(), "TRUE"),
+        ]
+    );
+}
+
+#[test]
+fn statements_are_annotated_with_their_nesting_depth() {
+    let src = "
+        PROGRAM prg
+            a := 1;
+            IF a > 0 THEN
+                b := 2;
+                WHILE b < 5 DO
+                    CASE b OF
+                    3: c := 3;
+                    END_CASE
+                END_WHILE
+            ELSE
+                d := 4;
+            END_IF
+        END_PROGRAM
+        ";
+    let (unit, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    // collect the start of every node in source order to look up the depths of the statements among them
+    struct NodeStarts(Vec<(AstId, usize)>);
+    impl AstVisitor for NodeStarts {
+        fn visit(&mut self, node: &AstNode) {
+            if let Some(range) = node.get_location().to_range() {
+                self.0.push((node.get_id(), range.start));
+            }
+            node.walk(self)
+        }
+    }
+    let mut starts = NodeStarts(vec![]);
+    starts.visit_compilation_unit(&unit);
+
+    let depths = statement_depths(&unit);
+    let statements = starts
+        .0
+        .iter()
+        .filter_map(|(id, start)| {
+            let first_line = src[*start..].lines().next()?;
+            Some((first_line.trim_end_matches(';'), *depths.get(id)?))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statements,
+        vec![
+            ("a := 1", 0),
+            ("IF a > 0 THEN", 0),
+            ("b := 2", 1),
+            ("WHILE b < 5 DO", 1),
+            ("CASE b OF", 2),
+            ("c := 3", 3),
+            ("d := 4", 1),
+        ]
+    );
+}
+
+#[test]
+fn unparse_renders_canonical_source() {
+    let src = r#"
+        TYPE Point : STRUCT x : INT; y : INT := 2; END_STRUCT END_TYPE
+
+        FUNCTION foo : INT
+        VAR_INPUT a : INT; b : ARRAY[0..3] OF REAL; END_VAR
+        VAR CONSTANT s : STRING[10] := 'a$'b'; END_VAR
+            IF a > 0 THEN foo := a * 2; ELSIF a = 0 THEN foo := 1; ELSE bar(x := a, y => foo); END_IF
+            FOR i := 0 TO 3 BY 1 DO b[i] := 1.5; END_FOR
+            CASE a OF 1, 2: foo := 1; ELSE RETURN; END_CASE
+        END_FUNCTION
+        "#;
+    let (unit, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let expected = "\
+TYPE
+    Point : STRUCT
+        x : INT;
+        y : INT := 2;
+    END_STRUCT;
+END_TYPE
+FUNCTION foo : INT
+VAR_INPUT
+    a : INT;
+    b : ARRAY[0..3] OF REAL;
+END_VAR
+VAR CONSTANT
+    s : STRING[10] := 'a$'b';
+END_VAR
+    IF a > 0 THEN
+        foo := a * 2;
+    ELSIF a = 0 THEN
+        foo := 1;
+    ELSE
+        bar(x := a, y => foo);
+    END_IF
+    FOR i := 0 TO 3 BY 1 DO
+        b[i] := 1.5;
+    END_FOR
+    CASE a OF
+        1, 2:
+            foo := 1;
+    ELSE
+        RETURN;
+    END_CASE
+END_FUNCTION
+";
+    assert_eq!(unparse(&unit), expected);
+}
+
+#[test]
+fn unparsed_source_reparses_to_an_equivalent_ast() {
+    let src = r#"
+        VAR_GLOBAL g AT %IX1.2 : BOOL; END_VAR
+        TYPE Color : (red, green := 5) BYTE; END_TYPE
+
+        FUNCTION_BLOCK fb EXTENDS base
+        VAR_INPUT {ref} x : DINT; END_VAR
+        VAR
+            p : REF_TO INT;
+            q : POINTER TO INT := NULL;
+            c : Color := Color#red;
+            r AT x : DINT;
+            d : DATE := D#2024-01-31;
+        END_VAR
+        METHOD m : BOOL
+        VAR_INPUT v : INT; END_VAR
+            m := NOT (v > 2);
+        END_METHOD
+            p^ := SUPER^.y;
+            WHILE x < 10 DO x := x + 1; END_WHILE
+            REPEAT EXIT; UNTIL TRUE END_REPEAT
+        END_FUNCTION_BLOCK
+
+        ACTIONS fb ACTION act x := 1; END_ACTION END_ACTIONS
+
+        FUNCTION gen<T: ANY_NUM> : T VAR_INPUT in : T; END_VAR END_FUNCTION
+
+        FUNCTION sign : DINT VAR_INPUT a : DINT; END_VAR
+            IF a > 0 THEN sign := 1; ELSIF a < 0 THEN sign := -1; ELSE RETURN (a + 1).b; END_IF
+        END_FUNCTION
+        "#;
+    let (unit, _) = parse(src);
+    let unparsed = unparse(&unit);
+    let (reparsed, diagnostics) = parse(&unparsed);
+    assert_eq!(diagnostics, vec![]);
+
+    // locations and ids differ between the two units, the debug output of the AST leaves them out
+    let bodies = |unit: &CompilationUnit| {
+        let mut bodies = unit
+            .implementations
+            .iter()
+            .map(|it| format!("{}: {:#?}", it.name, it.statements))
+            .collect::<Vec<_>>();
+        bodies.sort();
+        bodies
+    };
+    assert_eq!(format!("{:#?}", reparsed.global_vars), format!("{:#?}", unit.global_vars));
+    assert_eq!(format!("{:#?}", reparsed.user_types), format!("{:#?}", unit.user_types));
+    assert_eq!(format!("{:#?}", reparsed.pous), format!("{:#?}", unit.pous));
+    assert_eq!(bodies(&reparsed), bodies(&unit));
+    assert_eq!(unparse(&reparsed), unparsed);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "cannot be unparsed")]
+fn unparsing_a_statement_without_source_representation_panics_in_debug_builds() {
+    let (mut unit, _) = parse("PROGRAM prg x := 1; END_PROGRAM");
+    let statement = &mut unit.implementations[0].statements[0];
+    *statement =
+        AstFactory::create_label_statement("label".into(), statement.get_location(), statement.get_id());
+    unparse(&unit);
+}
+
+#[test]
+fn pou_source_is_extracted_with_its_methods_and_actions() {
+    let src = "
+        FUNCTION foo : INT
+            foo := 1;
+        END_FUNCTION
+
+        FUNCTION_BLOCK fb
+            METHOD m : INT
+                m := foo();
+            END_METHOD
+            x := 2;
+        END_FUNCTION_BLOCK
+
+        ACTION fb.a
+            x := 3;
+        END_ACTION
+
+        ACTIONS fb
+            ACTION b
+                x := 4;
+            END_ACTION
+        END_ACTIONS
+        ";
+    let (unit, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    assert_eq!(
+        extract_pou_source(src, &unit, "foo").unwrap(),
+        "FUNCTION foo : INT
+            foo := 1;
+        END_FUNCTION"
+    );
+    assert_eq!(
+        extract_pou_source(src, &unit, "FB").unwrap(),
+        "FUNCTION_BLOCK fb
+            METHOD m : INT
+                m := foo();
+            END_METHOD
+            x := 2;
+        END_FUNCTION_BLOCK
+
+        ACTION fb.a
+            x := 3;
+        END_ACTION
+
+        ACTIONS fb
+            ACTION b
+                x := 4;
+            END_ACTION
+        END_ACTIONS"
+    );
+    assert_eq!(extract_pou_source(src, &unit, "bar"), None);
+
+    // the extracted source can be parsed on its own
+    let extracted = extract_pou_source(src, &unit, "fb").unwrap();
+    let (unit, diagnostics) = parse(&extracted);
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(unit.pous.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(), vec!["fb", "fb.m"]);
+    let actions = unit
+        .implementations
+        .iter()
+        .filter(|it| it.pou_type == PouType::Action)
+        .map(|it| it.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(actions, vec!["fb.a", "fb.b"]);
+}
+
+#[test]
+fn reparse_pou_only_parses_the_given_range() {
+    let src = "
+        FUNCTION foo : INT
+            foo := 1;
+        END_FUNCTION
+
+        FUNCTION_BLOCK fb
+            METHOD m : INT
+                m := 2;
+            END_METHOD
+        END_FUNCTION_BLOCK
+        ";
+    let start = src.find("FUNCTION_BLOCK").unwrap();
+    let end = src.rfind("END_FUNCTION_BLOCK").unwrap() + "END_FUNCTION_BLOCK".len();
+
+    let (pous, implementations, diagnostics) =
+        reparse_pou(&SourceCode::from(src), start..end, IdProvider::default());
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(pous.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(), vec!["fb", "fb.m"]);
+    assert_eq!(implementations.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(), vec!["fb.m", "fb"]);
+    // the locations refer to the whole source
+    assert_eq!(&src[pous[0].name_location.to_range().unwrap()], "fb");
+    assert_eq!(&src[pous[1].name_location.to_range().unwrap()], "m");
+}
+
+#[test]
+fn reparse_pou_rejects_a_range_not_starting_with_a_pou() {
+    let src = "
+        FUNCTION foo : INT
+            foo := 1;
+        END_FUNCTION
+        ";
+    let source = SourceCode::from(src);
+    let start = src.find("foo := 1").unwrap();
+
+    let (pous, implementations, diagnostics) = reparse_pou(&source, start..src.len(), IdProvider::default());
+    assert!(pous.is_empty() && implementations.is_empty());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].get_error_code(), "E139");
+    assert_eq!(diagnostics[0].get_message(), "Expected a POU declaration, found 'foo'");
+    assert_eq!(diagnostics[0].get_location().to_range(), Some(start..start + 3));
+
+    let (pous, _, diagnostics) = reparse_pou(&source, 0..src.len() + 1, IdProvider::default());
+    assert!(pous.is_empty());
+    assert_eq!(diagnostics[0].get_error_code(), "E139");
+}
+
+#[test]
+fn semicolons_after_top_level_items_are_ignored() {
+    let src = "
+        ;
+        FUNCTION foo : INT
+        END_FUNCTION;
+
+        FUNCTION_BLOCK fb
+        END_FUNCTION_BLOCK;;
+
+        ACTION fb.a
+        END_ACTION;
+
+        PROGRAM prg
+        END_PROGRAM;
+
+        VAR_GLOBAL
+            g : INT;
+        END_VAR;
+
+        TYPE t : STRUCT
+            x : INT;
+        END_STRUCT END_TYPE;
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(unit.pous.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(), vec!["foo", "fb", "prg"]);
+    assert_eq!(
+        unit.implementations.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(),
+        vec!["foo", "fb", "fb.a", "prg"]
+    );
+    assert_eq!(unit.global_vars[0].variables[0].name, "g");
+    assert_eq!(unit.user_types.len(), 1);
+}
+
+#[test]
+fn signature_hash_ignores_the_body() {
+    let hash = |src: &str| signature_hash(&parse(src).0.pous[0]);
+
+    let original = hash("FUNCTION foo : INT VAR_INPUT a : INT; END_VAR foo := a; END_FUNCTION");
+    let body_changed = hash(
+        "
+        // the comment and the body do not matter
+        FUNCTION foo : INT
+        VAR_INPUT
+            a : INT;
+        END_VAR
+        VAR
+            b : INT;
+        END_VAR
+            b := a * 2;
+            foo := b;
+        END_FUNCTION",
+    );
+    assert_eq!(original, body_changed);
+    // names are case-insensitive
+    assert_eq!(original, hash("function FOO : int var_input A : Int; end_var FOO := A; end_function"));
+
+    for changed in [
+        "FUNCTION foo : INT VAR_INPUT a : DINT; END_VAR foo := a; END_FUNCTION",
+        "FUNCTION foo : INT VAR_INPUT b : INT; END_VAR foo := b; END_FUNCTION",
+        "FUNCTION foo : INT VAR_IN_OUT a : INT; END_VAR foo := a; END_FUNCTION",
+        "FUNCTION foo : DINT VAR_INPUT a : INT; END_VAR foo := a; END_FUNCTION",
+        "FUNCTION foo<T : ANY_INT> : INT VAR_INPUT a : INT; END_VAR foo := a; END_FUNCTION",
+        "FUNCTION bar : INT VAR_INPUT a : INT; END_VAR bar := a; END_FUNCTION",
+        "FUNCTION foo : INT VAR_INPUT a : ARRAY[0..1] OF INT; END_VAR foo := a[0]; END_FUNCTION",
+        "FUNCTION foo : INT VAR_INPUT a : STRING[10]; END_VAR END_FUNCTION",
+    ] {
+        assert_ne!(original, hash(changed), "{changed}");
+    }
+}
+
+#[test]
+fn signature_hash_compares_nested_types_structurally() {
+    let hash = |src: &str| signature_hash(&parse(src).0.pous[0]);
+
+    let original = hash("FUNCTION foo : INT VAR_INPUT a : ARRAY[0..1] OF REF_TO INT; END_VAR END_FUNCTION");
+    assert_eq!(
+        original,
+        hash("FUNCTION foo : INT VAR_INPUT\n    a : array [0..1] OF ref_to int;\nEND_VAR END_FUNCTION")
+    );
+    assert_ne!(
+        original,
+        hash("FUNCTION foo : INT VAR_INPUT a : ARRAY[0..2] OF REF_TO INT; END_VAR END_FUNCTION")
+    );
+    assert_ne!(
+        original,
+        hash("FUNCTION foo : INT VAR_INPUT a : ARRAY[0..1] OF REF_TO DINT; END_VAR END_FUNCTION")
+    );
+    assert_ne!(
+        original,
+        hash("FUNCTION foo : INT VAR_INPUT a : ARRAY[0..1] OF POINTER TO INT; END_VAR END_FUNCTION")
+    );
+}
+
+#[test]
+fn statements_with_text_returns_the_source_of_each_statement() {
+    let src = "
+        PROGRAM prg
+            x := 1;
+            IF x > 0 THEN
+                y := 2;
+            END_IF
+            foo(a := x,
+                b := y);
+        END_PROGRAM
+        ";
+    let (unit, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let statements = statements_with_text(src, &unit.implementations[0]);
+    let texts = statements.iter().map(|(_, text)| text.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        texts,
+        vec![
+            "x := 1",
+            "IF x > 0 THEN\n                y := 2;\n            END_IF",
+            "foo(a := x,\n                b := y)",
+        ]
+    );
+    // the locations point at the statements
+    let lines = statements.iter().map(|(location, _)| location.get_line_plus_one()).collect::<Vec<_>>();
+    assert_eq!(lines, vec![3, 4, 7]);
+}
+
+#[test]
+fn parse_container_merges_all_sources_of_a_directory() {
+    let directory = tempfile::tempdir().unwrap();
+    std::fs::write(
+        directory.path().join("a.st"),
+        "FUNCTION foo : INT END_FUNCTION TYPE MyInt : INT; END_TYPE",
+    )
+    .unwrap();
+    std::fs::write(directory.path().join("b.st"), "PROGRAM main END_PROGRAM").unwrap();
+
+    let (unit, diagnostics) =
+        parse_container(&directory.path(), LinkageType::Internal, IdProvider::default());
+
+    assert_eq!(diagnostics, vec![]);
+    let pous = unit.pous.iter().map(|it| it.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(pous, vec!["foo", "main"]);
+    assert_eq!(unit.user_types.len(), 1);
+    assert!(unit.pous[0].location.get_file_name().unwrap().ends_with("a.st"));
+    assert!(unit.pous[1].location.get_file_name().unwrap().ends_with("b.st"));
+}
+
+#[test]
+fn parse_container_reports_top_level_names_declared_in_several_files() {
+    let directory = tempfile::tempdir().unwrap();
+    std::fs::write(directory.path().join("a.st"), "FUNCTION foo : INT END_FUNCTION").unwrap();
+    std::fs::write(directory.path().join("b.st"), "FUNCTION_BLOCK FOO END_FUNCTION_BLOCK").unwrap();
+
+    let (unit, diagnostics) =
+        parse_container(&directory.path(), LinkageType::Internal, IdProvider::default());
+
+    assert_eq!(unit.pous.len(), 2);
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E004");
+    assert!(diagnostics[0].get_message().starts_with("FOO: Duplicate symbol, already declared in"));
+    assert!(diagnostics[0].get_location().get_file_name().unwrap().ends_with("b.st"));
+}
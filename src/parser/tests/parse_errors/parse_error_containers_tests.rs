@@ -1,7 +1,11 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 
-use crate::test_utils::tests::{
-    parse_and_report_parse_errors_buffered, parse_and_validate_buffered, parse_buffered,
+use crate::{
+    parser::ParseOptions,
+    test_utils::tests::{
+        parse, parse_and_report_parse_errors_buffered, parse_and_validate_buffered, parse_buffered,
+        parse_with_options,
+    },
 };
 use insta::{assert_debug_snapshot, assert_snapshot};
 
@@ -353,3 +357,109 @@ fn this_is_a_reserved_keyword() {
       │             ^^^^ Invalid use of `THIS`. Usage is only allowed within `FUNCTION_BLOCK` and its `METHOD`s and `ACTION`s.
     ");
 }
+
+/// parses the given source and returns the messages of all reported diagnostics
+fn parse_error_messages(src: &str) -> Vec<String> {
+    let (_, diagnostics) = parse(src);
+    diagnostics.iter().map(|it| it.get_message().to_string()).collect()
+}
+
+#[test]
+fn function_keyword_followed_by_eof_reports_missing_name_and_end() {
+    let messages = parse_error_messages("FUNCTION");
+
+    assert!(
+        messages.contains(&"Unexpected token: expected Identifier but found ".to_string()),
+        "{messages:?}"
+    );
+    assert!(messages.iter().any(|it| it.contains("expected KeywordEndFunction")), "{messages:?}");
+}
+
+#[test]
+fn interface_keyword_followed_by_eof_reports_missing_name_and_end() {
+    let messages = parse_error_messages("INTERFACE");
+
+    assert!(
+        messages.contains(&"Expected a name for the interface definition but got nothing".to_string()),
+        "{messages:?}"
+    );
+    assert!(messages.contains(&"Missing expected Token KeywordEndInterface".to_string()), "{messages:?}");
+}
+
+#[test]
+fn type_keyword_followed_by_eof_reports_missing_end() {
+    let (unit, diagnostics) = parse("TYPE");
+
+    assert!(unit.user_types.is_empty());
+    let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+    assert_eq!(messages, vec!["Unexpected token: expected KeywordEndType but found ''"]);
+}
+
+#[test]
+fn variable_block_after_the_body_is_reported_and_kept() {
+    let src = "
+        PROGRAM prg
+        VAR
+            x : INT;
+        END_VAR
+            x := 1;
+        VAR_TEMP
+            t : INT;
+        END_VAR
+            t := x;
+        END_PROGRAM
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0].get_message(), "Variable blocks must be declared before the body");
+    assert_eq!(diagnostics[0].get_error_code(), "E132");
+    assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "VAR_TEMP");
+
+    // both the statements and the misplaced block are kept
+    let variables = unit.pous[0]
+        .variable_blocks
+        .iter()
+        .flat_map(|it| it.variables.iter().map(|it| it.get_name()))
+        .collect::<Vec<_>>();
+    assert_eq!(variables, vec!["x", "t"]);
+    assert_eq!(unit.implementations[0].statements.len(), 2);
+    assert_eq!(&src[unit.implementations[0].end_location.to_range().unwrap()], "END_PROGRAM");
+}
+
+#[test]
+fn diagnostics_are_tagged_with_their_pou_if_requested() {
+    let src = "
+        FUNCTION foo : INT
+            foo := 1;
+        VAR_TEMP t : INT; END_VAR
+        END_FUNCTION
+        FUNCTION_BLOCK fb
+            METHOD m
+                m := 1;
+            VAR_TEMP t : INT; END_VAR
+            END_METHOD
+        END_FUNCTION_BLOCK
+        stray;
+        ";
+    let parse_with_scopes = |diagnostic_scopes| {
+        parse_with_options(src, ParseOptions { diagnostic_scopes, ..ParseOptions::default() }).1
+    };
+
+    let diagnostics = parse_with_scopes(true);
+    let scopes = diagnostics.iter().map(|it| (it.get_error_code(), it.get_scope())).collect::<Vec<_>>();
+    assert_eq!(
+        scopes,
+        vec![
+            ("E132", Some("foo")),
+            ("E132", Some("fb.m")),
+            // diagnostics outside of any POU have no scope, the trailing `;` is tolerated
+            ("E007", None),
+        ]
+    );
+
+    // without the option, no diagnostic is tagged
+    let diagnostics = parse_with_scopes(false);
+    assert_eq!(diagnostics.len(), 3);
+    assert!(diagnostics.iter().all(|it| it.get_scope().is_none()));
+}
@@ -1,9 +1,12 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
-use crate::{parser::tests::ref_to, test_utils::tests::parse_buffered};
+use crate::{
+    parser::tests::ref_to,
+    test_utils::tests::{parse, parse_buffered},
+};
 use insta::{assert_debug_snapshot, assert_snapshot};
 use plc_ast::ast::{
-    AccessModifier, AstFactory, DataType, DataTypeDeclaration, LinkageType, UserTypeDeclaration, Variable,
-    VariableBlock, VariableBlockType,
+    AccessModifier, AstFactory, DataType, DataTypeDeclaration, LinkageType, PointerKind, UserTypeDeclaration,
+    Variable, VariableBlock, VariableBlockType,
 };
 use plc_source::source_location::SourceLocation;
 use pretty_assertions::*;
@@ -202,6 +205,7 @@ fn invalid_variable_name_error_recovery() {
                 constant: false,
                 access: AccessModifier::Protected,
                 retain: false,
+                persistent: false,
                 location: SourceLocation::internal(),
                 variables: vec![Variable {
                     name: "c".into(),
@@ -211,6 +215,11 @@ fn invalid_variable_name_error_recovery() {
                     },
                     initializer: None,
                     address: None,
+                    retain: None,
+                    attributes: vec![],
+                    explicit_en_eno: false,
+                    optional: false,
+                    absolute_address: None,
                     location: SourceLocation::internal(),
                 },],
                 kind: VariableBlockType::Local,
@@ -574,9 +583,7 @@ fn test_nested_repeat_with_missing_until_end_repeat() {
                             base: None,
                         },
                     },
-                    body: [
-                        EmptyStatement,
-                    ],
+                    body: [],
                 },
                 Assignment {
                     left: ReferenceExpr {
@@ -646,9 +653,7 @@ fn test_nested_repeat_with_missing_condition_and_end_repeat() {
                             base: None,
                         },
                     },
-                    body: [
-                        EmptyStatement,
-                    ],
+                    body: [],
                 },
                 Assignment {
                     left: ReferenceExpr {
@@ -735,9 +740,7 @@ fn test_nested_repeat_with_missing_end_repeat() {
                             base: None,
                         },
                     },
-                    body: [
-                        EmptyStatement,
-                    ],
+                    body: [],
                 },
                 Assignment {
                     left: ReferenceExpr {
@@ -900,9 +903,7 @@ fn test_nested_while_with_missing_end_while() {
                             base: None,
                         },
                     },
-                    body: [
-                        EmptyStatement,
-                    ],
+                    body: [],
                 },
                 Assignment {
                     left: ReferenceExpr {
@@ -1131,6 +1132,8 @@ fn pointer_type_without_to_test() {
             auto_deref: None,
             type_safe: false,
             is_function: false,
+            kind: PointerKind::PointerTo,
+            nullability: None,
         },
         location: SourceLocation::internal(),
         initializer: None,
@@ -1160,6 +1163,8 @@ fn pointer_type_with_wrong_keyword_to_test() {
             auto_deref: None,
             type_safe: false,
             is_function: false,
+            kind: PointerKind::PointerTo,
+            nullability: None,
         },
         location: SourceLocation::internal(),
         initializer: None,
@@ -1178,3 +1183,99 @@ fn bitwise_access_error_validation() {
     let (_, diagnostics) = parse_buffered(src);
     assert_snapshot!(diagnostics);
 }
+
+/// parses the given loop followed by an assignment in a program and asserts that the loop's mismatched
+/// end keyword is reported once and the assignment following the loop is still parsed
+fn assert_mismatched_loop_end_recovers(src: &str, expected_message: &str, expected_loop: &str) {
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(
+        diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>(),
+        vec![expected_message],
+        "unexpected diagnostics: {diagnostics:#?}"
+    );
+    let statements = &unit.implementations[0].statements;
+    assert_eq!(statements.len(), 2);
+    assert!(format!("{:?}", statements[0]).starts_with(expected_loop));
+    assert!(format!("{:?}", statements[1]).starts_with("Assignment"));
+}
+
+#[test]
+fn for_loop_closed_with_end_while_is_reported_and_recovered() {
+    assert_mismatched_loop_end_recovers(
+        "PROGRAM My_PRG
+            FOR x := 1 TO 2 DO
+                y := x;
+            END_WHILE
+            y := x;
+        END_PROGRAM",
+        "Unexpected token: expected END_FOR but found END_WHILE",
+        "ForLoopStatement",
+    );
+}
+
+#[test]
+fn while_loop_closed_with_end_for_is_reported_and_recovered() {
+    assert_mismatched_loop_end_recovers(
+        "PROGRAM My_PRG
+            WHILE x = y DO
+                y := x;
+            END_FOR
+            y := x;
+        END_PROGRAM",
+        "Unexpected token: expected END_WHILE but found END_FOR",
+        "WhileLoopStatement",
+    );
+}
+
+#[test]
+fn repeat_loop_closed_with_end_while_is_reported_and_recovered() {
+    assert_mismatched_loop_end_recovers(
+        "PROGRAM My_PRG
+            REPEAT
+                y := x;
+            UNTIL x = y END_WHILE
+            y := x;
+        END_PROGRAM",
+        "Unexpected token: expected END_REPEAT but found END_WHILE",
+        "RepeatLoopStatement",
+    );
+}
+
+#[test]
+fn repeat_loop_without_until_closed_with_end_for_is_reported_and_recovered() {
+    assert_mismatched_loop_end_recovers(
+        "PROGRAM My_PRG
+            REPEAT
+                y := x;
+            END_FOR
+            y := x;
+        END_PROGRAM",
+        "Unexpected token: expected END_REPEAT but found END_FOR",
+        "RepeatLoopStatement",
+    );
+}
+
+#[test]
+fn nested_loop_missing_its_end_keyword_does_not_steal_the_enclosing_loop_end() {
+    let src = "PROGRAM My_PRG
+            WHILE x = y DO
+                FOR i := 1 TO 2 DO
+                    y := x;
+            END_WHILE
+            y := x;
+        END_PROGRAM";
+    let (unit, diagnostics) = parse(src);
+
+    // only the missing END_FOR is reported, the WHILE is closed by its own END_WHILE
+    assert!(!diagnostics.is_empty());
+    for diagnostic in &diagnostics {
+        assert_eq!(&src[diagnostic.get_location().to_range().unwrap()], "END_WHILE", "{diagnostic:#?}");
+        assert!(diagnostic.get_message().contains("KeywordEndFor"), "{diagnostic:#?}");
+    }
+
+    let statements = &unit.implementations[0].statements;
+    assert_eq!(statements.len(), 2);
+    assert!(format!("{:?}", statements[0]).starts_with("WhileLoopStatement"));
+    assert!(format!("{:?}", statements[1]).starts_with("Assignment"));
+}
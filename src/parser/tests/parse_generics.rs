@@ -1,5 +1,5 @@
 use insta::assert_debug_snapshot;
-use plc_ast::ast::{GenericBinding, TypeNature};
+use plc_ast::ast::{DataType, DataTypeDeclaration, GenericBinding, PointerKind, TypeNature};
 
 use crate::test_utils::tests::parse;
 
@@ -28,37 +28,52 @@ fn generic_markers_on_pou_added() {
     let generics = &function.generics;
     assert!(!generics.is_empty());
     let t = &generics[0];
-    assert_eq!(&GenericBinding { name: "A".into(), nature: TypeNature::Any }, t);
+    assert_eq!(&GenericBinding { name: "A".into(), nature: TypeNature::Any, constraint_types: vec![] }, t);
     let r = &generics[1];
-    assert_eq!(&GenericBinding { name: "B".into(), nature: TypeNature::Derived }, r);
+    assert_eq!(
+        &GenericBinding { name: "B".into(), nature: TypeNature::Derived, constraint_types: vec![] },
+        r
+    );
     let t = &generics[2];
-    assert_eq!(&GenericBinding { name: "C".into(), nature: TypeNature::Elementary }, t);
+    assert_eq!(
+        &GenericBinding { name: "C".into(), nature: TypeNature::Elementary, constraint_types: vec![] },
+        t
+    );
     let r = &generics[3];
-    assert_eq!(&GenericBinding { name: "D".into(), nature: TypeNature::Magnitude }, r);
+    assert_eq!(
+        &GenericBinding { name: "D".into(), nature: TypeNature::Magnitude, constraint_types: vec![] },
+        r
+    );
     let t = &generics[4];
-    assert_eq!(&GenericBinding { name: "E".into(), nature: TypeNature::Num }, t);
+    assert_eq!(&GenericBinding { name: "E".into(), nature: TypeNature::Num, constraint_types: vec![] }, t);
     let r = &generics[5];
-    assert_eq!(&GenericBinding { name: "F".into(), nature: TypeNature::Real }, r);
+    assert_eq!(&GenericBinding { name: "F".into(), nature: TypeNature::Real, constraint_types: vec![] }, r);
     let t = &generics[6];
-    assert_eq!(&GenericBinding { name: "G".into(), nature: TypeNature::Int }, t);
+    assert_eq!(&GenericBinding { name: "G".into(), nature: TypeNature::Int, constraint_types: vec![] }, t);
     let r = &generics[7];
-    assert_eq!(&GenericBinding { name: "H".into(), nature: TypeNature::Signed }, r);
+    assert_eq!(&GenericBinding { name: "H".into(), nature: TypeNature::Signed, constraint_types: vec![] }, r);
     let t = &generics[8];
-    assert_eq!(&GenericBinding { name: "I".into(), nature: TypeNature::Unsigned }, t);
+    assert_eq!(
+        &GenericBinding { name: "I".into(), nature: TypeNature::Unsigned, constraint_types: vec![] },
+        t
+    );
     let r = &generics[9];
-    assert_eq!(&GenericBinding { name: "J".into(), nature: TypeNature::Duration }, r);
+    assert_eq!(
+        &GenericBinding { name: "J".into(), nature: TypeNature::Duration, constraint_types: vec![] },
+        r
+    );
     let t = &generics[10];
-    assert_eq!(&GenericBinding { name: "K".into(), nature: TypeNature::Bit }, t);
+    assert_eq!(&GenericBinding { name: "K".into(), nature: TypeNature::Bit, constraint_types: vec![] }, t);
     let r = &generics[11];
-    assert_eq!(&GenericBinding { name: "L".into(), nature: TypeNature::Chars }, r);
+    assert_eq!(&GenericBinding { name: "L".into(), nature: TypeNature::Chars, constraint_types: vec![] }, r);
     let t = &generics[12];
-    assert_eq!(&GenericBinding { name: "M".into(), nature: TypeNature::String }, t);
+    assert_eq!(&GenericBinding { name: "M".into(), nature: TypeNature::String, constraint_types: vec![] }, t);
     let r = &generics[13];
-    assert_eq!(&GenericBinding { name: "N".into(), nature: TypeNature::Char }, r);
+    assert_eq!(&GenericBinding { name: "N".into(), nature: TypeNature::Char, constraint_types: vec![] }, r);
     let t = &generics[14];
-    assert_eq!(&GenericBinding { name: "O".into(), nature: TypeNature::Date }, t);
+    assert_eq!(&GenericBinding { name: "O".into(), nature: TypeNature::Date, constraint_types: vec![] }, t);
     let t = &generics[15];
-    assert_eq!(&GenericBinding { name: "P".into(), nature: TypeNature::__VLA }, t);
+    assert_eq!(&GenericBinding { name: "P".into(), nature: TypeNature::__VLA, constraint_types: vec![] }, t);
 }
 
 #[test]
@@ -70,9 +85,9 @@ fn generic_markers_on_method_added() {
     let generics = &function.generics;
     assert!(!generics.is_empty());
     let t = &generics[0];
-    assert_eq!(&GenericBinding { name: "T".into(), nature: TypeNature::Any }, t);
+    assert_eq!(&GenericBinding { name: "T".into(), nature: TypeNature::Any, constraint_types: vec![] }, t);
     let r = &generics[1];
-    assert_eq!(&GenericBinding { name: "R".into(), nature: TypeNature::Num }, r);
+    assert_eq!(&GenericBinding { name: "R".into(), nature: TypeNature::Num, constraint_types: vec![] }, r);
 }
 
 #[test]
@@ -93,3 +108,112 @@ fn generic_method_parameters_are_datatypes() {
     let variables = &function.variable_blocks[0].variables;
     assert_debug_snapshot!(variables);
 }
+
+#[test]
+fn ref_to_generic_parameter_is_parsed() {
+    let src = "FUNCTION test<T: ANY> : INT VAR ptr : REF_TO T; END_VAR END_FUNCTION";
+    let (parse_result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let function = &parse_result.pous[0];
+    let data_type = &function.variable_blocks[0].variables[0].data_type_declaration;
+    let DataTypeDeclaration::Definition { data_type, .. } = data_type else {
+        panic!("expected an inline pointer definition, got {data_type:#?}");
+    };
+    let DataType::PointerType { referenced_type, kind, .. } = data_type.as_ref() else {
+        panic!("expected a pointer type, got {data_type:#?}");
+    };
+    assert_eq!(kind, &PointerKind::RefTo);
+
+    // the referenced type is just the name of the generic parameter
+    let referenced_type = referenced_type.get_name().unwrap();
+    assert_eq!(referenced_type, "T");
+    assert!(function.generics.iter().any(|it| it.name == referenced_type));
+}
+
+#[test]
+fn generic_constraints_with_type_lists_are_parsed() {
+    let src = "FUNCTION test<A : ANY_NUM, B : {INT, STRING}, C : ANY_INT {SINT, USINT}> : INT END_FUNCTION";
+    let (parse_result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let generics = &parse_result.pous[0].generics;
+    // only a nature
+    assert_eq!(
+        generics[0],
+        GenericBinding { name: "A".into(), nature: TypeNature::Num, constraint_types: vec![] }
+    );
+    // only a type list, which does not restrict the nature
+    assert_eq!(
+        generics[1],
+        GenericBinding {
+            name: "B".into(),
+            nature: TypeNature::Any,
+            constraint_types: vec!["INT".into(), "STRING".into()]
+        }
+    );
+    // a nature and a type list
+    assert_eq!(
+        generics[2],
+        GenericBinding {
+            name: "C".into(),
+            nature: TypeNature::Int,
+            constraint_types: vec!["SINT".into(), "USINT".into()]
+        }
+    );
+}
+
+#[test]
+fn invalid_type_names_in_generic_constraints_are_reported() {
+    let src = "FUNCTION test<T : {INT, 2D, DINT}, R : ANY> : INT END_FUNCTION";
+    let (parse_result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E144");
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "`2D` is not a valid type name in the constraint of generic `T`"
+    );
+
+    // parsing continues with the valid types and the following generics
+    let generics = &parse_result.pous[0].generics;
+    assert_eq!(generics[0].constraint_types, vec!["INT", "DINT"]);
+    assert_eq!(generics[1].name, "R");
+}
+
+#[test]
+fn user_types_in_generic_constraints_are_accepted() {
+    let src = "FUNCTION test<T : {MyStruct, lib.MyEnum}> : INT END_FUNCTION";
+    let (parse_result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let generics = &parse_result.pous[0].generics;
+    assert_eq!(generics[0].nature, TypeNature::Any);
+    assert_eq!(generics[0].constraint_types, vec!["MyStruct", "lib.MyEnum"]);
+}
+
+#[test]
+fn generic_constrained_only_by_invalid_types_still_falls_back_to_any() {
+    let src = "FUNCTION test<T : {2D}> : INT END_FUNCTION";
+    let (parse_result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E144");
+    let generics = &parse_result.pous[0].generics;
+    assert_eq!(generics[0].name, "T");
+    assert_eq!(generics[0].nature, TypeNature::Any);
+    assert!(generics[0].constraint_types.is_empty());
+}
+
+#[test]
+fn type_lists_in_comments_do_not_constrain_generics() {
+    let src = "FUNCTION test<T : (* {INT, DINT} *) ANY_INT, R : ANY_NUM (* {REAL} *)> : INT END_FUNCTION";
+    let (parse_result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let generics = &parse_result.pous[0].generics;
+    assert_eq!(generics[0].nature, TypeNature::Int);
+    assert!(generics[0].constraint_types.is_empty());
+    assert_eq!(generics[1].nature, TypeNature::Num);
+    assert!(generics[1].constraint_types.is_empty());
+}
@@ -1,6 +1,9 @@
 use plc_ast::ast::{LinkageType, PouType};
 
-use crate::test_utils::tests::parse;
+use crate::{
+    parser::{ParseOptions, FORMULA_NAME},
+    test_utils::tests::{parse, parse_with_options},
+};
 
 #[test]
 fn simple_foo_program_can_be_parsed() {
@@ -136,3 +139,127 @@ fn simple_program_with_var_temp_can_be_parsed() {
 }"#;
     assert_eq!(ast_string, expected_ast);
 }
+
+#[test]
+fn program_methods_are_accepted_by_default() {
+    let src = "PROGRAM prg METHOD foo END_METHOD END_PROGRAM";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(unit.pous.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(), vec!["prg", "prg.foo"]);
+    assert_eq!(unit.implementations.len(), 2);
+}
+
+#[test]
+fn program_methods_are_reported_and_skipped_if_not_allowed() {
+    let src = "
+        PROGRAM prg
+            METHOD foo END_METHOD
+            x := 1;
+        END_PROGRAM
+        FUNCTION_BLOCK fb
+            METHOD bar END_METHOD
+        END_FUNCTION_BLOCK
+        ";
+    let (unit, diagnostics) =
+        parse_with_options(src, ParseOptions { allow_program_methods: false, ..ParseOptions::default() });
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].get_message(), "Methods are not allowed in a PROGRAM");
+    assert_eq!(diagnostics[0].get_error_code(), "E131");
+    assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "METHOD");
+
+    // the program's body and the methods of function blocks are unaffected
+    assert_eq!(unit.pous.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(), vec!["prg", "fb", "fb.bar"]);
+    assert_eq!(unit.implementations.iter().find(|it| it.name == "prg").unwrap().statements.len(), 1);
+}
+
+#[test]
+fn formula_expressions_are_wrapped_into_an_implicit_program() {
+    let src = "a + 1; foo(b, 2);; c > 3 AND d;";
+    let (unit, diagnostics) =
+        parse_with_options(src, ParseOptions { formula: true, ..ParseOptions::default() });
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(unit.pous.len(), 1);
+    assert_eq!(unit.pous[0].name, FORMULA_NAME);
+    assert_eq!(unit.pous[0].kind, PouType::Program);
+    assert!(unit.pous[0].variable_blocks.is_empty());
+
+    assert_eq!(unit.implementations.len(), 1);
+    let implementation = &unit.implementations[0];
+    assert_eq!(implementation.name, FORMULA_NAME);
+    assert_eq!(
+        implementation
+            .statements
+            .iter()
+            .map(|it| &src[it.get_location().to_range().unwrap()])
+            .collect::<Vec<_>>(),
+        vec!["a + 1", "foo(b, 2)", "c > 3 AND d"]
+    );
+}
+
+#[test]
+fn declarations_in_a_formula_are_reported() {
+    let src = "
+        a + 1;
+        VAR x : INT; END_VAR
+        b;
+        FUNCTION foo : INT END_FUNCTION
+        ";
+    let (unit, diagnostics) =
+        parse_with_options(src, ParseOptions { formula: true, ..ParseOptions::default() });
+
+    assert_eq!(diagnostics.len(), 2);
+    for (diagnostic, keyword) in diagnostics.iter().zip(["VAR", "FUNCTION"]) {
+        assert_eq!(diagnostic.get_message(), "Declarations are not allowed in a formula");
+        assert_eq!(diagnostic.get_error_code(), "E133");
+        assert_eq!(&src[diagnostic.get_location().to_range().unwrap()], keyword);
+    }
+
+    // the expressions around the declarations are still part of the formula
+    let formula = unit.implementations.iter().find(|it| it.name == FORMULA_NAME).unwrap();
+    assert_eq!(formula.statements.len(), 2);
+}
+
+#[test]
+fn bare_expressions_are_rejected_without_the_formula_option() {
+    let (unit, diagnostics) = parse("a + 1;");
+
+    assert!(!diagnostics.is_empty());
+    assert!(unit.implementations.is_empty());
+}
+
+#[test]
+fn program_with_a_task_association_can_be_parsed() {
+    let src = "
+        PROGRAM p WITH fast_task : main;
+        VAR
+            x : INT;
+        END_VAR
+            x := 1;
+        END_PROGRAM
+
+        PROGRAM q with slow_task
+        END_PROGRAM
+
+        PROGRAM r
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+    assert!(diagnostics.is_empty(), "{diagnostics:#?}");
+
+    let task = result.pous[0].task.as_ref().unwrap();
+    assert_eq!(task.task.name, "fast_task");
+    assert_eq!(task.program_type.as_ref().map(|it| it.name.as_str()), Some("main"));
+    assert_eq!(&src[task.location.to_range().unwrap()], "WITH fast_task : main");
+    // the rest of the program is parsed as usual
+    assert_eq!(result.pous[0].variable_blocks[0].variables[0].name, "x");
+    assert_eq!(result.implementations[0].statements.len(), 1);
+
+    let task = result.pous[1].task.as_ref().unwrap();
+    assert_eq!(task.task.name, "slow_task");
+    assert_eq!(task.program_type, None);
+
+    assert_eq!(result.pous[2].task, None);
+}
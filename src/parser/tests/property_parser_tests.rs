@@ -1,3 +1,5 @@
+use plc_ast::ast::PropertyKind;
+
 use crate::test_utils::tests::{parse, parse_buffered};
 
 #[test]
@@ -128,6 +130,7 @@ fn properties_can_be_parsed() {
                     },
                 },
             ],
+            overriding: false,
         },
     ]
     "###);
@@ -202,3 +205,68 @@ fn property_with_variable_block() {
       │                 ^^^ Variable blocks may only be defined within a GET or SET block in the context of properties
     ");
 }
+
+#[test]
+fn property_and_pou_implementations_report_whether_they_are_empty() {
+    let source = r"
+        FUNCTION_BLOCK foo
+            PROPERTY bar : INT
+                GET
+                    bar := 5;
+                END_GET
+                SET
+                END_SET
+            END_PROPERTY
+        END_FUNCTION_BLOCK
+
+        FUNCTION baz : INT
+        END_FUNCTION
+    ";
+
+    let (unit, diagnostics) = parse(source);
+    assert_eq!(diagnostics, vec![]);
+
+    let property = &unit.pous[0].properties[0];
+    assert!(!property.implementations[0].is_empty());
+    assert_eq!(property.implementations[0].statements().len(), 1);
+    assert!(property.implementations[1].is_empty());
+
+    let baz = unit.implementations.iter().find(|it| it.name == "baz").unwrap();
+    assert!(baz.is_empty());
+    assert!(baz.statements().is_empty());
+}
+
+#[test]
+fn overriding_properties_can_be_parsed() {
+    let source = r"
+        FUNCTION_BLOCK child EXTENDS parent
+            PROPERTY OVERRIDE bar : INT
+                GET
+                    bar := 5;
+                END_GET
+                SET
+                    localVariable := bar;
+                END_SET
+            END_PROPERTY
+            PROPERTY baz : INT
+                GET END_GET
+            END_PROPERTY
+        END_FUNCTION_BLOCK
+    ";
+
+    let (unit, diagnostics) = parse(source);
+    assert_eq!(diagnostics, vec![]);
+
+    let properties = &unit.pous[0].properties;
+    assert_eq!(properties.len(), 2);
+    assert_eq!(properties[0].ident.name, "bar");
+    assert!(properties[0].overriding);
+    assert_eq!(
+        properties[0].implementations.iter().map(|it| it.kind).collect::<Vec<_>>(),
+        vec![PropertyKind::Get, PropertyKind::Set]
+    );
+    assert_eq!(properties[0].implementations[1].statements().len(), 1);
+
+    assert_eq!(properties[1].ident.name, "baz");
+    assert!(!properties[1].overriding);
+}
@@ -1,5 +1,5 @@
 use crate::{
-    parser::tests::{empty_stmt, ref_to},
+    parser::{assignments_to, tests::ref_to},
     test_utils::tests::parse,
     typesystem::DINT_TYPE,
 };
@@ -9,20 +9,16 @@ use plc_source::source_location::SourceLocation;
 use pretty_assertions::*;
 
 #[test]
-fn empty_statements_are_are_parsed() {
+fn empty_statements_are_not_added_to_the_body() {
     let src = "PROGRAM buz ;;;; END_PROGRAM ";
-    let result = parse(src).0;
-
-    let prg = &result.implementations[0];
+    let (result, diagnostics) = parse(src);
 
-    assert_eq!(
-        format!("{:?}", prg.statements),
-        format!("{:?}", vec![empty_stmt(), empty_stmt(), empty_stmt(), empty_stmt(),]),
-    );
+    assert_eq!(diagnostics, vec![]);
+    assert!(result.implementations[0].statements.is_empty());
 }
 
 #[test]
-fn empty_statements_are_parsed_before_a_statement() {
+fn empty_statements_are_ignored_before_a_statement() {
     let src = "PROGRAM buz ;;;;x; END_PROGRAM ";
     let result = parse(src).0;
 
@@ -32,21 +28,31 @@ fn empty_statements_are_parsed_before_a_statement() {
         format!("{:?}", prg.statements),
         format!(
             "{:?}",
-            vec![
-                empty_stmt(),
-                empty_stmt(),
-                empty_stmt(),
-                empty_stmt(),
-                AstFactory::create_member_reference(
-                    AstFactory::create_identifier("x", SourceLocation::internal(), 0),
-                    None,
-                    0
-                ),
-            ]
+            vec![AstFactory::create_member_reference(
+                AstFactory::create_identifier("x", SourceLocation::internal(), 0),
+                None,
+                0
+            ),]
         ),
     );
 }
 
+#[test]
+fn redundant_semicolons_between_statements_are_ignored() {
+    for semicolons in [";", ";;", ";;;"] {
+        let src = format!("PROGRAM buz x := 1{semicolons} y := 2{semicolons} END_PROGRAM");
+        let (result, diagnostics) = parse(&src);
+
+        assert_eq!(diagnostics, vec![], "{src}");
+        let statements = &result.implementations[0].statements;
+        assert_eq!(
+            statements.iter().map(|it| &src[it.get_location().to_range().unwrap()]).collect::<Vec<_>>(),
+            vec!["x := 1", "y := 2"],
+            "{src}"
+        );
+    }
+}
+
 #[test]
 fn empty_statements_are_ignored_after_a_statement() {
     let src = "PROGRAM buz x;;;; END_PROGRAM ";
@@ -143,6 +149,11 @@ fn inline_enum_declaration_can_be_parsed() {
         },
         initializer: None,
         address: None,
+        retain: None,
+        attributes: vec![],
+        explicit_en_eno: false,
+        optional: false,
+        absolute_address: None,
         location: SourceLocation::internal(),
     };
     let expected_ast = format!("{:#?}", &v);
@@ -201,6 +212,7 @@ fn string_variable_declaration_can_be_parsed() {
                     value: 500,
                 },
             ),
+            encoding: None,
         },
     },
 }"#);
@@ -227,6 +239,7 @@ fn string_variable_declaration_can_be_parsed() {
                     value: 500,
                 },
             ),
+            encoding: None,
         },
     },
 }"#);
@@ -327,6 +340,8 @@ fn reference_to_dint_declaration() {
                         ),
                         type_safe: true,
                         is_function: false,
+                        kind: ReferenceTo,
+                        nullability: None,
                     },
                 },
             },
@@ -371,6 +386,8 @@ fn reference_to_string_declaration() {
                         ),
                         type_safe: true,
                         is_function: false,
+                        kind: ReferenceTo,
+                        nullability: None,
                     },
                 },
             },
@@ -409,6 +426,8 @@ fn aliasing_dint_variable() {
                         ),
                         type_safe: true,
                         is_function: false,
+                        kind: Alias,
+                        nullability: None,
                     },
                 },
                 initializer: Some(
@@ -427,3 +446,28 @@ fn aliasing_dint_variable() {
     }
     "#);
 }
+
+#[test]
+fn assignments_to_a_variable_can_be_found() {
+    let src = "
+    PROGRAM prg
+        x := 1;
+        y := x;
+        IF y > 0 THEN
+            X.a := 2;
+        END_IF
+        x[3] := 4;
+        foo(x := 5, a := y);
+        x REF= y;
+        xy := 7;
+    END_PROGRAM
+    ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let assignments = assignments_to(&result.implementations[0], "x")
+        .iter()
+        .map(|it| &src[it.to_range().unwrap()])
+        .collect::<Vec<_>>();
+    assert_eq!(assignments, vec!["x := 1", "X.a := 2", "x[3] := 4", "x REF= y"]);
+}
@@ -1,6 +1,12 @@
-use crate::test_utils::tests::{parse, parse_buffered};
+use crate::{
+    parser::{type_declarations, MemberInfo, TypeInfo, TypeKind},
+    test_utils::tests::{parse, parse_buffered},
+};
 use insta::{assert_debug_snapshot, assert_snapshot};
-use plc_ast::ast::{DataType, DataTypeDeclaration, UserTypeDeclaration, Variable};
+use plc_ast::ast::{
+    flatten_expression_list, AstNode, AstStatement, DataType, DataTypeDeclaration, Operator, PointerKind,
+    RangeStatement, UnaryExpression, UserTypeDeclaration, Variable,
+};
 use plc_source::source_location::SourceLocation;
 use pretty_assertions::*;
 
@@ -51,6 +57,11 @@ fn simple_struct_type_can_be_parsed() {
                         },
                         initializer: None,
                         address: None,
+                        retain: None,
+                        attributes: vec![],
+                        explicit_en_eno: false,
+                        optional: false,
+                        absolute_address: None,
                         location: SourceLocation::internal(),
                     },
                     Variable {
@@ -61,6 +72,11 @@ fn simple_struct_type_can_be_parsed() {
                         },
                         initializer: None,
                         address: None,
+                        retain: None,
+                        attributes: vec![],
+                        explicit_en_eno: false,
+                        optional: false,
+                        absolute_address: None,
                         location: SourceLocation::internal(),
                     },
                     Variable {
@@ -71,6 +87,11 @@ fn simple_struct_type_can_be_parsed() {
                         },
                         initializer: None,
                         address: None,
+                        retain: None,
+                        attributes: vec![],
+                        explicit_en_eno: false,
+                        optional: false,
+                        absolute_address: None,
                         location: SourceLocation::internal(),
                     },
                 ),
@@ -846,3 +867,438 @@ fn enum_with_no_elements_produces_syntax_error() {
     }
     "#);
 }
+
+#[test]
+fn struct_members_can_be_declared_retain_or_non_retain() {
+    let (result, diagnostics) = parse(
+        r#"
+        TYPE MyStruct : STRUCT
+            a : INT NON_RETAIN;
+            b : BOOL RETAIN;
+            c : DINT;
+        END_STRUCT
+        END_TYPE
+        "#,
+    );
+
+    assert_eq!(diagnostics, vec![]);
+    let DataType::StructType { variables, .. } = &result.user_types[0].data_type else {
+        panic!("expected a struct type, got {:#?}", result.user_types[0].data_type);
+    };
+    assert_eq!(variables.iter().map(|it| it.retain).collect::<Vec<_>>(), vec![Some(false), Some(true), None]);
+}
+
+#[test]
+fn struct_member_with_retain_and_non_retain_is_reported() {
+    let (result, diagnostics) = parse(
+        r#"
+        TYPE MyStruct : STRUCT
+            a : INT RETAIN NON_RETAIN;
+        END_STRUCT
+        END_TYPE
+        "#,
+    );
+
+    assert_eq!(diagnostics.len(), 1, "expected exactly one diagnostic, got {diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E124");
+    let DataType::StructType { variables, .. } = &result.user_types[0].data_type else {
+        panic!("expected a struct type, got {:#?}", result.user_types[0].data_type);
+    };
+    assert_eq!(variables[0].retain, Some(true));
+}
+
+#[test]
+fn array_bounds_with_round_parentheses_are_reported_and_parsed() {
+    for src in [
+        "TYPE MyArray : ARRAY (0..5) OF INT; END_TYPE",
+        "TYPE MyArray : ARRAY [0..5) OF INT; END_TYPE",
+        "TYPE MyArray : ARRAY (0..5] OF INT; END_TYPE",
+    ] {
+        let (result, diagnostics) = parse(src);
+
+        assert_eq!(
+            diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>(),
+            vec!["Array bounds must use square brackets '[]'"],
+            "unexpected diagnostics for {src}"
+        );
+        let DataType::ArrayType { bounds, referenced_type, is_variable_length, .. } =
+            &result.user_types[0].data_type
+        else {
+            panic!("expected an array type for {src}, got {:#?}", result.user_types[0].data_type);
+        };
+        assert_eq!(
+            format!("{bounds:?}"),
+            "RangeStatement { start: LiteralInteger { value: 0 }, end: LiteralInteger { value: 5 } }"
+        );
+        assert_eq!(referenced_type.get_name(), Some("INT"));
+        assert!(!is_variable_length);
+    }
+}
+
+#[test]
+fn array_bounds_with_negative_and_constant_expressions_are_fixed_length_ranges() {
+    // renders a bound as a literal value, a constant name or a negated constant name
+    fn bound(node: &AstNode) -> String {
+        match node.get_stmt() {
+            AstStatement::UnaryExpression(UnaryExpression { operator: Operator::Minus, value }) => {
+                format!("-{}", bound(value))
+            }
+            _ => node
+                .get_literal_integer_value()
+                .map(|it| it.to_string())
+                .or_else(|| node.get_flat_reference_name().map(str::to_string))
+                .unwrap_or_else(|| panic!("unexpected bound {node:?}")),
+        }
+    }
+
+    for (bounds_src, expected) in
+        [("-5..5", ("-5", "5")), ("0..N", ("0", "N")), ("N..M", ("N", "M")), ("-N..N", ("-N", "N"))]
+    {
+        let src = format!(
+            "
+            VAR_GLOBAL CONSTANT N : DINT := 5; M : DINT := 10; END_VAR
+            TYPE MyArray : ARRAY[{bounds_src}] OF INT; END_TYPE
+            "
+        );
+        let (result, diagnostics) = parse(&src);
+
+        assert_eq!(diagnostics, vec![], "unexpected diagnostics for {bounds_src}");
+        let DataType::ArrayType { bounds, is_variable_length, .. } = &result.user_types[0].data_type else {
+            panic!("expected an array type for {bounds_src}, got {:#?}", result.user_types[0].data_type);
+        };
+        let AstStatement::RangeStatement(RangeStatement { start, end }) = bounds.get_stmt() else {
+            panic!("expected a range for {bounds_src}, got {bounds:#?}");
+        };
+        assert_eq!((bound(start).as_str(), bound(end).as_str()), expected);
+        assert!(!is_variable_length, "{bounds_src} is not a variable length array");
+    }
+}
+
+#[test]
+fn stray_semicolons_in_a_type_block_are_tolerated() {
+    let src = "
+        TYPE
+            ;
+            MyInt : INT;
+            ;;
+            MyStruct : STRUCT x : INT; END_STRUCT;
+            ;
+        END_TYPE
+        TYPE MyReal : REAL; ; END_TYPE
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(
+        result.user_types.iter().map(|it| it.data_type.get_name().unwrap()).collect::<Vec<_>>(),
+        vec!["MyInt", "MyStruct", "MyReal"]
+    );
+}
+
+#[test]
+fn type_declarations_lists_structs_and_enums_with_their_members() {
+    let (result, diagnostics) = parse(
+        r#"
+        TYPE Point : STRUCT
+            x : INT;
+            y : REAL;
+        END_STRUCT
+        END_TYPE
+
+        TYPE Color : (red, green := 2, blue);
+        END_TYPE
+        "#,
+    );
+    assert_eq!(diagnostics, vec![]);
+
+    let member = |name: &str, type_name: Option<&str>| MemberInfo {
+        name: name.to_string(),
+        type_name: type_name.map(str::to_string),
+    };
+    assert_eq!(
+        type_declarations(&result),
+        vec![
+            TypeInfo {
+                name: "Point".to_string(),
+                kind: TypeKind::Struct,
+                members: vec![member("x", Some("INT")), member("y", Some("REAL"))],
+                referenced_type: None,
+            },
+            TypeInfo {
+                name: "Color".to_string(),
+                kind: TypeKind::Enum,
+                members: vec![member("red", None), member("green", None), member("blue", None)],
+                referenced_type: Some("DINT".to_string()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn enum_numeric_type_is_taken_from_either_side_of_the_element_list() {
+    let (result, diagnostics) = parse(
+        r#"
+        TYPE Color : BYTE (Red, Green, Blue); END_TYPE
+        TYPE Shape : (Circle, Square) BYTE; END_TYPE
+        TYPE Level : DINT (Low := -100000, High := 100000); END_TYPE
+        TYPE Mode : (Auto, Manual); END_TYPE
+        "#,
+    );
+    assert_eq!(diagnostics, vec![]);
+
+    let numeric_types = result
+        .user_types
+        .iter()
+        .map(|it| match &it.data_type {
+            DataType::EnumType { numeric_type, .. } => numeric_type.as_str(),
+            _ => unreachable!("expected an enum, got {:?}", it.data_type),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(numeric_types, vec!["BYTE", "BYTE", "DINT", "DINT"]);
+}
+
+#[test]
+fn enum_element_values_overflowing_the_numeric_type_are_reported() {
+    let (_, diagnostics) = parse(
+        r#"
+        TYPE Color : BYTE (Red := 300, Green := 255, Blue := -1); END_TYPE
+        TYPE Shape : (Circle := 3000000000) DINT; END_TYPE
+        TYPE Single : BYTE (Only := 256); END_TYPE
+        TYPE Fits : DINT (Low := -2147483648, High := 2147483647); END_TYPE
+        "#,
+    );
+
+    let messages = diagnostics.iter().map(|it| (it.get_error_code(), it.get_message())).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            ("E039", "This will overflow for type BYTE"),
+            ("E039", "This will overflow for type BYTE"),
+            ("E039", "This will overflow for type DINT"),
+            ("E039", "This will overflow for type BYTE"),
+        ]
+    );
+}
+
+#[test]
+fn comma_separated_type_names_share_one_definition() {
+    let (result, diagnostics) = parse(
+        r#"
+        TYPE
+            Temp1, Temp2, Temp3 : INT (0..100) := 20;
+        END_TYPE
+        "#,
+    );
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(result.user_types.len(), 3);
+
+    for (user_type, expected_name) in result.user_types.iter().zip(["Temp1", "Temp2", "Temp3"]) {
+        let DataType::SubRangeType { name, referenced_type, bounds } = &user_type.data_type else {
+            panic!("expected a subrange type, got {:?}", user_type.data_type);
+        };
+        assert_eq!(name.as_deref(), Some(expected_name));
+        assert_eq!(referenced_type, "INT");
+        assert!(bounds.is_some());
+        assert!(user_type.initializer.is_some());
+    }
+
+    let locations = result.user_types.iter().map(|it| it.location.to_range().unwrap()).collect::<Vec<_>>();
+    assert_eq!(locations, vec![26..31, 33..38, 40..45]);
+}
+
+#[test]
+fn missing_type_name_after_a_comma_is_reported() {
+    let (result, diagnostics) = parse(
+        r#"
+        TYPE
+            Temp1, : INT (0..100);
+        END_TYPE
+        "#,
+    );
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E007");
+    assert_eq!(diagnostics[0].get_message(), "Unexpected token: expected Identifier but found :");
+
+    // only the named type is declared
+    let names = result.user_types.iter().map(|it| it.data_type.get_name()).collect::<Vec<_>>();
+    assert_eq!(names, vec![Some("Temp1")]);
+}
+
+#[test]
+fn string_types_with_size_and_encoding_can_be_parsed() {
+    let (result, diagnostics) = parse(
+        r#"
+        TYPE MyString : STRING[10]; END_TYPE
+        TYPE MyWideString : WSTRING[5]; END_TYPE
+        TYPE MyEncodedString : STRING[10](CP1252) := 'abc'; END_TYPE
+        TYPE MyUnknownEncoding : WSTRING[20] (SOME_ENCODING); END_TYPE
+        "#,
+    );
+    assert_eq!(diagnostics, vec![]);
+
+    let strings = result
+        .user_types
+        .iter()
+        .map(|it| match &it.data_type {
+            DataType::StringType { name, is_wide, encoding, .. } => {
+                (name.as_deref().unwrap(), *is_wide, encoding.as_deref())
+            }
+            _ => unreachable!("expected a string type, got {:?}", it.data_type),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        strings,
+        vec![
+            ("MyString", false, None),
+            ("MyWideString", true, None),
+            ("MyEncodedString", false, Some("CP1252")),
+            ("MyUnknownEncoding", true, Some("SOME_ENCODING")),
+        ]
+    );
+    assert!(result.user_types[2].initializer.is_some());
+}
+
+#[test]
+fn nested_pointer_location_excludes_the_initializer() {
+    let src = r#"
+        PROGRAM prg
+        VAR
+            x : REF_TO REF_TO INT := REF(y);
+        END_VAR
+        END_PROGRAM
+        "#;
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let variable = &result.pous[0].variable_blocks[0].variables[0];
+    assert!(variable.initializer.is_some());
+    let DataTypeDeclaration::Definition { data_type, location, .. } = &variable.data_type_declaration else {
+        panic!("expected a pointer definition, got {:?}", variable.data_type_declaration);
+    };
+    assert_eq!(&src[location.to_range().unwrap()], "REF_TO REF_TO INT");
+
+    let DataType::PointerType { referenced_type, .. } = data_type.as_ref() else {
+        panic!("expected a pointer type, got {data_type:?}");
+    };
+    assert_eq!(&src[referenced_type.get_location().to_range().unwrap()], "REF_TO INT");
+}
+
+#[test]
+fn array_without_bounds_is_parsed_as_variable_length_array() {
+    let src = r#"
+        FUNCTION foo : INT
+        VAR_IN_OUT
+            arr : ARRAY OF INT;
+        END_VAR
+        END_FUNCTION
+        "#;
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "Missing array bounds, use `ARRAY[*] OF` to declare a variable length array"
+    );
+    assert_eq!(diagnostics[0].get_error_code(), "E134");
+    assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "ARRAY OF");
+
+    let variable = &result.pous[0].variable_blocks[0].variables[0];
+    let DataTypeDeclaration::Definition { data_type, location, .. } = &variable.data_type_declaration else {
+        panic!("expected an array definition, got {:?}", variable.data_type_declaration);
+    };
+    assert_eq!(&src[location.to_range().unwrap()], "ARRAY OF INT");
+
+    let DataType::ArrayType { bounds, referenced_type, is_variable_length, .. } = data_type.as_ref() else {
+        panic!("expected an array type, got {data_type:?}");
+    };
+    assert!(is_variable_length);
+    assert_eq!(bounds.get_stmt(), &AstStatement::VlaRangeStatement);
+    assert_eq!(referenced_type.get_name(), Some("INT"));
+}
+
+#[test]
+fn pointer_types_record_the_keyword_they_were_declared_with() {
+    let src = r#"
+        FUNCTION_BLOCK fb END_FUNCTION_BLOCK
+        PROGRAM prg
+        VAR
+            p : POINTER TO INT;
+            r : REF_TO INT;
+            rt : REFTO INT;
+            rf : REFERENCE TO INT;
+            al AT p : INT;
+            addr AT %IX1.0 : BOOL;
+            fp : __FPOINTER fb;
+        END_VAR
+        END_PROGRAM
+        "#;
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let kinds = result.pous[1].variable_blocks[0]
+        .variables
+        .iter()
+        .map(|it| match &it.data_type_declaration {
+            DataTypeDeclaration::Definition { data_type, .. } => match data_type.as_ref() {
+                DataType::PointerType { kind, .. } => (it.get_name(), *kind),
+                _ => panic!("expected a pointer type, got {data_type:?}"),
+            },
+            _ => panic!("expected a pointer definition, got {:?}", it.data_type_declaration),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            ("p", PointerKind::PointerTo),
+            ("r", PointerKind::RefTo),
+            ("rt", PointerKind::RefTo),
+            ("rf", PointerKind::ReferenceTo),
+            ("al", PointerKind::Alias),
+            ("addr", PointerKind::Alias),
+            ("fp", PointerKind::Function),
+        ]
+    );
+}
+
+#[test]
+fn array_of_arrays_is_nested_while_multi_dimensional_arrays_are_not() {
+    let src = "
+        VAR_GLOBAL
+            nested : ARRAY[0..2] OF ARRAY[0..3] OF INT;
+            multi : ARRAY[0..2, 0..3] OF INT;
+        END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let text = |declaration: &DataTypeDeclaration| &src[declaration.get_location().to_range().unwrap()];
+    let array = |declaration: &DataTypeDeclaration| match declaration {
+        DataTypeDeclaration::Definition { data_type, .. } => match data_type.as_ref() {
+            DataType::ArrayType { bounds, referenced_type, .. } => {
+                (flatten_expression_list(bounds).len(), referenced_type.as_ref().clone())
+            }
+            other => panic!("expected an array, got {other:#?}"),
+        },
+        other => panic!("expected a definition, got {other:#?}"),
+    };
+    let variables = &result.global_vars[0].variables;
+
+    // an array of arrays holds the inner array as its element type
+    let nested = &variables[0].data_type_declaration;
+    assert_eq!(text(nested), "ARRAY[0..2] OF ARRAY[0..3] OF INT");
+    let (dimensions, element) = array(nested);
+    assert_eq!(dimensions, 1);
+    assert_eq!(text(&element), "ARRAY[0..3] OF INT");
+    let (dimensions, inner_element) = array(&element);
+    assert_eq!(dimensions, 1);
+    assert_eq!(inner_element.get_name(), Some("INT"));
+    assert_eq!(text(&inner_element), "INT");
+
+    // a multi-dimensional array is a single array with two dimensions
+    let multi = &variables[1].data_type_declaration;
+    assert_eq!(text(multi), "ARRAY[0..2, 0..3] OF INT");
+    let (dimensions, element) = array(multi);
+    assert_eq!(dimensions, 2);
+    assert_eq!(element.get_name(), Some("INT"));
+}
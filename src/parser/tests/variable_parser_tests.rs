@@ -1,4 +1,11 @@
-use plc_ast::ast::{LinkageType, VariableBlock};
+use plc_ast::{
+    ast::{
+        flatten_expression_list, AccessDirection, AstStatement, Attribute, DataType, DataTypeDeclaration,
+        DirectAccessType, HardwareAccess, HardwareAccessType, LinkageType, Nullability, PointerKind,
+        ReferenceAccess, ReferenceExpr, Variable, VariableBlock,
+    },
+    literals::{AstLiteral, StringValue},
+};
 
 use crate::test_utils::tests::parse;
 
@@ -385,6 +392,92 @@ fn var_config_location() {
     assert_eq!("main.instance.foo", &src[result.var_config[0].location.to_range().unwrap()]);
 }
 
+#[test]
+fn var_config_with_indexed_template_instance() {
+    let src = r#"
+    VAR_CONFIG
+        main.instances[0].foo AT %QX0.3 : BOOL;
+    END_VAR
+    "#;
+
+    let (result, diagnostics) = parse(src);
+
+    assert!(diagnostics.is_empty());
+    let config = &result.var_config[0];
+    assert_eq!("main.instances[0].foo", &src[config.location.to_range().unwrap()]);
+
+    // `foo` is accessed on the element `[0]` of `main.instances`
+    let AstStatement::ReferenceExpr(ReferenceExpr {
+        access: ReferenceAccess::Member(member),
+        base: Some(base),
+    }) = config.reference.get_stmt()
+    else {
+        panic!("expected a member reference, got {:#?}", config.reference);
+    };
+    assert_eq!(member.get_flat_reference_name(), Some("foo"));
+    let AstStatement::ReferenceExpr(ReferenceExpr {
+        access: ReferenceAccess::Index(index),
+        base: Some(base),
+    }) = base.get_stmt()
+    else {
+        panic!("expected an index access, got {base:#?}");
+    };
+    assert_eq!(index.get_literal_integer_value(), Some(0));
+    assert_eq!(base.get_flat_reference_name(), Some("instances"));
+
+    let AstStatement::HardwareAccess(HardwareAccess { direction, access, address }) =
+        config.address.get_stmt()
+    else {
+        panic!("expected a hardware access, got {:#?}", config.address);
+    };
+    assert_eq!(direction, &HardwareAccessType::Output);
+    assert_eq!(access, &DirectAccessType::Bit);
+    assert_eq!(
+        address.iter().map(|it| it.get_literal_integer_value()).collect::<Vec<_>>(),
+        vec![Some(0), Some(3)]
+    );
+}
+
+#[test]
+fn var_config_with_nested_indexed_targets() {
+    let src = r#"
+    VAR_CONFIG
+        plc.station[2].input AT %IX0.0 : BOOL;
+        plc.grid[1, 2].cells[i].output AT %QX1.7 : BOOL;
+    END_VAR
+    "#;
+
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(
+        result.var_config.iter().map(|it| &src[it.location.to_range().unwrap()]).collect::<Vec<_>>(),
+        vec!["plc.station[2].input", "plc.grid[1, 2].cells[i].output"]
+    );
+}
+
+#[test]
+fn var_config_with_malformed_targets() {
+    let src = r#"
+    VAR_CONFIG
+        plc.station[2]].input AT %IX0.0 : BOOL;
+        plc.station(2).input AT %IX0.1 : BOOL;
+        plc station AT %IX0.2 : BOOL;
+    END_VAR
+    "#;
+
+    let (result, diagnostics) = parse(src);
+
+    assert!(diagnostics.iter().all(|it| it.get_error_code() == "E138"), "{diagnostics:#?}");
+    assert_eq!(
+        diagnostics.iter().map(|it| &src[it.get_location().to_range().unwrap()]).collect::<Vec<_>>(),
+        vec!["plc.station[2]].input", "plc.station(2).input", "plc station"]
+    );
+
+    // the rest of each entry is still parsed
+    assert_eq!(result.var_config.len(), 3);
+}
+
 #[test]
 fn var_external() {
     let src = r#"
@@ -653,6 +746,8 @@ fn function_pointer() {
                             auto_deref: None,
                             type_safe: false,
                             is_function: true,
+                            kind: Function,
+                            nullability: None,
                         },
                     },
                 },
@@ -667,6 +762,8 @@ fn function_pointer() {
                             auto_deref: None,
                             type_safe: false,
                             is_function: true,
+                            kind: Function,
+                            nullability: None,
                         },
                     },
                 },
@@ -693,6 +790,8 @@ fn function_pointer() {
                                 auto_deref: None,
                                 type_safe: false,
                                 is_function: true,
+                                kind: Function,
+                                nullability: None,
                             },
                         },
                     },
@@ -707,3 +806,893 @@ fn function_pointer() {
     }
     "#);
 }
+
+fn struct_members(declaration: &DataTypeDeclaration) -> &[Variable] {
+    let DataTypeDeclaration::Definition { data_type, .. } = declaration else {
+        panic!("expected a type definition, got {declaration:?}");
+    };
+    let DataType::StructType { name: None, variables } = data_type.as_ref() else {
+        panic!("expected an anonymous struct, got {data_type:?}");
+    };
+    variables
+}
+
+#[test]
+fn nested_anonymous_structs_consume_their_own_end_struct() {
+    let src = "
+        PROGRAM prg
+        VAR
+            config : STRUCT
+                a : INT;
+                b : STRUCT
+                    c : STRUCT
+                        d : BOOL := TRUE;
+                    END_STRUCT;
+                    e : DINT;
+                END_STRUCT;
+                f : INT;
+            END_STRUCT;
+            after : INT;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let variables = &result.pous[0].variable_blocks[0].variables;
+    assert_eq!(variables.iter().map(Variable::get_name).collect::<Vec<_>>(), vec!["config", "after"]);
+
+    let config = struct_members(&variables[0].data_type_declaration);
+    assert_eq!(config.iter().map(Variable::get_name).collect::<Vec<_>>(), vec!["a", "b", "f"]);
+
+    let b = struct_members(&config[1].data_type_declaration);
+    assert_eq!(b.iter().map(Variable::get_name).collect::<Vec<_>>(), vec!["c", "e"]);
+
+    let c = struct_members(&b[0].data_type_declaration);
+    assert_eq!(c.iter().map(Variable::get_name).collect::<Vec<_>>(), vec!["d"]);
+    assert_eq!(format!("{:?}", c[0].initializer), "Some(LiteralBool { value: true })");
+}
+
+#[test]
+fn unbalanced_nested_anonymous_struct_reports_missing_end_struct() {
+    let src = "
+        PROGRAM prg
+        VAR
+            config : STRUCT
+                b : STRUCT
+                    c : BOOL;
+                END_STRUCT;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert!(
+        diagnostics.iter().any(|it| it.get_message() == "Missing expected Token [KeywordEndStruct]"),
+        "expected a missing END_STRUCT diagnostic, got {diagnostics:#?}"
+    );
+
+    // the inner struct is still complete and attached to its parent
+    let config = struct_members(&result.pous[0].variable_blocks[0].variables[0].data_type_declaration);
+    assert_eq!(config.iter().map(Variable::get_name).collect::<Vec<_>>(), vec!["b"]);
+    let b = struct_members(&config[0].data_type_declaration);
+    assert_eq!(b.iter().map(Variable::get_name).collect::<Vec<_>>(), vec!["c"]);
+}
+
+#[test]
+fn variable_named_like_elementary_type_is_reported() {
+    let src = "
+        PROGRAM main
+        VAR
+            INT : INT;
+            count : INT;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(result.pous[0].variable_blocks[0].variables.len(), 2);
+    assert_eq!(diagnostics.len(), 1, "expected exactly one diagnostic, got {diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E123");
+    assert_eq!(diagnostics[0].get_message(), "Variable `INT` has the same name as an elementary data type");
+}
+
+#[test]
+fn variable_not_named_like_a_type_is_not_reported() {
+    let src = "
+        PROGRAM main
+        VAR
+            count : INT;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (_, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn display_mode_attribute_is_captured_on_variable() {
+    let src = "
+        PROGRAM main
+        VAR
+            {attribute 'displaymode' := 'hex'}
+            flags : WORD;
+            count : INT;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let variables = &result.pous[0].variable_blocks[0].variables;
+    assert_eq!(variables[0].get_display_mode(), Some("hex"));
+    assert_eq!(
+        variables[0].get_attribute("DisplayMode"),
+        Some(&Attribute { name: "displaymode".to_string(), value: Some("hex".to_string()) })
+    );
+    assert_eq!(variables[1].get_display_mode(), None);
+    assert!(variables[1].attributes.is_empty());
+}
+
+#[test]
+fn constant_struct_with_literal_initializer_keeps_its_initializer() {
+    let src = "
+    PROGRAM prg
+    VAR CONSTANT
+        c : Point := (x := 1, y := 2);
+        d : Point;
+    END_VAR
+    END_PROGRAM
+    ";
+    let (result, diag) = parse(src);
+    assert_eq!(diag, vec![]);
+
+    let variables = &result.pous[0].variable_blocks[0].variables;
+    let initializer = variables[0].initializer.as_ref().expect("the literal initializer must be kept");
+    let elements = flatten_expression_list(initializer);
+    assert_eq!(elements.len(), 2);
+    assert!(elements.iter().all(|it| matches!(it.get_stmt(), AstStatement::Assignment(_))));
+
+    // only the constant without an initializer gets a default value
+    let default = variables[1].initializer.as_ref().expect("constants are default-initialized");
+    assert!(matches!(default.get_stmt(), AstStatement::DefaultValue(_)));
+}
+
+#[test]
+fn initial_value_attribute_is_used_as_initializer() {
+    let src = "
+        PROGRAM main
+        VAR
+            {attribute 'initial_value' := '2 + 3'}
+            a : INT;
+            b : INT;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let variables = &result.pous[0].variable_blocks[0].variables;
+    let initializer = variables[0].initializer.as_ref().expect("the attribute's value initializes `a`");
+    assert!(matches!(initializer.get_stmt(), AstStatement::BinaryExpression(_)));
+    // the initializer's location points into the attribute
+    assert_eq!(&src[initializer.get_location().to_range().unwrap()], "2 + 3");
+    assert_eq!(variables[1].initializer, None);
+}
+
+#[test]
+fn initial_value_attribute_conflicting_with_an_initializer_is_reported() {
+    let src = "
+        PROGRAM main
+        VAR
+            {attribute 'initial_value' := '5'}
+            a : INT := 7;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    let codes = diagnostics.iter().map(|it| it.get_error_code()).collect::<Vec<_>>();
+    assert_eq!(codes, vec!["E126"]);
+    // the explicit initializer wins
+    let initializer = result.pous[0].variable_blocks[0].variables[0].initializer.as_ref().unwrap();
+    assert_eq!(initializer.get_literal_integer_value(), Some(7));
+}
+
+#[test]
+fn constant_pointer_without_initializer_is_reported() {
+    let src = "
+        PROGRAM prg
+        VAR CONSTANT
+            x : REF_TO INT;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].get_error_code(), "E128");
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "CONSTANT variable 'x' of type REF_TO INT requires an explicit initializer"
+    );
+    // no default value is injected for the pointer
+    assert!(result.pous[0].variable_blocks[0].variables[0].initializer.is_none());
+}
+
+#[test]
+fn constant_elementary_variable_without_initializer_gets_a_default_value() {
+    let src = "
+        PROGRAM prg
+        VAR CONSTANT
+            x : INT;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let initializer = result.pous[0].variable_blocks[0].variables[0].initializer.as_ref();
+    assert!(matches!(initializer.map(|it| it.get_stmt()), Some(AstStatement::DefaultValue(_))));
+}
+
+#[test]
+fn constant_global_arrays_keep_their_array_initializers() {
+    let src = "
+        VAR_GLOBAL CONSTANT
+            full : ARRAY[0..3] OF INT := [1, 2, 3, 4];
+            partial : ARRAY[0..3] OF INT := [1, 2];
+            multiplied : ARRAY[0..3] OF INT := [2(7), 9];
+            uninitialized : ARRAY[0..3] OF INT;
+        END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let variables = &result.global_vars[0].variables;
+    let elements = |index: usize| {
+        let initializer = variables[index].initializer.as_ref().expect("the array initializer must be kept");
+        let AstStatement::Literal(AstLiteral::Array(array)) = initializer.get_stmt() else {
+            panic!("expected an array literal, got {initializer:#?}");
+        };
+        array
+            .elements()
+            .map(|it| flatten_expression_list(it).iter().map(|it| it.get_literal_integer_value()).collect())
+            .unwrap_or_else(Vec::<Option<i128>>::new)
+    };
+
+    assert_eq!(elements(0), vec![Some(1), Some(2), Some(3), Some(4)]);
+    // a partial initializer is kept as written, the remaining elements are default-initialized later
+    assert_eq!(elements(1), vec![Some(1), Some(2)]);
+    assert_eq!(elements(2), vec![Some(7), Some(7), Some(9)]);
+
+    // only the constant array without an initializer gets a default value
+    let default = variables[3].initializer.as_ref().expect("constants are default-initialized");
+    assert!(matches!(default.get_stmt(), AstStatement::DefaultValue(_)));
+}
+
+#[test]
+fn global_variables_keep_their_hardware_address() {
+    let src = "
+        VAR_GLOBAL
+            myInput AT %IX0.0 : BOOL;
+            myOutput AT %QW1 : WORD := 5;
+        END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let addresses = result.global_vars[0]
+        .variables
+        .iter()
+        .map(|it| {
+            let Some(AstStatement::HardwareAccess(HardwareAccess { direction, access, address })) =
+                it.address.as_ref().map(|it| it.get_stmt())
+            else {
+                panic!("expected a hardware address for {}", it.name);
+            };
+            let address =
+                address.iter().map(|it| it.get_literal_integer_value().unwrap()).collect::<Vec<_>>();
+            (it.name.as_str(), *direction, *access, address)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        addresses,
+        vec![
+            ("myInput", HardwareAccessType::Input, DirectAccessType::Bit, vec![0, 0]),
+            ("myOutput", HardwareAccessType::Output, DirectAccessType::Word, vec![1]),
+        ]
+    );
+}
+
+#[test]
+fn global_block_mixing_addressed_and_plain_variables() {
+    let src = "
+        VAR_GLOBAL
+            sensor AT %IW2 : INT;
+            counter : DINT := 7;
+        END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let [sensor, counter] = result.global_vars[0].variables.as_slice() else {
+        panic!("expected two global variables, got {:#?}", result.global_vars);
+    };
+
+    assert_eq!(sensor.name, "sensor");
+    assert!(matches!(
+        sensor.address.as_ref().map(|it| it.get_stmt()),
+        Some(AstStatement::HardwareAccess(HardwareAccess {
+            direction: HardwareAccessType::Input,
+            access: DirectAccessType::Word,
+            ..
+        }))
+    ));
+
+    assert_eq!(counter.name, "counter");
+    assert!(counter.address.is_none());
+    assert_eq!(counter.data_type_declaration.get_name(), Some("DINT"));
+    assert_eq!(counter.initializer.as_ref().and_then(|it| it.get_literal_integer_value()), Some(7));
+}
+
+#[test]
+fn global_input_variable_with_initializer_is_reported() {
+    let src = "
+        VAR_GLOBAL
+            myInput AT %IX0.0 : BOOL := TRUE;
+        END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].get_error_code(), "E129");
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "Global variable `myInput` is mapped to an input address and should not have an initializer"
+    );
+    assert!(result.global_vars[0].variables[0].address.is_some());
+}
+
+#[test]
+fn subrange_input_with_default_within_its_bounds() {
+    let src = "
+        FUNCTION_BLOCK fb
+        VAR_INPUT
+            level : INT(0..100) := 50;
+        END_VAR
+        END_FUNCTION_BLOCK
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let variable = &result.pous[0].variable_blocks[0].variables[0];
+    let DataTypeDeclaration::Definition { data_type, .. } = &variable.data_type_declaration else {
+        panic!("expected a subrange definition, got {:?}", variable.data_type_declaration);
+    };
+    let DataType::SubRangeType { referenced_type, bounds: Some(bounds), .. } = data_type.as_ref() else {
+        panic!("expected a subrange type, got {data_type:?}");
+    };
+    assert_eq!(referenced_type, "INT");
+    assert_eq!(
+        format!("{bounds:?}"),
+        "RangeStatement { start: LiteralInteger { value: 0 }, end: LiteralInteger { value: 100 } }"
+    );
+    assert_eq!(variable.initializer.as_ref().and_then(|it| it.get_literal_integer_value()), Some(50));
+}
+
+#[test]
+fn subrange_input_with_default_outside_its_bounds_is_reported() {
+    let src = "
+        FUNCTION_BLOCK fb
+        VAR_INPUT
+            level : INT(0..100) := 150;
+            offset : INT(-10..10) := -11;
+            bounded : INT(0..100) := 100;
+        END_VAR
+        END_FUNCTION_BLOCK
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 2);
+    for (diagnostic, (message, value)) in diagnostics.iter().zip([
+        ("Initial value 150 is outside the subrange 0..100", "150"),
+        ("Initial value -11 is outside the subrange -10..10", "-11"),
+    ]) {
+        assert_eq!(diagnostic.get_message(), message);
+        assert_eq!(diagnostic.get_error_code(), "E135");
+        assert_eq!(&src[diagnostic.get_location().to_range().unwrap()], value);
+    }
+
+    // the variables are kept including their defaults
+    let variables = &result.pous[0].variable_blocks[0].variables;
+    assert_eq!(variables.len(), 3);
+    assert!(variables.iter().all(|it| it.initializer.is_some()));
+}
+
+#[test]
+fn variable_block_qualifiers_are_recorded_and_contradicting_ones_reported() {
+    for (qualifiers, (constant, retain, persistent), reported) in [
+        ("CONSTANT", (true, false, false), None),
+        ("RETAIN", (false, true, false), None),
+        ("PERSISTENT", (false, false, true), None),
+        ("RETAIN PERSISTENT", (false, true, true), None),
+        ("PERSISTENT RETAIN", (false, true, true), None),
+        ("CONSTANT RETAIN", (true, true, false), Some("RETAIN has no effect on CONSTANT variables")),
+        ("CONSTANT PERSISTENT", (true, false, true), Some("PERSISTENT has no effect on CONSTANT variables")),
+        ("PERSISTENT CONSTANT", (true, false, true), Some("PERSISTENT has no effect on CONSTANT variables")),
+        (
+            "CONSTANT RETAIN PERSISTENT",
+            (true, true, true),
+            Some("RETAIN and PERSISTENT have no effect on CONSTANT variables"),
+        ),
+    ] {
+        let src = format!("PROGRAM prg VAR {qualifiers} x : INT := 1; END_VAR END_PROGRAM");
+        let (result, diagnostics) = parse(&src);
+
+        let block = &result.pous[0].variable_blocks[0];
+        assert_eq!(
+            (block.constant, block.retain, block.persistent),
+            (constant, retain, persistent),
+            "{qualifiers}"
+        );
+        if let Some(message) = reported {
+            assert_eq!(diagnostics.len(), 1, "{qualifiers}");
+            assert_eq!(diagnostics[0].get_message(), message);
+            assert_eq!(diagnostics[0].get_error_code(), "E137");
+            assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "VAR");
+        } else {
+            assert_eq!(diagnostics, vec![], "{qualifiers}");
+        }
+    }
+}
+
+#[test]
+fn persistent_can_still_be_used_as_a_variable_name() {
+    let src = "
+        VAR_GLOBAL PERSISTENT
+            persistent : BOOL;
+        END_VAR
+        PROGRAM prg
+        VAR
+            Persistent, other : INT;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    assert!(result.global_vars[0].persistent);
+    assert_eq!(result.global_vars[0].variables[0].name, "persistent");
+
+    let block = &result.pous[0].variable_blocks[0];
+    assert!(!block.persistent);
+    assert_eq!(
+        block.variables.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(),
+        vec!["Persistent", "other"]
+    );
+}
+
+#[test]
+fn persistent_can_be_used_as_the_name_of_a_located_variable() {
+    let src = "
+        PROGRAM prg
+        VAR
+            persistent AT %IX1.0 : BOOL;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let block = &result.pous[0].variable_blocks[0];
+    assert!(!block.persistent);
+    assert_eq!(block.variables.len(), 1);
+    assert_eq!(block.variables[0].name, "persistent");
+    assert!(block.variables[0].address.is_some());
+}
+
+#[test]
+fn ref_to_output_is_parsed_as_a_pointer() {
+    let src = "
+        FUNCTION_BLOCK fb
+        VAR_OUTPUT
+            out : REF_TO INT;
+        END_VAR
+        END_FUNCTION_BLOCK
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let variable = &result.pous[0].variable_blocks[0].variables[0];
+    let DataTypeDeclaration::Definition { data_type, .. } = &variable.data_type_declaration else {
+        panic!("expected an inline pointer definition, got {variable:#?}");
+    };
+    let DataType::PointerType { referenced_type, auto_deref, type_safe, is_function, kind, .. } =
+        data_type.as_ref()
+    else {
+        panic!("expected a pointer type, got {data_type:#?}");
+    };
+    assert_eq!(referenced_type.get_name(), Some("INT"));
+    assert_eq!(auto_deref, &None);
+    assert!(type_safe);
+    assert!(!is_function);
+    assert_eq!(kind, &PointerKind::RefTo);
+}
+
+#[test]
+fn ref_to_output_with_an_initializer_is_reported() {
+    let src = "
+        FUNCTION_BLOCK fb
+        VAR_OUTPUT
+            out : REF_TO INT := REF(g);
+            ptr : POINTER TO INT := REF(g);
+        END_VAR
+        VAR_INPUT
+            in : REF_TO INT := REF(g);
+        END_VAR
+        END_FUNCTION_BLOCK
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_message(), "Output 'out' of type REF_TO can not have an initializer");
+    assert_eq!(diagnostics[0].get_error_code(), "E140");
+    assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "out");
+
+    // the initializer is kept
+    assert!(result.pous[0].variable_blocks[0].variables[0].initializer.is_some());
+}
+
+#[test]
+fn nullability_pragmas_on_pointers_are_recorded() {
+    let src = "
+        VAR_GLOBAL
+            a : {not_null} REF_TO INT;
+            b : REF_TO {nullable} INT;
+            c : {nullable} REFERENCE TO INT;
+            d : REF_TO INT;
+        END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+    assert!(diagnostics.is_empty(), "{diagnostics:#?}");
+
+    let nullabilities = result.global_vars[0]
+        .variables
+        .iter()
+        .map(|variable| match &variable.data_type_declaration {
+            DataTypeDeclaration::Definition { data_type, .. } => match data_type.as_ref() {
+                DataType::PointerType { nullability, .. } => *nullability,
+                other => panic!("expected a pointer type, got {other:#?}"),
+            },
+            other => panic!("expected a definition, got {other:#?}"),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        nullabilities,
+        vec![Some(Nullability::NotNull), Some(Nullability::Nullable), Some(Nullability::Nullable), None]
+    );
+}
+
+#[test]
+fn nullability_pragma_on_a_non_pointer_is_reported() {
+    let src = "
+        VAR_GLOBAL
+            a : {not_null} INT;
+        END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_message(), "The pragma {not_null} can only be applied to pointer types");
+    assert_eq!(diagnostics[0].get_error_code(), "E141");
+    assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "{not_null}");
+
+    // the variable is still declared with its type
+    assert_eq!(result.global_vars[0].variables[0].data_type_declaration.get_name(), Some("INT"));
+}
+
+#[test]
+fn address_pragmas_place_variables_at_absolute_addresses() {
+    let src = "
+        VAR_GLOBAL
+            {address 16#1000}
+            hex : INT;
+            {address '0x2000'}
+            quoted : INT;
+            {address 4096}
+            decimal, second : INT;
+            {attribute 'displaymode' := 'hex'}
+            other : INT;
+        END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let addresses = result.global_vars[0]
+        .variables
+        .iter()
+        .map(|it| (it.name.as_str(), it.absolute_address))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        addresses,
+        vec![
+            ("hex", Some(0x1000)),
+            ("quoted", Some(0x2000)),
+            ("decimal", Some(4096)),
+            ("second", Some(4096)),
+            ("other", None),
+        ]
+    );
+}
+
+#[test]
+fn invalid_and_conflicting_address_pragmas_are_reported() {
+    let src = "
+        VAR_GLOBAL
+            {address 'top'}
+            invalid : INT;
+            {address 16#1000}
+            conflicting AT %IX1.2 : BOOL;
+        END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 2, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E142");
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "Invalid absolute address 'top', expected a hex or decimal number"
+    );
+    assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "{address 'top'}");
+    assert_eq!(diagnostics[1].get_error_code(), "E143");
+    assert_eq!(&src[diagnostics[1].get_location().to_range().unwrap()], "{address 16#1000}");
+
+    let variables = &result.global_vars[0].variables;
+    assert_eq!(variables[0].absolute_address, None);
+    // both locations are kept for the conflicting variable
+    assert_eq!(variables[1].absolute_address, Some(0x1000));
+    assert!(variables[1].address.is_some());
+}
+
+#[test]
+fn address_pragmas_in_comments_are_ignored() {
+    let src = "
+        VAR_GLOBAL
+            (* {address 16#1000} *)
+            commented : INT;
+            {address 16#2000} (* a comment *)
+            placed : INT;
+        END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let variables = &result.global_vars[0].variables;
+    assert_eq!(variables[0].absolute_address, None);
+    assert_eq!(variables[1].absolute_address, Some(0x2000));
+}
+
+#[test]
+fn reserved_keywords_as_variable_names_are_reported() {
+    let src = "
+        PROGRAM prg
+        VAR
+            TYPE (* a comment *) : INT;
+            var, case : BOOL;
+            ok : INT := 1;
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+
+    let messages = diagnostics.iter().map(|it| it.get_message()).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "'TYPE' is a reserved keyword and cannot be used as an identifier",
+            "'var' is a reserved keyword and cannot be used as an identifier",
+            "'case' is a reserved keyword and cannot be used as an identifier",
+        ]
+    );
+    assert!(diagnostics.iter().all(|it| it.get_error_code() == "E145"));
+    assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "TYPE");
+
+    // the declarations are still parsed
+    let names =
+        result.pous[0].variable_blocks[0].variables.iter().map(|it| it.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["TYPE", "var", "case", "ok"]);
+}
+
+#[test]
+fn ref_to_initialized_with_the_address_of_a_struct_member() {
+    let src = "
+        PROGRAM prg
+        VAR
+            s : point;
+            p : REF_TO INT := REF(s.field);
+        END_VAR
+        END_PROGRAM
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let variable = &result.pous[0].variable_blocks[0].variables[1];
+    let DataTypeDeclaration::Definition { data_type, location: type_location, .. } =
+        &variable.data_type_declaration
+    else {
+        panic!("expected an inline pointer definition, got {:#?}", variable.data_type_declaration);
+    };
+    assert!(matches!(data_type.as_ref(), DataType::PointerType { kind: PointerKind::RefTo, .. }));
+
+    // the initializer is a call to REF with the member access as its argument
+    let initializer = variable.initializer.as_ref().expect("the initializer is attached to the pointer");
+    let AstStatement::CallStatement(call) = initializer.get_stmt() else {
+        panic!("expected a call, got {initializer:#?}");
+    };
+    assert_eq!(call.operator.get_flat_reference_name(), Some("REF"));
+    let argument = call.parameters.as_ref().expect("REF has an argument");
+    assert_eq!(&src[argument.get_location().to_range().unwrap()], "s.field");
+    assert!(matches!(
+        argument.get_stmt(),
+        AstStatement::ReferenceExpr(ReferenceExpr { access: ReferenceAccess::Member(_), base: Some(_) })
+    ));
+
+    // the type's location does not overlap the initializer
+    let type_range = type_location.to_range().unwrap();
+    let initializer_range = initializer.get_location().to_range().unwrap();
+    assert_eq!(&src[type_range.clone()], "REF_TO INT");
+    assert_eq!(&src[initializer_range.clone()], "REF(s.field)");
+    assert!(type_range.end < initializer_range.start);
+}
+
+#[test]
+fn unicode_names_are_parsed_with_byte_accurate_locations() {
+    let src = "
+        FUNCTION_BLOCK Wärmetauscher
+        VAR
+            Größe : INT;
+            温度 : REAL;
+        END_VAR
+            温度 := Größe;
+        END_FUNCTION_BLOCK
+        ";
+    let (result, diagnostics) = parse(src);
+    assert_eq!(diagnostics, vec![]);
+
+    let pou = &result.pous[0];
+    assert_eq!(pou.name, "Wärmetauscher");
+    assert_eq!(&src[pou.name_location.to_range().unwrap()], "Wärmetauscher");
+
+    let variables = &pou.variable_blocks[0].variables;
+    assert_eq!(variables[0].name, "Größe");
+    assert_eq!(&src[variables[0].location.to_range().unwrap()], "Größe");
+    assert_eq!(variables[1].name, "温度");
+    assert_eq!(&src[variables[1].location.to_range().unwrap()], "温度");
+    assert_eq!(&src[variables[1].data_type_declaration.get_location().to_range().unwrap()], "REAL");
+
+    let statement = &result.implementations[0].statements[0];
+    assert_eq!(&src[statement.get_location().to_range().unwrap()], "温度 := Größe");
+}
+
+#[test]
+fn var_access_block_is_parsed() {
+    let src = "
+        VAR_ACCESS
+            counter : main.fb.count : INT READ_WRITE;
+            state : main.state : BOOL read_only;
+        END_VAR
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+    let entries = unit
+        .var_access
+        .iter()
+        .map(|it| {
+            (it.name.name.as_str(), &src[it.reference.get_location().to_range().unwrap()], it.direction)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        vec![
+            ("counter", "main.fb.count", AccessDirection::ReadWrite),
+            ("state", "main.state", AccessDirection::ReadOnly),
+        ]
+    );
+    assert_eq!(unit.var_access[0].data_type.get_name(), Some("INT"));
+    assert_eq!(unit.var_access[1].data_type.get_name(), Some("BOOL"));
+}
+
+#[test]
+fn var_access_without_direction_defaults_to_read_only() {
+    let src = "
+        VAR_ACCESS
+            counter : main.count : INT;
+        END_VAR
+        ";
+    let (unit, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E147");
+    assert_eq!(
+        diagnostics[0].get_message(),
+        "Missing access direction for 'counter', defaulting to READ_ONLY"
+    );
+    assert_eq!(unit.var_access[0].direction, AccessDirection::ReadOnly);
+}
+
+#[test]
+fn string_initializers_with_matching_quotes_are_accepted() {
+    let src = r#"
+        VAR_GLOBAL
+            a : STRING := 'abc';
+            b : STRING[10] := 'abc';
+            c : WSTRING := "abc";
+            d : WSTRING[10] := "abc";
+        END_VAR
+        "#;
+    let (_, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn string_initializers_with_mismatched_quotes_are_reported() {
+    let src = r#"
+        VAR_GLOBAL
+            a : STRING := "abc";
+            b : WSTRING[10] := 'xyz';
+        END_VAR
+        "#;
+    let (unit, diagnostics) = parse(src);
+
+    let messages = diagnostics
+        .iter()
+        .map(|it| (it.get_error_code(), it.get_message(), &src[it.get_location().to_range().unwrap()]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            ("E148", r#"STRING literals are written in single quotes, use 'abc' instead"#, r#""abc""#),
+            ("E148", r#"WSTRING literals are written in double quotes, use "xyz" instead"#, "'xyz'"),
+        ]
+    );
+
+    // the literals are kept as written
+    let initializers = unit.global_vars[0]
+        .variables
+        .iter()
+        .map(|it| it.initializer.as_ref().unwrap().get_stmt())
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        initializers[0],
+        AstStatement::Literal(AstLiteral::String(StringValue { value, is_wide: true })) if value == "abc"
+    ));
+    assert!(matches!(
+        initializers[1],
+        AstStatement::Literal(AstLiteral::String(StringValue { value, is_wide: false })) if value == "xyz"
+    ));
+}
+
+#[test]
+fn global_constant_retain_block_is_reported_once_and_keeps_both_flags() {
+    let src = "
+        VAR_GLOBAL CONSTANT RETAIN
+            a : INT := 1;
+            b : INT := 2;
+        END_VAR
+        ";
+    let (result, diagnostics) = parse(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0].get_error_code(), "E137");
+    assert_eq!(diagnostics[0].get_message(), "RETAIN has no effect on CONSTANT variables");
+    assert_eq!(&src[diagnostics[0].get_location().to_range().unwrap()], "VAR_GLOBAL");
+
+    let block = &result.global_vars[0];
+    assert!(block.constant);
+    assert!(block.retain);
+}
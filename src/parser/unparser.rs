@@ -0,0 +1,539 @@
+//! Renders a parsed [`CompilationUnit`] back into canonical structured text.
+//!
+//! The output is not meant to preserve the original formatting (comments, casing, line breaks), but
+//! to re-parse into an equivalent AST. Interfaces, properties, attribute pragmas and dialect
+//! extensions are not rendered yet.
+
+use plc_ast::{
+    ast::{
+        ArgumentProperty, Assignment, AstNode, AstStatement, BinaryExpression, CallStatement,
+        CompilationUnit, DataType, DataTypeDeclaration, DirectAccess, DirectAccessType, GenericBinding,
+        HardwareAccess, HardwareAccessType, LinkageType, MultipliedStatement, Operator, PointerKind, Pou,
+        PouType, RangeStatement, ReferenceAccess, ReferenceExpr, UnaryExpression, UserTypeDeclaration,
+        Variable, VariableBlock, VariableBlockType,
+    },
+    control_statements::{AstControlStatement, ConditionalBlock, ReturnStatement},
+    literals::{Array, AstLiteral, Time},
+};
+
+use crate::typesystem::DINT_TYPE;
+
+const INDENT: &str = "    ";
+
+/// renders the given unit as structured text
+pub fn unparse(unit: &CompilationUnit) -> String {
+    let mut unparser = Unparser::default();
+
+    for block in &unit.global_vars {
+        unparser.variable_block(block);
+    }
+
+    if !unit.user_types.is_empty() {
+        unparser.line("TYPE");
+        unparser.indented(|unparser| unit.user_types.iter().for_each(|it| unparser.user_type(it)));
+        unparser.line("END_TYPE");
+    }
+
+    // methods are rendered as part of their parent
+    for pou in unit.pous.iter().filter(|it| !matches!(it.kind, PouType::Method { .. })) {
+        unparser.pou(unit, pou);
+    }
+
+    for action in unit.implementations.iter().filter(|it| it.pou_type == PouType::Action) {
+        unparser.line(&format!("ACTION {}", action.name));
+        unparser.statements(&action.statements);
+        unparser.line("END_ACTION");
+    }
+
+    unparser.text
+}
+
+#[derive(Default)]
+struct Unparser {
+    text: String,
+    indent: usize,
+}
+
+impl Unparser {
+    /// appends the given text at the current indentation, text spanning multiple lines is indented line by line
+    fn line(&mut self, text: &str) {
+        for line in text.lines() {
+            for _ in 0..self.indent {
+                self.text.push_str(INDENT);
+            }
+            self.text.push_str(line);
+            self.text.push('\n');
+        }
+    }
+
+    fn indented(&mut self, f: impl FnOnce(&mut Self)) {
+        self.indent += 1;
+        f(self);
+        self.indent -= 1;
+    }
+
+    fn pou(&mut self, unit: &CompilationUnit, pou: &Pou) {
+        if pou.linkage == LinkageType::External {
+            self.line("{external}");
+        }
+
+        let (keyword, end_keyword) = match pou.kind {
+            PouType::Function => ("FUNCTION", "END_FUNCTION"),
+            PouType::FunctionBlock => ("FUNCTION_BLOCK", "END_FUNCTION_BLOCK"),
+            PouType::Class => ("CLASS", "END_CLASS"),
+            PouType::Method { .. } => ("METHOD", "END_METHOD"),
+            _ => ("PROGRAM", "END_PROGRAM"),
+        };
+
+        // a method's name is qualified with its parent's name
+        let name = match &pou.kind {
+            PouType::Method { parent, .. } => pou
+                .name
+                .strip_prefix(parent.as_str())
+                .and_then(|it| it.strip_prefix('.'))
+                .unwrap_or(&pou.name),
+            _ => &pou.name,
+        };
+        let mut header = format!("{keyword} {name}");
+        if !pou.generics.is_empty() {
+            let generics = pou.generics.iter().map(GenericBinding::signature_string).collect::<Vec<_>>();
+            header.push_str(&format!("<{}>", generics.join(", ")));
+        }
+        if let Some(return_type) = &pou.return_type {
+            header.push_str(&format!(" : {}", data_type_declaration(return_type)));
+        }
+        if let Some(super_class) = &pou.super_class {
+            header.push_str(&format!(" EXTENDS {}", super_class.name));
+        }
+        if !pou.interfaces.is_empty() {
+            let interfaces = pou.interfaces.iter().map(|it| it.name.as_str()).collect::<Vec<_>>();
+            header.push_str(&format!(" IMPLEMENTS {}", interfaces.join(", ")));
+        }
+        self.line(&header);
+
+        for block in &pou.variable_blocks {
+            self.variable_block(block);
+        }
+
+        let is_method_of_pou =
+            |it: &&Pou| matches!(&it.kind, PouType::Method { parent, .. } if parent == &pou.name);
+        for method in unit.pous.iter().filter(is_method_of_pou) {
+            self.indented(|unparser| unparser.pou(unit, method));
+        }
+
+        let implementation =
+            unit.implementations.iter().find(|it| it.name == pou.name && it.pou_type != PouType::Action);
+        if let Some(implementation) = implementation {
+            self.statements(&implementation.statements);
+        }
+
+        self.line(end_keyword);
+    }
+
+    fn variable_block(&mut self, block: &VariableBlock) {
+        let mut header = match block.kind {
+            VariableBlockType::Local => "VAR",
+            VariableBlockType::Temp => "VAR_TEMP",
+            VariableBlockType::Input(ArgumentProperty::ByVal) => "VAR_INPUT",
+            VariableBlockType::Input(ArgumentProperty::ByRef) => "VAR_INPUT {ref}",
+            VariableBlockType::Output => "VAR_OUTPUT",
+            VariableBlockType::Global => "VAR_GLOBAL",
+            VariableBlockType::InOut => "VAR_IN_OUT",
+            VariableBlockType::External => "VAR_EXTERNAL",
+        }
+        .to_string();
+        if block.constant {
+            header.push_str(" CONSTANT");
+        }
+        if block.retain {
+            header.push_str(" RETAIN");
+        }
+        if block.persistent {
+            header.push_str(" PERSISTENT");
+        }
+        self.line(&header);
+        self.indented(|unparser| block.variables.iter().for_each(|it| unparser.variable(it)));
+        self.line("END_VAR");
+    }
+
+    fn variable(&mut self, variable: &Variable) {
+        let mut line = variable.name.clone();
+        let mut initializer = variable.initializer.as_ref();
+
+        // variables declared with `AT` are represented as alias pointers
+        let data_type = match aliased_type(&variable.data_type_declaration) {
+            Some(referenced_type) => {
+                if let Some(address) = &variable.address {
+                    line.push_str(&format!(" AT {}", expression(address)));
+                } else if let Some(aliased) = initializer.take() {
+                    line.push_str(&format!(" AT {}", expression(aliased)));
+                }
+                data_type_declaration(referenced_type)
+            }
+            None => data_type_declaration(&variable.data_type_declaration),
+        };
+        line.push_str(&format!(" : {data_type}"));
+        match variable.retain {
+            Some(true) => line.push_str(" RETAIN"),
+            Some(false) => line.push_str(" NON_RETAIN"),
+            None => {}
+        }
+
+        // constants without an initializer are given a default value by the parser
+        if let Some(initializer) = initializer.filter(|it| !it.is_default_value()) {
+            line.push_str(&format!(" := {}", expression(initializer)));
+        }
+        line.push(';');
+        self.line(&line);
+    }
+
+    fn user_type(&mut self, user_type: &UserTypeDeclaration) {
+        let name = user_type.data_type.get_name().unwrap_or_default();
+        let mut line = format!("{name} : {}", data_type(&user_type.data_type));
+        if let Some(initializer) = &user_type.initializer {
+            line.push_str(&format!(" := {}", expression(initializer)));
+        }
+        line.push(';');
+        self.line(&line);
+    }
+
+    fn statements(&mut self, statements: &[AstNode]) {
+        self.indented(|unparser| statements.iter().for_each(|it| unparser.statement(it)));
+    }
+
+    fn statement(&mut self, statement: &AstNode) {
+        match statement.get_stmt() {
+            AstStatement::ControlStatement(AstControlStatement::If(stmt)) => {
+                for (index, ConditionalBlock { condition, body }) in stmt.blocks.iter().enumerate() {
+                    let keyword = if index == 0 { "IF" } else { "ELSIF" };
+                    self.line(&format!("{keyword} {} THEN", expression(condition)));
+                    self.statements(body);
+                }
+                if !stmt.else_block.is_empty() {
+                    self.line("ELSE");
+                    self.statements(&stmt.else_block);
+                }
+                self.line("END_IF");
+            }
+            AstStatement::ControlStatement(AstControlStatement::ForLoop(stmt)) => {
+                let mut header = format!(
+                    "FOR {} := {} TO {}",
+                    expression(&stmt.counter),
+                    expression(&stmt.start),
+                    expression(&stmt.end)
+                );
+                if let Some(step) = &stmt.by_step {
+                    header.push_str(&format!(" BY {}", expression(step)));
+                }
+                self.line(&format!("{header} DO"));
+                self.statements(&stmt.body);
+                self.line("END_FOR");
+            }
+            AstStatement::ControlStatement(AstControlStatement::WhileLoop(stmt)) => {
+                self.line(&format!("WHILE {} DO", expression(&stmt.condition)));
+                self.statements(&stmt.body);
+                self.line("END_WHILE");
+            }
+            AstStatement::ControlStatement(AstControlStatement::RepeatLoop(stmt)) => {
+                self.line("REPEAT");
+                self.statements(&stmt.body);
+                self.line(&format!("UNTIL {}", expression(&stmt.condition)));
+                self.line("END_REPEAT");
+            }
+            AstStatement::ControlStatement(AstControlStatement::Case(stmt)) => {
+                self.line(&format!("CASE {} OF", expression(&stmt.selector)));
+                self.indented(|unparser| {
+                    for ConditionalBlock { condition, body } in &stmt.case_blocks {
+                        unparser.line(&format!("{}:", expression(condition)));
+                        unparser.statements(body);
+                    }
+                });
+                if !stmt.else_block.is_empty() {
+                    self.line("ELSE");
+                    self.statements(&stmt.else_block);
+                }
+                self.line("END_CASE");
+            }
+            AstStatement::ControlStatement(AstControlStatement::Try(stmt)) => {
+                self.line("__TRY");
+                self.statements(&stmt.body);
+                if let Some(variable) = &stmt.catch_variable {
+                    self.line(&format!("__CATCH({})", expression(variable)));
+                    self.statements(&stmt.catch_body);
+                }
+                if !stmt.finally_body.is_empty() {
+                    self.line("__FINALLY");
+                    self.statements(&stmt.finally_body);
+                }
+                self.line("END_TRY");
+            }
+            // conditional returns and jumps only exist in graphical languages, allocations in lowered code
+            AstStatement::ReturnStatement(ReturnStatement { condition: Some(_), .. })
+            | AstStatement::JumpStatement(_)
+            | AstStatement::LabelStatement(_)
+            | AstStatement::AllocationStatement(_) => {
+                unsupported(statement);
+            }
+            AstStatement::ReturnStatement(ReturnStatement { value: Some(value), .. }) => {
+                self.line(&format!("RETURN {};", expression(value)))
+            }
+            AstStatement::ReturnStatement(_) => self.line("RETURN;"),
+            AstStatement::ExitStatement(_) => self.line("EXIT;"),
+            AstStatement::ContinueStatement(_) => self.line("CONTINUE;"),
+            AstStatement::EmptyStatement(_) => {}
+            _ => self.line(&format!("{};", expression(statement))),
+        }
+    }
+}
+
+fn data_type_declaration(declaration: &DataTypeDeclaration) -> String {
+    match declaration {
+        DataTypeDeclaration::Reference { referenced_type, .. } => referenced_type.clone(),
+        DataTypeDeclaration::Definition { data_type, .. } => data_type(data_type),
+        DataTypeDeclaration::Aggregate { referenced_type, .. } => referenced_type.clone(),
+    }
+}
+
+/// renders the definition of the given data type, which may span multiple lines for structs
+fn data_type(data_type: &DataType) -> String {
+    match data_type {
+        DataType::StructType { variables, .. } => {
+            let mut members = Unparser { text: String::new(), indent: 1 };
+            variables.iter().for_each(|it| members.variable(it));
+            format!("STRUCT\n{}END_STRUCT", members.text)
+        }
+        DataType::EnumType { numeric_type, elements, .. } => {
+            let elements = format!("({})", list(elements));
+            if numeric_type == DINT_TYPE {
+                elements
+            } else {
+                format!("{elements} {numeric_type}")
+            }
+        }
+        DataType::SubRangeType { referenced_type, bounds, .. } => match bounds {
+            Some(bounds) => format!("{referenced_type}({})", expression(bounds)),
+            None => referenced_type.clone(),
+        },
+        DataType::ArrayType { bounds, referenced_type, .. } => {
+            format!("ARRAY[{}] OF {}", list(bounds), data_type_declaration(referenced_type))
+        }
+        DataType::PointerType { referenced_type, kind, .. } => {
+            let keyword = match kind {
+                PointerKind::Function => "__FPOINTER",
+                PointerKind::ReferenceTo => "REFERENCE TO",
+                PointerKind::RefTo | PointerKind::Alias => "REF_TO",
+                PointerKind::PointerTo => "POINTER TO",
+            };
+            format!("{keyword} {}", data_type_declaration(referenced_type))
+        }
+        DataType::StringType { is_wide, size, encoding, .. } => {
+            let mut text = if *is_wide { "WSTRING" } else { "STRING" }.to_string();
+            if let Some(size) = size {
+                text.push_str(&format!("[{}]", expression(size)));
+            }
+            if let Some(encoding) = encoding {
+                text.push_str(&format!("({encoding})"));
+            }
+            text
+        }
+        DataType::VarArgs { referenced_type, sized } => {
+            let referenced_type =
+                referenced_type.as_ref().map(|it| data_type_declaration(it)).unwrap_or_default();
+            let sized = if *sized { "{sized} " } else { "" };
+            format!("{sized}{referenced_type}...")
+        }
+        DataType::GenericType { generic_symbol, .. } => generic_symbol.clone(),
+    }
+}
+
+/// renders the given expression, parentheses are kept as parsed
+pub(super) fn expression(node: &AstNode) -> String {
+    match node.get_stmt() {
+        AstStatement::Literal(literal) => literal_text(literal),
+        AstStatement::MultipliedStatement(MultipliedStatement { multiplier, element }) => {
+            format!("{multiplier}({})", expression(element))
+        }
+        AstStatement::ReferenceExpr(ReferenceExpr { access, base }) => {
+            let base = base.as_ref().map(|it| expression(it));
+            match (access, base) {
+                (ReferenceAccess::Member(member), Some(base)) => format!("{base}.{}", expression(member)),
+                (ReferenceAccess::Member(member), None) => expression(member),
+                (ReferenceAccess::Global(member), _) => format!(".{}", expression(member)),
+                (ReferenceAccess::Index(index), base) => {
+                    format!("{}[{}]", base.unwrap_or_default(), expression(index))
+                }
+                (ReferenceAccess::Cast(target), base) => {
+                    format!("{}#{}", base.unwrap_or_default(), expression(target))
+                }
+                (ReferenceAccess::Deref, base) => format!("{}^", base.unwrap_or_default()),
+                // the address-of operator only appears in lowered code, `REF` is its source equivalent
+                (ReferenceAccess::Address, base) => format!("REF({})", base.unwrap_or_default()),
+            }
+        }
+        AstStatement::Identifier(name) => name.clone(),
+        AstStatement::Super(deref) => if deref.is_some() { "SUPER^" } else { "SUPER" }.to_string(),
+        AstStatement::This => "THIS".to_string(),
+        AstStatement::DirectAccess(DirectAccess { access, index }) => {
+            format!("%{}{}", direct_access_type(access), expression(index))
+        }
+        AstStatement::HardwareAccess(HardwareAccess { direction, access, address }) => {
+            let direction = match direction {
+                HardwareAccessType::Input => "I",
+                HardwareAccessType::Output => "Q",
+                HardwareAccessType::Memory => "M",
+                HardwareAccessType::Global => "G",
+            };
+            let address = address.iter().map(expression).collect::<Vec<_>>();
+            format!("%{direction}{}{}", direct_access_type(access), address.join("."))
+        }
+        AstStatement::BinaryExpression(BinaryExpression { operator, left, right }) => {
+            format!("{} {} {}", expression(left), operator, expression(right))
+        }
+        AstStatement::UnaryExpression(UnaryExpression { operator: Operator::Not, value }) => {
+            format!("NOT {}", expression(value))
+        }
+        AstStatement::UnaryExpression(UnaryExpression { operator, value }) => {
+            format!("{operator}{}", expression(value))
+        }
+        AstStatement::ExpressionList(_) => list(node),
+        AstStatement::ParenExpression(inner) => format!("({})", expression(inner)),
+        AstStatement::RangeStatement(RangeStatement { start, end }) => {
+            format!("{}..{}", expression(start), expression(end))
+        }
+        AstStatement::VlaRangeStatement => "*".to_string(),
+        AstStatement::Assignment(Assignment { left, right }) => {
+            format!("{} := {}", expression(left), expression(right))
+        }
+        AstStatement::OutputAssignment(Assignment { left, right }) => {
+            format!("{} => {}", expression(left), expression(right))
+        }
+        AstStatement::RefAssignment(Assignment { left, right }) => {
+            format!("{} REF= {}", expression(left), expression(right))
+        }
+        AstStatement::CallStatement(CallStatement { operator, parameters }) => {
+            let parameters = parameters.as_ref().map(|it| list(it)).unwrap_or_default();
+            format!("{}({parameters})", expression(operator))
+        }
+        AstStatement::CaseCondition(condition) => expression(condition),
+        // placeholders without a source representation, e.g. an omitted call argument
+        AstStatement::EmptyStatement(_) | AstStatement::DefaultValue(_) => String::new(),
+        // statements are rendered by `statement`, they cannot appear within an expression
+        AstStatement::ControlStatement(_)
+        | AstStatement::ExitStatement(_)
+        | AstStatement::ContinueStatement(_)
+        | AstStatement::ReturnStatement(_)
+        | AstStatement::JumpStatement(_)
+        | AstStatement::LabelStatement(_)
+        | AstStatement::AllocationStatement(_) => unsupported(node),
+    }
+}
+
+/// renders a node the unparser has no source representation for. An incomplete rendering would silently
+/// change the program, so this fails loudly in debug builds and leaves the node out otherwise
+fn unsupported(node: &AstNode) -> String {
+    if cfg!(debug_assertions) {
+        panic!("{node:?} cannot be unparsed");
+    }
+    String::new()
+}
+
+/// renders the elements of an expression list (or a single expression) separated by commas
+fn list(node: &AstNode) -> String {
+    match node.get_stmt() {
+        AstStatement::ExpressionList(expressions) => {
+            expressions.iter().map(expression).collect::<Vec<_>>().join(", ")
+        }
+        _ => expression(node),
+    }
+}
+
+fn literal_text(literal: &AstLiteral) -> String {
+    match literal {
+        AstLiteral::Null => "NULL".to_string(),
+        AstLiteral::Integer(value) => value.to_string(),
+        AstLiteral::Real(value) => value.clone(),
+        AstLiteral::Bool(value) => if *value { "TRUE" } else { "FALSE" }.to_string(),
+        AstLiteral::String(string) => {
+            let quote = if string.is_wide() { '"' } else { '\'' };
+            let mut text = String::from(quote);
+            for c in string.value().chars() {
+                match c {
+                    '$' => text.push_str("$$"),
+                    '\n' => text.push_str("$N"),
+                    '\r' => text.push_str("$R"),
+                    '\t' => text.push_str("$T"),
+                    c if c == quote => text.push_str(&format!("${quote}")),
+                    c => text.push(c),
+                }
+            }
+            text.push(quote);
+            text
+        }
+        AstLiteral::Date(date) => format!("D#{:04}-{:02}-{:02}", date.year(), date.month(), date.day()),
+        AstLiteral::DateAndTime(dt) => format!(
+            "DT#{:04}-{:02}-{:02}-{}",
+            dt.year(),
+            dt.month(),
+            dt.day(),
+            time_of_day_text(dt.hour(), dt.min(), dt.sec(), dt.nano())
+        ),
+        AstLiteral::TimeOfDay(tod) => {
+            format!("TOD#{}", time_of_day_text(tod.hour(), tod.min(), tod.sec(), tod.nano()))
+        }
+        AstLiteral::Time(time) => time_text(time),
+        AstLiteral::Array(Array { elements }) => {
+            format!("[{}]", elements.as_ref().map(|it| list(it)).unwrap_or_default())
+        }
+    }
+}
+
+fn time_of_day_text(hour: u32, min: u32, sec: u32, nano: u32) -> String {
+    if nano == 0 {
+        format!("{hour:02}:{min:02}:{sec:02}")
+    } else {
+        let fraction = format!("{nano:09}");
+        format!("{hour:02}:{min:02}:{sec:02}.{}", fraction.trim_end_matches('0'))
+    }
+}
+
+fn time_text(time: &Time) -> String {
+    let segments = [
+        (time.day(), "d"),
+        (time.hour(), "h"),
+        (time.min(), "m"),
+        (time.sec(), "s"),
+        (time.milli(), "ms"),
+        (time.micro(), "us"),
+        (time.nano() as f64, "ns"),
+    ];
+    let mut text = segments
+        .iter()
+        .filter(|(value, _)| *value != 0.0)
+        .map(|(value, unit)| format!("{value}{unit}"))
+        .collect::<String>();
+    if text.is_empty() {
+        text = "0s".to_string();
+    }
+    let sign = if time.is_negative() { "-" } else { "" };
+    format!("T#{sign}{text}")
+}
+
+fn direct_access_type(access: &DirectAccessType) -> &'static str {
+    match access {
+        DirectAccessType::Bit => "X",
+        DirectAccessType::Byte => "B",
+        DirectAccessType::Word => "W",
+        DirectAccessType::DWord => "D",
+        DirectAccessType::LWord => "L",
+        DirectAccessType::Template => "*",
+    }
+}
+
+/// returns the type behind an `AT` declaration, which the parser represents as an alias pointer
+fn aliased_type(declaration: &DataTypeDeclaration) -> Option<&DataTypeDeclaration> {
+    let DataTypeDeclaration::Definition { data_type, .. } = declaration else { return None };
+    match data_type.as_ref() {
+        DataType::PointerType { referenced_type, kind: PointerKind::Alias, .. } => Some(referenced_type),
+        _ => None,
+    }
+}
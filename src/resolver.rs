@@ -1771,6 +1771,14 @@ impl<'i> TypeAnnotator<'i> {
                         });
                         stmt.else_block.iter().for_each(|s| self.visit_statement(ctx, s));
                     }
+                    AstControlStatement::Try(stmt) => {
+                        stmt.body.iter().for_each(|s| self.visit_statement(ctx, s));
+                        if let Some(variable) = &stmt.catch_variable {
+                            self.visit_statement(ctx, variable);
+                        }
+                        stmt.catch_body.iter().for_each(|s| self.visit_statement(ctx, s));
+                        stmt.finally_body.iter().for_each(|s| self.visit_statement(ctx, s));
+                    }
                 }
             }
 
@@ -1994,10 +2002,13 @@ impl<'i> TypeAnnotator<'i> {
             AstStatement::ReferenceExpr(data, ..) => {
                 self.visit_reference_expr(&data.access, data.base.as_deref(), statement, ctx);
             }
-            AstStatement::ReturnStatement(ReturnStatement { condition }) => {
+            AstStatement::ReturnStatement(ReturnStatement { condition, value }) => {
                 if let Some(condition) = condition {
                     self.visit_statement(ctx, condition)
                 }
+                if let Some(value) = value {
+                    self.visit_statement(ctx, value)
+                }
             }
             AstStatement::LabelStatement(..) => {
                 if let Some(pou) = ctx.pou {
@@ -393,7 +393,7 @@ impl TypeAnnotator<'_> {
         generics_candidates: FxHashMap<String, Vec<String>>,
     ) -> FxHashMap<String, GenericType> {
         let mut generic_map: FxHashMap<String, GenericType> = FxHashMap::default();
-        for GenericBinding { name, nature } in generics {
+        for GenericBinding { name, nature, .. } in generics {
             let smallest_possible_type =
                 self.index.find_effective_type_info(get_smallest_possible_type(nature));
             //Get the current binding
@@ -19,7 +19,7 @@ pub mod tests {
         index::{self, FxIndexSet, Index},
         lexer,
         lowering::calls::AggregateTypeLowerer,
-        parser,
+        parser::{self, ParseOptions},
         resolver::{
             const_evaluator::evaluate_constants, AnnotationMapImpl, AstAnnotations, Dependency,
             StringLiterals, TypeAnnotator,
@@ -29,10 +29,15 @@ pub mod tests {
     };
 
     pub fn parse(src: &str) -> (CompilationUnit, Vec<Diagnostic>) {
-        parser::parse(
+        parse_with_options(src, ParseOptions::default())
+    }
+
+    pub fn parse_with_options(src: &str, options: ParseOptions) -> (CompilationUnit, Vec<Diagnostic>) {
+        parser::parse_with_options(
             lexer::lex_with_ids(src, IdProvider::default(), SourceLocationFactory::internal(src)),
             LinkageType::Internal,
             "test.st",
+            options,
         )
     }
 
@@ -7,8 +7,9 @@ use std::{
 
 use anyhow::{anyhow, Result};
 use plc_ast::{
-    ast::{AstNode, AutoDerefType, Operator, PouType, TypeNature},
+    ast::{AstNode, AstStatement, AutoDerefType, DataTypeDeclaration, Operator, PouType, TypeNature},
     literals::{AstLiteral, StringValue},
+    mut_visitor::{AstVisitorMut, WalkerMut},
     provider::IdProvider,
 };
 use plc_source::source_location::SourceLocation;
@@ -1507,3 +1508,161 @@ pub fn get_literal_actual_signed_type_name(lit: &AstLiteral, signed: bool) -> Op
         _ => None,
     }
 }
+
+/// compares the shape of two declared data types, i.e. their kind, referenced types and bounds, while
+/// ignoring source locations, scopes and the name of the declared type itself. Type names are compared
+/// case-insensitively. Bounds and sizes are compared by their expression without parentheses, so
+/// `ARRAY[0..(5)] OF INT` equals `ARRAY[0 .. 5] OF INT`
+pub fn structurally_equal(a: &DataTypeDeclaration, b: &DataTypeDeclaration) -> bool {
+    match (a, b) {
+        (
+            DataTypeDeclaration::Reference { referenced_type: a, .. },
+            DataTypeDeclaration::Reference { referenced_type: b, .. },
+        )
+        | (
+            DataTypeDeclaration::Aggregate { referenced_type: a, .. },
+            DataTypeDeclaration::Aggregate { referenced_type: b, .. },
+        ) => a.eq_ignore_ascii_case(b),
+        (
+            DataTypeDeclaration::Definition { data_type: a, .. },
+            DataTypeDeclaration::Definition { data_type: b, .. },
+        ) => data_types_structurally_equal(a, b),
+        _ => false,
+    }
+}
+
+fn data_types_structurally_equal(a: &plc_ast::ast::DataType, b: &plc_ast::ast::DataType) -> bool {
+    use plc_ast::ast::DataType;
+
+    let names_equal = |a: &Option<String>, b: &Option<String>| match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        (a, b) => a.is_none() && b.is_none(),
+    };
+    let optional_expressions_equal = |a: &Option<AstNode>, b: &Option<AstNode>| match (a, b) {
+        (Some(a), Some(b)) => expressions_equal(a, b),
+        (a, b) => a.is_none() && b.is_none(),
+    };
+
+    match (a, b) {
+        (DataType::StructType { variables: a, .. }, DataType::StructType { variables: b, .. }) => {
+            a.len() == b.len()
+                && a.iter().zip(b).all(|(a, b)| {
+                    a.name.eq_ignore_ascii_case(&b.name)
+                        && structurally_equal(&a.data_type_declaration, &b.data_type_declaration)
+                        && optional_expressions_equal(&a.initializer, &b.initializer)
+                })
+        }
+        (
+            DataType::EnumType { numeric_type: a_type, elements: a_elements, .. },
+            DataType::EnumType { numeric_type: b_type, elements: b_elements, .. },
+        ) => a_type.eq_ignore_ascii_case(b_type) && expressions_equal(a_elements, b_elements),
+        (
+            DataType::SubRangeType { referenced_type: a_type, bounds: a_bounds, .. },
+            DataType::SubRangeType { referenced_type: b_type, bounds: b_bounds, .. },
+        ) => a_type.eq_ignore_ascii_case(b_type) && optional_expressions_equal(a_bounds, b_bounds),
+        (
+            DataType::ArrayType {
+                bounds: a_bounds,
+                referenced_type: a_type,
+                is_variable_length: a_is_variable_length,
+                ..
+            },
+            DataType::ArrayType {
+                bounds: b_bounds,
+                referenced_type: b_type,
+                is_variable_length: b_is_variable_length,
+                ..
+            },
+        ) => {
+            a_is_variable_length == b_is_variable_length
+                && expressions_equal(a_bounds, b_bounds)
+                && structurally_equal(a_type, b_type)
+        }
+        (
+            DataType::PointerType {
+                referenced_type: a_type,
+                auto_deref: a_auto_deref,
+                type_safe: a_type_safe,
+                is_function: a_is_function,
+                kind: a_kind,
+                nullability: a_nullability,
+                ..
+            },
+            DataType::PointerType {
+                referenced_type: b_type,
+                auto_deref: b_auto_deref,
+                type_safe: b_type_safe,
+                is_function: b_is_function,
+                kind: b_kind,
+                nullability: b_nullability,
+                ..
+            },
+        ) => {
+            a_auto_deref == b_auto_deref
+                && a_type_safe == b_type_safe
+                && a_is_function == b_is_function
+                && a_kind == b_kind
+                && a_nullability == b_nullability
+                && structurally_equal(a_type, b_type)
+        }
+        (
+            DataType::StringType { is_wide: a_is_wide, size: a_size, encoding: a_encoding, .. },
+            DataType::StringType { is_wide: b_is_wide, size: b_size, encoding: b_encoding, .. },
+        ) => {
+            a_is_wide == b_is_wide
+                && optional_expressions_equal(a_size, b_size)
+                && names_equal(a_encoding, b_encoding)
+        }
+        (
+            DataType::VarArgs { referenced_type: a_type, sized: a_sized },
+            DataType::VarArgs { referenced_type: b_type, sized: b_sized },
+        ) => {
+            a_sized == b_sized
+                && match (a_type, b_type) {
+                    (Some(a), Some(b)) => structurally_equal(a, b),
+                    (a, b) => a.is_none() && b.is_none(),
+                }
+        }
+        (
+            DataType::GenericType { generic_symbol: a_symbol, nature: a_nature, .. },
+            DataType::GenericType { generic_symbol: b_symbol, nature: b_nature, .. },
+        ) => a_symbol.eq_ignore_ascii_case(b_symbol) && a_nature == b_nature,
+        _ => false,
+    }
+}
+
+/// compares two expressions without their parentheses, ignoring their ids and locations
+fn expressions_equal(a: &AstNode, b: &AstNode) -> bool {
+    fn normalize(node: &AstNode) -> AstNode {
+        let mut node = node.clone();
+        ParenthesesRemover.visit(&mut node);
+        IdAndLocationRemover.visit(&mut node);
+        node
+    }
+
+    normalize(a) == normalize(b)
+}
+
+/// replaces every parenthesized expression with the expression it contains
+struct ParenthesesRemover;
+
+impl AstVisitorMut for ParenthesesRemover {
+    fn visit_paren_expression(&mut self, node: &mut AstNode) {
+        let AstStatement::ParenExpression(inner) = node.get_stmt_mut() else {
+            unreachable!("Must be ParenExpression");
+        };
+        inner.walk(self);
+        *node = std::mem::take(inner.as_mut());
+    }
+}
+
+/// resets the id and the location of every node, so nodes parsed from different positions compare equal
+struct IdAndLocationRemover;
+
+impl AstVisitorMut for IdAndLocationRemover {
+    fn visit(&mut self, node: &mut AstNode) {
+        node.id = 0;
+        node.location = SourceLocation::undefined();
+        node.walk(self)
+    }
+}
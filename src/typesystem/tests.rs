@@ -3,12 +3,12 @@ use plc_source::source_location::SourceLocation;
 
 use crate::{
     index::Index,
-    test_utils::tests::index,
+    test_utils::tests::{index, parse},
     typesystem::{
-        self, get_equals_function_name_for, get_signed_type, Dimension, BOOL_TYPE, BYTE_TYPE, CHAR_TYPE,
-        DATE_AND_TIME_TYPE, DATE_TYPE, DINT_TYPE, DWORD_TYPE, INT_TYPE, LINT_TYPE, LREAL_TYPE, LWORD_TYPE,
-        REAL_TYPE, SINT_TYPE, STRING_TYPE, TIME_OF_DAY_TYPE, TIME_TYPE, UDINT_TYPE, UINT_TYPE, ULINT_TYPE,
-        USINT_TYPE, WCHAR_TYPE, WORD_TYPE, WSTRING_TYPE,
+        self, get_equals_function_name_for, get_signed_type, structurally_equal, Dimension, BOOL_TYPE,
+        BYTE_TYPE, CHAR_TYPE, DATE_AND_TIME_TYPE, DATE_TYPE, DINT_TYPE, DWORD_TYPE, INT_TYPE, LINT_TYPE,
+        LREAL_TYPE, LWORD_TYPE, REAL_TYPE, SINT_TYPE, STRING_TYPE, TIME_OF_DAY_TYPE, TIME_TYPE, UDINT_TYPE,
+        UINT_TYPE, ULINT_TYPE, USINT_TYPE, WCHAR_TYPE, WORD_TYPE, WSTRING_TYPE,
     },
 };
 
@@ -605,3 +605,44 @@ fn array_size_nested_tests() {
     //the size of the array is 20*size(int)
     assert_eq!(6400, nested_array.get_type_information().get_size_in_bits(&index).unwrap());
 }
+
+#[test]
+fn structurally_equal_declarations_ignore_locations_and_parentheses() {
+    let (unit, diagnostics) = parse(
+        "
+        VAR_GLOBAL
+            a : ARRAY[0..5] OF INT;
+            b : ARRAY[0 .. 5] OF INT;
+            c : ARRAY[(0)..(5)] OF INT;
+            d : ARRAY[0..6] OF INT;
+            e : ARRAY[0..5] OF DINT;
+            f : ARRAY[0..5, 0..1] OF INT;
+            g : array[0..5] of int;
+            h : REF_TO ARRAY[0..(5)] OF INT;
+            i : POINTER TO ARRAY[0..5] OF INT;
+            j : REF_TO ARRAY[0..5] OF INT;
+        END_VAR
+        ",
+    );
+    assert!(diagnostics.is_empty(), "{diagnostics:#?}");
+    let declarations =
+        unit.global_vars[0].variables.iter().map(|it| &it.data_type_declaration).collect::<Vec<_>>();
+
+    // the same bounds, also if they are written differently
+    assert!(structurally_equal(declarations[0], declarations[0]));
+    assert!(structurally_equal(declarations[0], declarations[1]));
+    assert!(structurally_equal(declarations[0], declarations[2]));
+
+    // different bounds, element types or dimensions
+    assert!(!structurally_equal(declarations[0], declarations[3]));
+    assert!(!structurally_equal(declarations[0], declarations[4]));
+    assert!(!structurally_equal(declarations[0], declarations[5]));
+
+    // type names are case-insensitive
+    assert!(structurally_equal(declarations[0], declarations[6]));
+
+    // nested types are compared by their kind and their own shape
+    assert!(structurally_equal(declarations[7], declarations[9]));
+    assert!(!structurally_equal(declarations[7], declarations[8]));
+    assert!(!structurally_equal(declarations[0], declarations[7]));
+}
@@ -15,6 +15,7 @@ use crate::resolver::{AnnotationMap, StatementAnnotation};
 pub fn visit_pou<T: AnnotationMap>(validator: &mut Validator, pou: &Pou, context: &ValidationContext<'_, T>) {
     if pou.linkage != LinkageType::External {
         validate_pou(validator, pou);
+        validate_generic_constraints(validator, pou, context);
         validate_interface_impl(validator, context, pou);
         validate_base_class(validator, context, pou);
         validate_methods_overrides(validator, context, pou.id, &pou.name, &pou.name_location);
@@ -178,8 +179,9 @@ where
         return;
     }
 
-    // Check if the interfaces are implemented on the correct POU types
-    if !matches!(pou.kind, PouType::FunctionBlock | PouType::Class) {
+    // Check if the interfaces are implemented on the correct POU types, functions and programs are already
+    // reported by the parser
+    if !matches!(pou.kind, PouType::FunctionBlock | PouType::Class | PouType::Function | PouType::Program) {
         let location = {
             let location_first = pou.interfaces.first().unwrap();
             let location_last = pou.interfaces.last().unwrap();
@@ -264,6 +266,20 @@ fn validate_pou(validator: &mut Validator, pou: &Pou) {
     }
 }
 
+/// reports the types listed in a generic constraint which do not exist, e.g. `Undefined` in
+/// `FUNCTION foo<T : {INT, Undefined}>`
+fn validate_generic_constraints<T: AnnotationMap>(
+    validator: &mut Validator,
+    pou: &Pou,
+    context: &ValidationContext<'_, T>,
+) {
+    for type_name in pou.generics.iter().flat_map(|it| &it.constraint_types) {
+        if context.index.find_effective_type_by_name(type_name).is_none() {
+            validator.push_diagnostic(Diagnostic::unknown_type(type_name, &pou.name_location));
+        }
+    }
+}
+
 fn validate_class(validator: &mut Validator, pou: &Pou) {
     // var in/out/inout blocks are not allowed inside of class declaration
     // TODO: This should be on each block
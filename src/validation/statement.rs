@@ -8,7 +8,7 @@ use plc_ast::{
         flatten_expression_list, AstNode, AstStatement, BinaryExpression, CallStatement, DirectAccess,
         DirectAccessType, JumpStatement, Operator, ReferenceAccess, UnaryExpression,
     },
-    control_statements::{AstControlStatement, ConditionalBlock},
+    control_statements::{AstControlStatement, ConditionalBlock, ReturnStatement},
     literals::{Array, AstLiteral, StringValue},
 };
 use plc_diagnostics::diagnostics::Diagnostic;
@@ -129,7 +129,14 @@ pub fn visit_statement<T: AnnotationMap>(
         }
         // AstStatement::ExitStatement { location, id } => (),
         // AstStatement::ContinueStatement { location, id } => (),
-        // AstStatement::ReturnStatement { location, id } => (),
+        AstStatement::ReturnStatement(ReturnStatement { value: Some(value), .. }) => {
+            visit_statement(validator, value, context);
+            validator.push_diagnostic(
+                Diagnostic::new("RETURN cannot return a value, assign it to the return variable instead")
+                    .with_error_code("E152")
+                    .with_location(value.as_ref()),
+            );
+        }
         // AstStatement::LiteralNull { location, id } => (),
         AstStatement::ParenExpression(expr) => visit_statement(validator, expr, context),
         AstStatement::Super(_) => {
@@ -419,6 +426,14 @@ fn validate_control_statement<T: AnnotationMap>(
         AstControlStatement::Case(stmt) => {
             validate_case_statement(validator, &stmt.selector, &stmt.case_blocks, &stmt.else_block, context);
         }
+        AstControlStatement::Try(stmt) => {
+            stmt.body.iter().for_each(|s| visit_statement(validator, s, context));
+            if let Some(variable) = &stmt.catch_variable {
+                visit_statement(validator, variable, context);
+            }
+            stmt.catch_body.iter().for_each(|s| visit_statement(validator, s, context));
+            stmt.finally_body.iter().for_each(|s| visit_statement(validator, s, context));
+        }
     }
 }
 
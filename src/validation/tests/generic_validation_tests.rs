@@ -59,6 +59,24 @@ fn non_resolved_generics_reported() {
     assert_snapshot!(&diagnostics);
 }
 
+#[test]
+fn unknown_types_in_generic_constraints_are_reported() {
+    let src = r"
+        TYPE MyStruct : STRUCT x : INT; END_STRUCT END_TYPE
+        FUNCTION test<T : ANY {INT, MyStruct, Undefined}> : INT VAR_INPUT x : T; END_VAR END_FUNCTION
+    ";
+
+    let diagnostics = parse_and_validate_buffered(src);
+    assert_snapshot!(diagnostics, @r###"
+    error[E052]: Unknown type: Undefined
+      ┌─ <internal>:3:18
+      │
+    3 │         FUNCTION test<T : ANY {INT, MyStruct, Undefined}> : INT VAR_INPUT x : T; END_VAR END_FUNCTION
+      │                  ^^^^ Unknown type: Undefined
+
+    "###);
+}
+
 // ##########    ANY_MAGNITUDE    ##########
 
 #[test]
@@ -57,17 +57,17 @@ fn not_supported_pou_type_implements_interface() {
 
     let diagnostics = parse_and_validate_buffered(source);
     insta::assert_snapshot!(diagnostics, @r###"
-    error[E110]: Interfaces can only be implemented by `CLASS` or `FUNCTION_BLOCK`
-       ┌─ <internal>:10:36
+    error[E110]: PROGRAM cannot implement interfaces
+       ┌─ <internal>:10:25
        │
     10 │         PROGRAM     baz IMPLEMENTS interfaceA            /* ... */ END_PROGRAM
-       │                                    ^^^^^^^^^^ Interfaces can only be implemented by `CLASS` or `FUNCTION_BLOCK`
+       │                         ^^^^^^^^^^ PROGRAM cannot implement interfaces
 
-    error[E110]: Interfaces can only be implemented by `CLASS` or `FUNCTION_BLOCK`
-       ┌─ <internal>:11:36
+    error[E110]: FUNCTION cannot implement interfaces
+       ┌─ <internal>:11:25
        │
     11 │         FUNCTION    qux IMPLEMENTS interfaceA, interfaceB /* ... */ END_FUNCTION
-       │                                    ^^^^^^^^^^^^^^^^^^^^^^ Interfaces can only be implemented by `CLASS` or `FUNCTION_BLOCK`
+       │                         ^^^^^^^^^^ FUNCTION cannot implement interfaces
     "###);
 }
 
@@ -2555,3 +2555,25 @@ fn unresolved_qualified_reference_in_array_access() {
        │                    ^^^ Could not resolve reference to bar
     "###);
 }
+
+#[test]
+fn return_with_a_value_is_reported() {
+    let diagnostics = parse_and_validate_buffered(
+        "
+        FUNCTION foo : INT
+        VAR_INPUT
+            a, b : INT;
+        END_VAR
+            RETURN a + b;
+        END_FUNCTION
+        ",
+    );
+
+    assert_snapshot!(diagnostics, @r###"
+    error[E152]: RETURN cannot return a value, assign it to the return variable instead
+      ┌─ <internal>:6:20
+      │
+    6 │             RETURN a + b;
+      │                    ^^^^^ RETURN cannot return a value, assign it to the return variable instead
+    "###);
+}
@@ -142,6 +142,32 @@ fn constant_fb_instances_are_illegal() {
     assert_snapshot!(&diagnostics);
 }
 
+#[test]
+fn constant_fb_instances_without_initializer_are_not_reported_as_missing_an_initializer() {
+    // GIVEN a constant FB instance without an initializer
+    // WHEN it is validated
+    let diagnostics = parse_and_validate_buffered(
+        "
+        FUNCTION_BLOCK MyFb
+            ;
+        END_FUNCTION_BLOCK
+
+        VAR_GLOBAL CONSTANT
+            instance : MyFb;
+        END_VAR
+      ",
+    );
+
+    // THEN only the illegal constant is reported, E128 only covers pointers
+    assert_snapshot!(diagnostics, @r###"
+    error[E035]: Invalid constant instance, FUNCTION_BLOCK- and CLASS-instances cannot be declared constant
+      ┌─ <internal>:7:13
+      │
+    7 │             instance : MyFb;
+      │             ^^^^^^^^ Invalid constant instance, FUNCTION_BLOCK- and CLASS-instances cannot be declared constant
+    "###);
+}
+
 #[test]
 fn sized_varargs_require_type() {
     // GIVEN a function with a untyped sized variadic argument